@@ -0,0 +1,357 @@
+// Copyright 2021 Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The distributor hands the airdrops straight to the bSei and stSei holders,
+//! instead of swapping them to the reward denom. For each stage, the owner funds
+//! the stage as it posts the root of a merkle tree computed off-chain from the
+//! `basset_sei_reward` `Holders` and the stSei balances. A leaf is the sha256 of
+//! the holder address followed by its amount, and the pairs of hashes are
+//! sorted before being hashed together.
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    attr, coins, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use sha2::{Digest, Sha256};
+
+use crate::msg::{
+    AirdropAsset, ConfigResponse, ExecuteMsg, InstantiateMsg, IsClaimedResponse,
+    LatestStageResponse, QueryMsg, StageResponse,
+};
+use crate::state::{Config, Stage, CLAIMS, CONFIG, LATEST_STAGE, STAGES};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let owner = msg.owner.unwrap_or_else(|| info.sender.to_string());
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_canonicalize(&owner)?,
+        },
+    )?;
+    LATEST_STAGE.save(deps.storage, &0)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, info, owner),
+        ExecuteMsg::RegisterMerkleRoot {
+            merkle_root,
+            asset,
+            total_amount,
+            expiration,
+        } => execute_register_merkle_root(
+            deps,
+            env,
+            info,
+            merkle_root,
+            asset,
+            total_amount,
+            expiration,
+        ),
+        ExecuteMsg::Claim {
+            stage,
+            amount,
+            proof,
+        } => execute_claim(deps, env, info, stage, amount, proof),
+        ExecuteMsg::Clawback { stage, recipient } => {
+            execute_clawback(deps, env, info, stage, recipient)
+        }
+    }
+}
+
+/// Only creator/owner is allowed to execute
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+) -> StdResult<Response> {
+    let mut config = check_owner(deps.as_ref(), &info)?;
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_canonicalize(&owner)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "update_config")]))
+}
+
+/// Open the next stage, funded with its `total_amount` of the asset.
+/// A native asset is sent along with the call, a cw20 one is transferred from
+/// the owner, who must have approved the allowance.
+/// Only creator/owner is allowed to execute
+pub fn execute_register_merkle_root(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    merkle_root: String,
+    asset: AirdropAsset,
+    total_amount: Uint128,
+    expiration: u64,
+) -> StdResult<Response> {
+    check_owner(deps.as_ref(), &info)?;
+
+    decode_hash(&merkle_root)?;
+    if expiration <= env.block.time.seconds() {
+        return Err(StdError::generic_err("expiration must be in the future"));
+    }
+    if total_amount.is_zero() {
+        return Err(StdError::generic_err("total_amount must be positive"));
+    }
+    let funding_msg = match &asset {
+        AirdropAsset::Native { denom } => {
+            if info.funds != coins(total_amount.u128(), denom) {
+                return Err(StdError::generic_err(format!(
+                    "The stage must be funded with {}{}",
+                    total_amount, denom
+                )));
+            }
+            None
+        }
+        AirdropAsset::Cw20 { contract_addr } => {
+            if !info.funds.is_empty() {
+                return Err(StdError::generic_err("A cw20 stage takes no native funds"));
+            }
+            Some(WasmMsg::Execute {
+                contract_addr: deps.api.addr_validate(contract_addr)?.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: info.sender.to_string(),
+                    recipient: env.contract.address.to_string(),
+                    amount: total_amount,
+                })?,
+                funds: vec![],
+            })
+        }
+    };
+
+    let stage = LATEST_STAGE
+        .load(deps.storage)?
+        .checked_add(1)
+        .ok_or_else(|| StdError::generic_err("No stage left to register"))?;
+    STAGES.save(
+        deps.storage,
+        stage,
+        &Stage {
+            merkle_root: merkle_root.clone(),
+            asset,
+            total_amount,
+            claimed_amount: Uint128::zero(),
+            expiration,
+            clawed_back: false,
+        },
+    )?;
+    LATEST_STAGE.save(deps.storage, &stage)?;
+
+    Ok(Response::new().add_messages(funding_msg).add_attributes(vec![
+        attr("action", "register_merkle_root"),
+        attr("stage", stage.to_string()),
+        attr("merkle_root", merkle_root),
+        attr("total_amount", total_amount),
+    ]))
+}
+
+/// Claim the amount of the sender's leaf.
+/// Each address can only claim once per stage
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage: u8,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> StdResult<Response> {
+    let mut stage_info = load_stage(deps.as_ref(), stage)?;
+    if stage_info.clawed_back || env.block.time.seconds() >= stage_info.expiration {
+        return Err(StdError::generic_err(format!("Stage {} has expired", stage)));
+    }
+    if CLAIMS.has(deps.storage, (stage, &info.sender)) {
+        return Err(StdError::generic_err(format!(
+            "{} has already claimed stage {}",
+            info.sender, stage
+        )));
+    }
+
+    verify_proof(&stage_info.merkle_root, &info.sender, amount, &proof)?;
+
+    stage_info.claimed_amount += amount;
+    if stage_info.claimed_amount > stage_info.total_amount {
+        return Err(StdError::generic_err(format!(
+            "The claims exceed the total amount of stage {}",
+            stage
+        )));
+    }
+    STAGES.save(deps.storage, stage, &stage_info)?;
+    CLAIMS.save(deps.storage, (stage, &info.sender), &amount)?;
+
+    Ok(Response::new()
+        .add_message(send_asset(&stage_info.asset, &info.sender, amount)?)
+        .add_attributes(vec![
+            attr("action", "claim"),
+            attr("stage", stage.to_string()),
+            attr("address", info.sender),
+            attr("amount", amount),
+        ]))
+}
+
+/// Send the unclaimed amount of an expired stage back.
+/// Only creator/owner is allowed to execute
+pub fn execute_clawback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage: u8,
+    recipient: Option<String>,
+) -> StdResult<Response> {
+    let config = check_owner(deps.as_ref(), &info)?;
+
+    let mut stage_info = load_stage(deps.as_ref(), stage)?;
+    if env.block.time.seconds() < stage_info.expiration {
+        return Err(StdError::generic_err(format!(
+            "Stage {} expires at {}",
+            stage, stage_info.expiration
+        )));
+    }
+    if stage_info.clawed_back {
+        return Err(StdError::generic_err(format!(
+            "Stage {} has already been clawed back",
+            stage
+        )));
+    }
+
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => deps.api.addr_humanize(&config.owner)?,
+    };
+    let amount = stage_info.total_amount.checked_sub(stage_info.claimed_amount)?;
+    stage_info.clawed_back = true;
+    STAGES.save(deps.storage, stage, &stage_info)?;
+
+    let mut response = Response::new();
+    if !amount.is_zero() {
+        response = response.add_message(send_asset(&stage_info.asset, &recipient, amount)?);
+    }
+    Ok(response.add_attributes(vec![
+        attr("action", "clawback"),
+        attr("stage", stage.to_string()),
+        attr("recipient", recipient),
+        attr("amount", amount),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::LatestStage {} => to_json_binary(&LatestStageResponse {
+            latest_stage: LATEST_STAGE.load(deps.storage)?,
+        }),
+        QueryMsg::Stage { stage } => to_json_binary(&query_stage(deps, stage)?),
+        QueryMsg::IsClaimed { stage, address } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_json_binary(&IsClaimedResponse {
+                is_claimed: CLAIMS.has(deps.storage, (stage, &address)),
+            })
+        }
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: deps.api.addr_humanize(&config.owner)?.to_string(),
+    })
+}
+
+fn query_stage(deps: Deps, stage: u8) -> StdResult<StageResponse> {
+    let stage_info = load_stage(deps, stage)?;
+    Ok(StageResponse {
+        stage,
+        merkle_root: stage_info.merkle_root,
+        asset: stage_info.asset,
+        total_amount: stage_info.total_amount,
+        claimed_amount: stage_info.claimed_amount,
+        expiration: stage_info.expiration,
+        clawed_back: stage_info.clawed_back,
+    })
+}
+
+fn check_owner(deps: Deps, info: &MessageInfo) -> StdResult<Config> {
+    let config = CONFIG.load(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    Ok(config)
+}
+
+fn load_stage(deps: Deps, stage: u8) -> StdResult<Stage> {
+    STAGES
+        .may_load(deps.storage, stage)?
+        .ok_or_else(|| StdError::generic_err(format!("Stage {} is not registered", stage)))
+}
+
+fn decode_hash(hash: &str) -> StdResult<[u8; 32]> {
+    let mut buf = [0u8; 32];
+    hex::decode_to_slice(hash, &mut buf)
+        .map_err(|_| StdError::generic_err(format!("Invalid sha256 hash: {}", hash)))?;
+    Ok(buf)
+}
+
+fn verify_proof(
+    merkle_root: &str,
+    address: &Addr,
+    amount: Uint128,
+    proof: &[String],
+) -> StdResult<()> {
+    let leaf = format!("{}{}", address, amount);
+    let mut hash: [u8; 32] = Sha256::digest(leaf.as_bytes()).into();
+    for sibling in proof {
+        let mut pair = [hash, decode_hash(sibling)?];
+        pair.sort_unstable();
+        hash = Sha256::digest(pair.concat()).into();
+    }
+
+    if hash != decode_hash(merkle_root)? {
+        return Err(StdError::generic_err("Verification of the merkle proof failed"));
+    }
+    Ok(())
+}
+
+fn send_asset(asset: &AirdropAsset, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match asset {
+        AirdropAsset::Cw20 { contract_addr } => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }),
+        AirdropAsset::Native { denom } => CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), denom),
+        }),
+    })
+}
@@ -0,0 +1,98 @@
+// Copyright 2021 Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Defaults to the sender
+    pub owner: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Update config
+    UpdateConfig { owner: Option<String> },
+
+    /// Open a new stage distributing `total_amount` of `asset` along the merkle tree.
+    /// A native `total_amount` must be sent along; a cw20 one is transferred from
+    /// the sender, who must have approved the allowance.
+    RegisterMerkleRoot {
+        /// hex encoded root of the tree of sha256(address + amount) leaves
+        merkle_root: String,
+        asset: AirdropAsset,
+        total_amount: Uint128,
+        /// time in seconds after which the stage can no longer be claimed
+        expiration: u64,
+    },
+
+    /// Claim the `amount` allotted to the sender in the stage
+    Claim {
+        stage: u8,
+        amount: Uint128,
+        /// hex encoded sibling hashes from the leaf to the root
+        proof: Vec<String>,
+    },
+
+    /// Send the unclaimed tokens of an expired stage to the `recipient` (defaults to the owner)
+    Clawback {
+        stage: u8,
+        recipient: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AirdropAsset {
+    Cw20 { contract_addr: String },
+    Native { denom: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    LatestStage {},
+    Stage { stage: u8 },
+    IsClaimed { stage: u8, address: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LatestStageResponse {
+    pub latest_stage: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StageResponse {
+    pub stage: u8,
+    pub merkle_root: String,
+    pub asset: AirdropAsset,
+    pub total_amount: Uint128,
+    pub claimed_amount: Uint128,
+    pub expiration: u64,
+    pub clawed_back: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsClaimedResponse {
+    pub is_claimed: bool,
+}
@@ -0,0 +1,43 @@
+// Copyright 2021 Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::msg::AirdropAsset;
+use cosmwasm_std::{Addr, CanonicalAddr, Uint128};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub static CONFIG: Item<Config> = Item::new("config");
+
+pub static LATEST_STAGE: Item<u8> = Item::new("latest_stage");
+
+pub static STAGES: Map<u8, Stage> = Map::new("stages");
+
+/// The amount claimed by each address of each stage
+pub static CLAIMS: Map<(u8, &Addr), Uint128> = Map::new("claims");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: CanonicalAddr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Stage {
+    pub merkle_root: String,
+    pub asset: AirdropAsset,
+    pub total_amount: Uint128,
+    pub claimed_amount: Uint128,
+    pub expiration: u64,
+    pub clawed_back: bool,
+}
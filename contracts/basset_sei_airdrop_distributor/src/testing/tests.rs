@@ -0,0 +1,286 @@
+// Copyright 2021 Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::contract::{execute, instantiate, query};
+use crate::msg::{
+    AirdropAsset, ExecuteMsg, InstantiateMsg, IsClaimedResponse, LatestStageResponse, QueryMsg,
+    StageResponse,
+};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, BankMsg, CosmosMsg, Env, OwnedDeps, StdError, Uint128,
+    WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use sha2::{Digest, Sha256};
+
+fn leaf(address: &str, amount: u128) -> [u8; 32] {
+    Sha256::digest(format!("{}{}", address, amount).as_bytes()).into()
+}
+
+fn parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let mut pair = [a, b];
+    pair.sort_unstable();
+    Sha256::digest(pair.concat()).into()
+}
+
+/// The tree of alice (100), bob (200) and carol (300), carol being promoted to the second level
+fn sample_tree() -> (String, Vec<String>, Vec<String>) {
+    let (alice, bob, carol) = (leaf("alice", 100), leaf("bob", 200), leaf("carol", 300));
+    let root = parent(parent(alice, bob), carol);
+    let alice_proof = vec![hex::encode(bob), hex::encode(carol)];
+    let carol_proof = vec![hex::encode(parent(alice, bob))];
+    (hex::encode(root), alice_proof, carol_proof)
+}
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.minus_seconds(env.block.time.seconds()).plus_seconds(seconds);
+    env
+}
+
+fn init(asset: AirdropAsset) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg { owner: None };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let funds = match &asset {
+        AirdropAsset::Native { denom } => coins(600, denom),
+        AirdropAsset::Cw20 { .. } => vec![],
+    };
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: sample_tree().0,
+        asset,
+        total_amount: Uint128::new(600),
+        expiration: 2000,
+    };
+    execute(deps.as_mut(), env_at(1000), mock_info("owner", &funds), msg).unwrap();
+    deps
+}
+
+fn query_stage(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> StageResponse {
+    from_json(query(deps.as_ref(), mock_env(), QueryMsg::Stage { stage: 1 }).unwrap()).unwrap()
+}
+
+#[test]
+fn proper_register_merkle_root() {
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg { owner: None };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let register = |merkle_root: String, expiration: u64| ExecuteMsg::RegisterMerkleRoot {
+        merkle_root,
+        asset: AirdropAsset::Native {
+            denom: String::from("uatom"),
+        },
+        total_amount: Uint128::new(600),
+        expiration,
+    };
+
+    let funds = coins(600, "uatom");
+    let res = execute(
+        deps.as_mut(),
+        env_at(1000),
+        mock_info("alice", &funds),
+        register(sample_tree().0, 2000),
+    );
+    assert_eq!(res.unwrap_err(), StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(1000),
+        mock_info("owner", &funds),
+        register(String::from("root"), 2000),
+    );
+    assert_eq!(res.unwrap_err(), StdError::generic_err("Invalid sha256 hash: root"));
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(1000),
+        mock_info("owner", &funds),
+        register(sample_tree().0, 1000),
+    );
+    assert_eq!(res.unwrap_err(), StdError::generic_err("expiration must be in the future"));
+
+    // the stage must be funded with its total amount
+    let res = execute(
+        deps.as_mut(),
+        env_at(1000),
+        mock_info("owner", &coins(599, "uatom")),
+        register(sample_tree().0, 2000),
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("The stage must be funded with 600uatom")
+    );
+
+    for _ in 0..2 {
+        execute(
+            deps.as_mut(),
+            env_at(1000),
+            mock_info("owner", &funds),
+            register(sample_tree().0, 2000),
+        )
+        .unwrap();
+    }
+
+    // a cw20 stage pulls its total amount from the owner
+    let msg = ExecuteMsg::RegisterMerkleRoot {
+        merkle_root: sample_tree().0,
+        asset: AirdropAsset::Cw20 {
+            contract_addr: String::from("airdrop_token"),
+        },
+        total_amount: Uint128::new(600),
+        expiration: 2000,
+    };
+    let res = execute(deps.as_mut(), env_at(1000), mock_info("owner", &funds), msg.clone());
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("A cw20 stage takes no native funds")
+    );
+    let res = execute(deps.as_mut(), env_at(1000), mock_info("owner", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("airdrop_token"),
+            msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: String::from("owner"),
+                recipient: String::from(MOCK_CONTRACT_ADDR),
+                amount: Uint128::new(600),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let res: LatestStageResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::LatestStage {}).unwrap()).unwrap();
+    assert_eq!(res.latest_stage, 3);
+    assert_eq!(query_stage(&deps).claimed_amount, Uint128::zero());
+}
+
+#[test]
+fn proper_claim() {
+    let mut deps = init(AirdropAsset::Cw20 {
+        contract_addr: String::from("airdrop_token"),
+    });
+    let (_, alice_proof, carol_proof) = sample_tree();
+    let claim = |amount: u128, proof: &Vec<String>| ExecuteMsg::Claim {
+        stage: 1,
+        amount: Uint128::new(amount),
+        proof: proof.clone(),
+    };
+
+    let alice = mock_info("alice", &[]);
+
+    // a wrong amount or a wrong proof does not verify
+    let res = execute(deps.as_mut(), env_at(1500), alice.clone(), claim(200, &alice_proof));
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("Verification of the merkle proof failed")
+    );
+    let res = execute(deps.as_mut(), env_at(1500), mock_info("bob", &[]), claim(200, &alice_proof));
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("Verification of the merkle proof failed")
+    );
+
+    let res = execute(deps.as_mut(), env_at(1500), alice.clone(), claim(100, &alice_proof))
+        .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("airdrop_token"),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: String::from("alice"),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // double-claims are rejected
+    let res = execute(deps.as_mut(), env_at(1500), alice.clone(), claim(100, &alice_proof));
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("alice has already claimed stage 1")
+    );
+
+    let query_msg = QueryMsg::IsClaimed {
+        stage: 1,
+        address: String::from("alice"),
+    };
+    let res: IsClaimedResponse =
+        from_json(query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+    assert!(res.is_claimed);
+
+    execute(deps.as_mut(), env_at(1500), mock_info("carol", &[]), claim(300, &carol_proof))
+        .unwrap();
+    assert_eq!(query_stage(&deps).claimed_amount, Uint128::new(400));
+
+    let res = execute(deps.as_mut(), env_at(2000), mock_info("bob", &[]), claim(200, &vec![]));
+    assert_eq!(res.unwrap_err(), StdError::generic_err("Stage 1 has expired"));
+}
+
+#[test]
+fn proper_clawback() {
+    let mut deps = init(AirdropAsset::Native {
+        denom: String::from("uatom"),
+    });
+    let (_, alice_proof, _) = sample_tree();
+    let msg = ExecuteMsg::Claim {
+        stage: 1,
+        amount: Uint128::new(100),
+        proof: alice_proof,
+    };
+    let res = execute(deps.as_mut(), env_at(1500), mock_info("alice", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("alice"),
+            amount: coins(100, "uatom"),
+        })
+    );
+
+    let clawback = ExecuteMsg::Clawback {
+        stage: 1,
+        recipient: None,
+    };
+    let res = execute(deps.as_mut(), env_at(1999), mock_info("owner", &[]), clawback.clone());
+    assert_eq!(res.unwrap_err(), StdError::generic_err("Stage 1 expires at 2000"));
+
+    let res = execute(deps.as_mut(), env_at(2000), mock_info("alice", &[]), clawback.clone());
+    assert_eq!(res.unwrap_err(), StdError::generic_err("unauthorized"));
+
+    // only the unclaimed part goes back
+    let res = execute(deps.as_mut(), env_at(2000), mock_info("owner", &[]), clawback.clone())
+        .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("owner"),
+            amount: coins(500, "uatom"),
+        })
+    );
+    assert!(query_stage(&deps).clawed_back);
+
+    let res = execute(deps.as_mut(), env_at(2000), mock_info("owner", &[]), clawback);
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("Stage 1 has already been clawed back")
+    );
+}
@@ -0,0 +1,285 @@
+// Copyright 2021 Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The airdrop registry keeps the airdrops of the tokens distributed to the
+//! hub's delegations. The hub forwards the claims of its `UpdateGlobalIndex`
+//! airdrop hooks here, and the registry turns each into a hub `ClaimAirdrop`
+//! whose swap proceeds are sent to the bSei reward contract.
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    attr, to_json_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128, WasmMsg,
+};
+
+use crate::state::{read_airdrop_infos, read_airdrop_tokens, Config, AIRDROP_INFO, CONFIG};
+use basset::airdrop::{
+    AirdropHandleMsg, AirdropInfo, AirdropInfoElem, AirdropInfoResponse, ConfigResponse,
+    ExecuteMsg, InstantiateMsg, PairHandleMsg, QueryMsg,
+};
+use basset::hub::ExecuteMsg::ClaimAirdrop;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_canonicalize(info.sender.as_str())?,
+            hub_contract: deps.api.addr_canonicalize(&msg.hub_contract)?,
+            reward_contract: deps.api.addr_canonicalize(&msg.reward_contract)?,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::FabricateClaim {
+            airdrop_token,
+            stage,
+            amount,
+            proof,
+        } => execute_fabricate_claim(deps, env, info, airdrop_token, stage, amount, proof),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            hub_contract,
+            reward_contract,
+        } => execute_update_config(deps, env, info, owner, hub_contract, reward_contract),
+        ExecuteMsg::AddAirdropInfo {
+            airdrop_token,
+            airdrop_info,
+        } => execute_add_airdrop_info(deps, env, info, airdrop_token, airdrop_info),
+        ExecuteMsg::RemoveAirdropInfo { airdrop_token } => {
+            execute_remove_airdrop_info(deps, env, info, airdrop_token)
+        }
+        ExecuteMsg::UpdateAirdropInfo {
+            airdrop_token,
+            airdrop_info,
+        } => execute_update_airdrop_info(deps, env, info, airdrop_token, airdrop_info),
+    }
+}
+
+/// Claim an airdrop through the hub, which holds the delegations it was given to.
+/// Only the hub (through its airdrop hooks) or the owner is allowed to execute
+pub fn execute_fabricate_claim(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    airdrop_token: String,
+    stage: u8,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if sender_raw != config.hub_contract && sender_raw != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let airdrop_info = load_airdrop_info(deps.as_ref(), &airdrop_token)?;
+    let hub_contract = deps.api.addr_humanize(&config.hub_contract)?;
+    let reward_contract = deps.api.addr_humanize(&config.reward_contract)?;
+
+    let claim_msg: Binary = to_json_binary(&AirdropHandleMsg::Claim {
+        stage,
+        amount,
+        proof,
+    })?;
+    let swap_msg: Binary = to_json_binary(&PairHandleMsg::Swap {
+        belief_price: airdrop_info.swap_belief_price,
+        max_spread: airdrop_info.swap_max_spread,
+        to: Some(reward_contract.to_string()),
+    })?;
+
+    let message = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: hub_contract.to_string(),
+        msg: to_json_binary(&ClaimAirdrop {
+            airdrop_token_contract: airdrop_info.airdrop_token_contract,
+            airdrop_contract: airdrop_info.airdrop_contract,
+            airdrop_swap_contract: airdrop_info.airdrop_swap_contract,
+            claim_msg,
+            swap_msg,
+        })?,
+        funds: vec![],
+    });
+
+    Ok(Response::new().add_message(message).add_attributes(vec![
+        attr("action", "fabricate_claim"),
+        attr("airdrop_token", airdrop_token),
+        attr("stage", stage.to_string()),
+        attr("amount", amount),
+    ]))
+}
+
+/// Update the config. Update the owner, hub and reward contract addresses.
+/// Only creator/owner is allowed to execute
+pub fn execute_update_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    owner: Option<String>,
+    hub_contract: Option<String>,
+    reward_contract: Option<String>,
+) -> StdResult<Response> {
+    let mut config = check_owner(deps.as_ref(), &info)?;
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_canonicalize(&owner)?;
+    }
+    if let Some(hub_contract) = hub_contract {
+        config.hub_contract = deps.api.addr_canonicalize(&hub_contract)?;
+    }
+    if let Some(reward_contract) = reward_contract {
+        config.reward_contract = deps.api.addr_canonicalize(&reward_contract)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "update_config")]))
+}
+
+/// Only creator/owner is allowed to execute
+pub fn execute_add_airdrop_info(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    airdrop_token: String,
+    airdrop_info: AirdropInfo,
+) -> StdResult<Response> {
+    check_owner(deps.as_ref(), &info)?;
+
+    if AIRDROP_INFO.has(deps.storage, &airdrop_token) {
+        return Err(StdError::generic_err(format!(
+            "There is already an airdrop info for {}",
+            airdrop_token
+        )));
+    }
+    validate_airdrop_info(deps.as_ref(), &airdrop_info)?;
+    AIRDROP_INFO.save(deps.storage, &airdrop_token, &airdrop_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_airdrop_info"),
+        attr("airdrop_token", airdrop_token),
+    ]))
+}
+
+/// Only creator/owner is allowed to execute
+pub fn execute_remove_airdrop_info(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    airdrop_token: String,
+) -> StdResult<Response> {
+    check_owner(deps.as_ref(), &info)?;
+
+    load_airdrop_info(deps.as_ref(), &airdrop_token)?;
+    AIRDROP_INFO.remove(deps.storage, &airdrop_token);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_airdrop_info"),
+        attr("airdrop_token", airdrop_token),
+    ]))
+}
+
+/// Only creator/owner is allowed to execute
+pub fn execute_update_airdrop_info(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    airdrop_token: String,
+    airdrop_info: AirdropInfo,
+) -> StdResult<Response> {
+    check_owner(deps.as_ref(), &info)?;
+
+    load_airdrop_info(deps.as_ref(), &airdrop_token)?;
+    validate_airdrop_info(deps.as_ref(), &airdrop_info)?;
+    AIRDROP_INFO.save(deps.storage, &airdrop_token, &airdrop_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_airdrop_info"),
+        attr("airdrop_token", airdrop_token),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::AirdropInfo {
+            airdrop_token,
+            start_after,
+            limit,
+        } => to_json_binary(&query_airdrop_info(deps, airdrop_token, start_after, limit)?),
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: deps.api.addr_humanize(&config.owner)?.to_string(),
+        hub_contract: deps.api.addr_humanize(&config.hub_contract)?.to_string(),
+        reward_contract: deps.api.addr_humanize(&config.reward_contract)?.to_string(),
+        airdrop_tokens: read_airdrop_tokens(deps.storage)?,
+    })
+}
+
+fn query_airdrop_info(
+    deps: Deps,
+    airdrop_token: Option<String>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AirdropInfoResponse> {
+    let airdrop_info = match airdrop_token {
+        Some(airdrop_token) => {
+            let info = load_airdrop_info(deps, &airdrop_token)?;
+            vec![AirdropInfoElem {
+                airdrop_token,
+                info,
+            }]
+        }
+        None => read_airdrop_infos(deps.storage, start_after, limit)?,
+    };
+    Ok(AirdropInfoResponse { airdrop_info })
+}
+
+fn check_owner(deps: Deps, info: &MessageInfo) -> StdResult<Config> {
+    let config = CONFIG.load(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    Ok(config)
+}
+
+fn load_airdrop_info(deps: Deps, airdrop_token: &str) -> StdResult<AirdropInfo> {
+    AIRDROP_INFO
+        .may_load(deps.storage, airdrop_token)?
+        .ok_or_else(|| {
+            StdError::generic_err(format!("There is no airdrop info for {}", airdrop_token))
+        })
+}
+
+fn validate_airdrop_info(deps: Deps, airdrop_info: &AirdropInfo) -> StdResult<()> {
+    deps.api.addr_validate(&airdrop_info.airdrop_token_contract)?;
+    deps.api.addr_validate(&airdrop_info.airdrop_contract)?;
+    deps.api.addr_validate(&airdrop_info.airdrop_swap_contract)?;
+    Ok(())
+}
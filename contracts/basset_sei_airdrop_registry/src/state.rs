@@ -0,0 +1,58 @@
+// Copyright 2021 Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use basset::airdrop::{AirdropInfo, AirdropInfoElem};
+use cosmwasm_std::{CanonicalAddr, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub static CONFIG: Item<Config> = Item::new("config");
+
+pub static AIRDROP_INFO: Map<&str, AirdropInfo> = Map::new("airdrop_info");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: CanonicalAddr,
+    pub hub_contract: CanonicalAddr,
+    pub reward_contract: CanonicalAddr,
+}
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn read_airdrop_tokens(storage: &dyn Storage) -> StdResult<Vec<String>> {
+    AIRDROP_INFO
+        .keys(storage, None, None, Order::Ascending)
+        .collect()
+}
+
+pub fn read_airdrop_infos(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<AirdropInfoElem>> {
+    let lim = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    AIRDROP_INFO
+        .range(storage, start, None, Order::Ascending)
+        .take(lim)
+        .map(|item| {
+            item.map(|(airdrop_token, info)| AirdropInfoElem {
+                airdrop_token,
+                info,
+            })
+        })
+        .collect()
+}
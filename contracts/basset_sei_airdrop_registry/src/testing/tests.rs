@@ -0,0 +1,250 @@
+// Copyright 2021 Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::contract::{execute, instantiate, query};
+use crate::state::CONFIG;
+use basset::airdrop::{
+    AirdropHandleMsg, AirdropInfo, AirdropInfoElem, AirdropInfoResponse, ConfigResponse,
+    ExecuteMsg, InstantiateMsg, PairHandleMsg, QueryMsg,
+};
+use basset::hub::ExecuteMsg::ClaimAirdrop;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier};
+use cosmwasm_std::{
+    from_json, to_json_binary, Api, CosmosMsg, Decimal, MemoryStorage, OwnedDeps, StdError,
+    Uint128, WasmMsg,
+};
+
+fn sample_airdrop_info() -> AirdropInfo {
+    AirdropInfo {
+        airdrop_token_contract: String::from("airdrop_token"),
+        airdrop_contract: String::from("airdrop_contract"),
+        airdrop_swap_contract: String::from("airdrop_pair"),
+        swap_belief_price: None,
+        swap_max_spread: Some(Decimal::percent(1)),
+    }
+}
+
+fn init() -> OwnedDeps<MemoryStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg {
+        hub_contract: String::from("hub"),
+        reward_contract: String::from("reward"),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    deps
+}
+
+#[test]
+fn proper_instantiate() {
+    let deps = init();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.owner, deps.api.addr_canonicalize("owner").unwrap());
+    assert_eq!(config.hub_contract, deps.api.addr_canonicalize("hub").unwrap());
+
+    let res: ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        ConfigResponse {
+            owner: String::from("owner"),
+            hub_contract: String::from("hub"),
+            reward_contract: String::from("reward"),
+            airdrop_tokens: vec![],
+        }
+    );
+}
+
+#[test]
+fn proper_update_config() {
+    let mut deps = init();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(String::from("new_owner")),
+        hub_contract: None,
+        reward_contract: Some(String::from("new_reward")),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("hub", &[]), msg.clone());
+    assert_eq!(res.unwrap_err(), StdError::generic_err("unauthorized"));
+
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let res: ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(res.owner, "new_owner");
+    assert_eq!(res.hub_contract, "hub");
+    assert_eq!(res.reward_contract, "new_reward");
+}
+
+#[test]
+fn proper_airdrop_info() {
+    let mut deps = init();
+    let owner = mock_info("owner", &[]);
+
+    let msg = ExecuteMsg::AddAirdropInfo {
+        airdrop_token: String::from("TOKEN"),
+        airdrop_info: sample_airdrop_info(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("hub", &[]), msg.clone());
+    assert_eq!(res.unwrap_err(), StdError::generic_err("unauthorized"));
+
+    execute(deps.as_mut(), mock_env(), owner.clone(), msg.clone()).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), owner.clone(), msg);
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("There is already an airdrop info for TOKEN")
+    );
+
+    let other_info = AirdropInfo {
+        swap_belief_price: Some(Decimal::percent(50)),
+        ..sample_airdrop_info()
+    };
+    let msg = ExecuteMsg::AddAirdropInfo {
+        airdrop_token: String::from("OTHER"),
+        airdrop_info: other_info.clone(),
+    };
+    execute(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+
+    let res: ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(res.airdrop_tokens, vec!["OTHER", "TOKEN"]);
+
+    let query_msg = QueryMsg::AirdropInfo {
+        airdrop_token: None,
+        start_after: Some(String::from("OTHER")),
+        limit: None,
+    };
+    let res: AirdropInfoResponse =
+        from_json(query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+    assert_eq!(
+        res.airdrop_info,
+        vec![AirdropInfoElem {
+            airdrop_token: String::from("TOKEN"),
+            info: sample_airdrop_info(),
+        }]
+    );
+
+    let updated_info = AirdropInfo {
+        airdrop_swap_contract: String::from("new_pair"),
+        ..sample_airdrop_info()
+    };
+    let msg = ExecuteMsg::UpdateAirdropInfo {
+        airdrop_token: String::from("TOKEN"),
+        airdrop_info: updated_info.clone(),
+    };
+    execute(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+
+    let query_msg = QueryMsg::AirdropInfo {
+        airdrop_token: Some(String::from("TOKEN")),
+        start_after: None,
+        limit: None,
+    };
+    let res: AirdropInfoResponse =
+        from_json(query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+    assert_eq!(res.airdrop_info[0].info, updated_info);
+
+    let msg = ExecuteMsg::RemoveAirdropInfo {
+        airdrop_token: String::from("TOKEN"),
+    };
+    execute(deps.as_mut(), mock_env(), owner.clone(), msg.clone()).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), owner.clone(), msg);
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("There is no airdrop info for TOKEN")
+    );
+
+    let msg = ExecuteMsg::UpdateAirdropInfo {
+        airdrop_token: String::from("TOKEN"),
+        airdrop_info: updated_info,
+    };
+    let res = execute(deps.as_mut(), mock_env(), owner, msg);
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("There is no airdrop info for TOKEN")
+    );
+
+    let query_msg = QueryMsg::AirdropInfo {
+        airdrop_token: None,
+        start_after: None,
+        limit: None,
+    };
+    let res: AirdropInfoResponse =
+        from_json(query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+    assert_eq!(
+        res.airdrop_info,
+        vec![AirdropInfoElem {
+            airdrop_token: String::from("OTHER"),
+            info: other_info,
+        }]
+    );
+}
+
+#[test]
+fn proper_fabricate_claim() {
+    let mut deps = init();
+
+    let msg = ExecuteMsg::AddAirdropInfo {
+        airdrop_token: String::from("TOKEN"),
+        airdrop_info: sample_airdrop_info(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let claim = |airdrop_token: &str| ExecuteMsg::FabricateClaim {
+        airdrop_token: String::from(airdrop_token),
+        stage: 1,
+        amount: Uint128::new(1000),
+        proof: vec![String::from("proof")],
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), claim("TOKEN"));
+    assert_eq!(res.unwrap_err(), StdError::generic_err("unauthorized"));
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("hub", &[]), claim("UNKNOWN"));
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("There is no airdrop info for UNKNOWN")
+    );
+
+    // the hub forwards the claim of its airdrop hooks, and gets the claim back
+    let res = execute(deps.as_mut(), mock_env(), mock_info("hub", &[]), claim("TOKEN")).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: String::from("hub"),
+            msg: to_json_binary(&ClaimAirdrop {
+                airdrop_token_contract: String::from("airdrop_token"),
+                airdrop_contract: String::from("airdrop_contract"),
+                airdrop_swap_contract: String::from("airdrop_pair"),
+                claim_msg: to_json_binary(&AirdropHandleMsg::Claim {
+                    stage: 1,
+                    amount: Uint128::new(1000),
+                    proof: vec![String::from("proof")],
+                })
+                .unwrap(),
+                swap_msg: to_json_binary(&PairHandleMsg::Swap {
+                    belief_price: None,
+                    max_spread: Some(Decimal::percent(1)),
+                    to: Some(String::from("reward")),
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), claim("TOKEN"));
+    assert_eq!(res.unwrap().messages.len(), 1);
+}
@@ -18,9 +18,12 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use basset::hub::{
-    AllHistoryResponse, Config, ConfigResponse, CurrentBatchResponse, ExecuteMsg, InstantiateMsg,
-    MigrateMsg, NewOwnerResponse, Parameters, QueryMsg, State, StateResponse,
-    UnbondRequestsResponse, WithdrawableUnbondedResponse,
+    AllHistoryResponse, BatchRequestsResponse, Config, ConfigResponse, CurrentBatchResponse,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, NewOwnerResponse, NextBatchTimeResponse, Parameters,
+    QueryMsg, SimulateBondResponse, SimulateConvertResponse, SimulateUnbondResponse, State,
+    BatchSlashingResponse, ProposalTallyResponse, ProposalVoteResponse, SlashingEventsResponse,
+    StateResponse, TokenClassesResponse,
+    UnbondRequestsDetailedResponse, UnbondRequestsResponse, WithdrawableUnbondedResponse,
 };
 
 fn main() {
@@ -39,8 +42,19 @@ fn main() {
     export_schema(&schema_for!(StateResponse), &out_dir);
     export_schema(&schema_for!(WithdrawableUnbondedResponse), &out_dir);
     export_schema(&schema_for!(UnbondRequestsResponse), &out_dir);
+    export_schema(&schema_for!(UnbondRequestsDetailedResponse), &out_dir);
     export_schema(&schema_for!(CurrentBatchResponse), &out_dir);
+    export_schema(&schema_for!(NextBatchTimeResponse), &out_dir);
     export_schema(&schema_for!(AllHistoryResponse), &out_dir);
+    export_schema(&schema_for!(BatchRequestsResponse), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
     export_schema(&schema_for!(NewOwnerResponse), &out_dir);
+    export_schema(&schema_for!(SimulateBondResponse), &out_dir);
+    export_schema(&schema_for!(SimulateUnbondResponse), &out_dir);
+    export_schema(&schema_for!(SimulateConvertResponse), &out_dir);
+    export_schema(&schema_for!(TokenClassesResponse), &out_dir);
+    export_schema(&schema_for!(ProposalVoteResponse), &out_dir);
+    export_schema(&schema_for!(ProposalTallyResponse), &out_dir);
+    export_schema(&schema_for!(SlashingEventsResponse), &out_dir);
+    export_schema(&schema_for!(BatchSlashingResponse), &out_dir);
 }
@@ -13,33 +13,37 @@
 // limitations under the License.
 
 use crate::contract::{query_total_bsei_issued, query_total_stsei_issued, slashing};
-use crate::math::decimal_division;
+use crate::convert::check_min_mint_amount;
+use crate::math::{decimal_division, Peg};
 use crate::state::{CONFIG, CURRENT_BATCH, PARAMETERS, STATE};
-use basset::hub::BondType;
+use crate::token_class::split_class_rewards;
+use basset::hub::{BondType, Config, Parameters, State};
 use basset_sei_validators_registry::common::calculate_delegations;
 use basset_sei_validators_registry::msg::QueryMsg as QueryValidators;
 use basset_sei_validators_registry::registry::ValidatorResponse;
 use cosmwasm_std::{
-    attr, to_json_binary, Coin, CosmosMsg, DepsMut, Env, MessageInfo, QueryRequest, Response,
-    StakingMsg, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+    attr, to_json_binary, Addr, Binary, Coin, CosmosMsg, DepsMut, Env, MessageInfo,
+    QueryRequest, Response, StakingMsg, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
 };
 use cw20::Cw20ExecuteMsg;
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_bond(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     bond_type: BondType,
+    recipient: Option<String>,
+    callback: Option<Binary>,
+    min_mint_amount: Option<Uint128>,
 ) -> Result<Response, StdError> {
     let params = PARAMETERS.load(deps.storage)?;
-    let coin_denom = params.underlying_coin_denom;
-    let threshold = params.er_threshold;
-    let recovery_fee = params.peg_recovery_fee;
+    let coin_denom = params.underlying_coin_denom.clone();
     let config = CONFIG.load(deps.storage)?;
 
     let reward_dispatcher_addr =
         deps.api
-            .addr_humanize(&config.reward_dispatcher_contract.ok_or_else(|| {
+            .addr_humanize(&config.reward_dispatcher_contract.clone().ok_or_else(|| {
                 StdError::generic_err("the reward dispatcher contract must have been registered")
             })?)?;
 
@@ -54,26 +58,24 @@ pub fn execute_bond(
         BondType::StSei | BondType::BondRewards => current_batch.requested_stsei,
     };
 
-    // coin must have be sent along with transaction and it should be in underlying coin denom
-    if info.funds.len() > 1usize {
-        return Err(StdError::generic_err(
-            "More than one coin is sent; only one asset is supported",
-        ));
-    }
-  
-    // coin must have be sent along with transaction and it should be in underlying coin denom
-    let payment = info
-        .funds
-        .iter()
-        .find(|x| x.denom == coin_denom && x.amount > Uint128::zero())
-        .ok_or_else(|| {
-            StdError::generic_err(format!("No {} assets are provided to bond", coin_denom))
-        })?;
+    let payment = bonded_coin(&info, &coin_denom)?;
+
+    let sender = info.sender.clone();
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => sender.clone(),
+    };
+    let contract_address = env.contract.address.clone();
 
     // check slashing
-    let state = slashing(&mut deps, env)?;
+    let (state, draw_msg) = slashing(&mut deps, env)?;
 
-    let sender = info.sender.clone();
+    // the rewards are shared among stSei and the token classes
+    let (bond_amount, reward_msgs) = if bond_type == BondType::BondRewards {
+        split_class_rewards(&mut deps, payment.amount)?
+    } else {
+        (payment.amount, vec![])
+    };
 
     // get the total supply
     let mut total_supply = match bond_type {
@@ -84,23 +86,17 @@ pub fn execute_bond(
     };
 
     // peg recovery fee should be considered
-    let mint_amount = match bond_type {
-        BondType::BSei => {
-            let bsei_mint_amount = decimal_division(payment.amount, state.bsei_exchange_rate);
-            let mut mint_amount_with_fee = bsei_mint_amount;
-            if state.bsei_exchange_rate < threshold {
-                let max_peg_fee = bsei_mint_amount * recovery_fee;
-                let required_peg_fee =
-                    (total_supply + bsei_mint_amount + current_batch.requested_bsei_with_fee)
-                        - (state.total_bond_bsei_amount + payment.amount);
-                let peg_fee = Uint128::min(max_peg_fee, required_peg_fee);
-                mint_amount_with_fee = bsei_mint_amount.checked_sub(peg_fee)?;
-            }
-            mint_amount_with_fee
-        }
-        BondType::StSei => decimal_division(payment.amount, state.stsei_exchange_rate),
-        BondType::BondRewards => Uint128::zero(),
-    };
+    let (mint_amount, _peg_fee) = calculate_mint_amount(
+        &state,
+        &params,
+        current_batch.requested_bsei_with_fee,
+        total_supply,
+        &bond_type,
+        payment.amount,
+    )?;
+
+    // The peg fee and the slashing can both lower the minted amount
+    check_min_mint_amount(mint_amount, min_mint_amount)?;
 
     // total supply should be updated for exchange rate calculation.
     total_supply += mint_amount;
@@ -113,11 +109,8 @@ pub fn execute_bond(
                 prev_state.update_bsei_exchange_rate(total_supply, requested_with_fee);
                 Ok(prev_state)
             }
-            BondType::BondRewards => {
-                prev_state.total_bond_stsei_amount += payment.amount;
-                prev_state.update_stsei_exchange_rate(total_supply, requested_with_fee);
-                Ok(prev_state)
-            }
+            // the rewards are already bonded by the split
+            BondType::BondRewards => Ok(prev_state),
             BondType::StSei => {
                 prev_state.total_bond_stsei_amount += payment.amount;
                 Ok(prev_state)
@@ -125,44 +118,15 @@ pub fn execute_bond(
         }
     })?;
 
-    let validators_registry_contract = if let Some(v) = config.validators_registry_contract {
-        v
-    } else {
-        return Err(StdError::generic_err(
-            "Validators registry contract address is empty",
-        ));
-    };
-    let validators: Vec<ValidatorResponse> =
-        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: deps
-                .api
-                .addr_humanize(&validators_registry_contract)?
-                .to_string(),
-            msg: to_json_binary(&QueryValidators::GetValidatorsForDelegation {})?,
-        }))?;
-
-    if validators.is_empty() {
-        return Err(StdError::generic_err("Validators registry is empty"));
-    }
-
-    let (_remaining_buffered_balance, delegations) =
-        calculate_delegations(payment.amount, validators.as_slice())?;
-
-    let mut external_call_msgs: Vec<cosmwasm_std::CosmosMsg> = vec![];
-    for i in 0..delegations.len() {
-        if delegations[i].is_zero() {
-            continue;
-        }
-        external_call_msgs.push(cosmwasm_std::CosmosMsg::Staking(StakingMsg::Delegate {
-            validator: validators[i].address.clone(),
-            amount: Coin::new(delegations[i].u128(), payment.denom.as_str()),
-        }));
-    }
+    let mut external_call_msgs =
+        delegation_messages(&deps, &config, Coin::new(bond_amount.u128(), &payment.denom))?;
 
     //we don't need to mint stSei when bonding rewards
     if bond_type == BondType::BondRewards {
         let res = Response::new()
             .add_messages(external_call_msgs)
+            .add_messages(reward_msgs)
+            .add_messages(draw_msg)
             .add_attributes(vec![
                 attr("action", "bond_rewards"),
                 attr("from", sender),
@@ -171,11 +135,6 @@ pub fn execute_bond(
         return Ok(res);
     }
 
-    let mint_msg = Cw20ExecuteMsg::Mint {
-        recipient: sender.to_string(),
-        amount: mint_amount,
-    };
-
     let token_address = match bond_type {
         BondType::BSei => deps
             .api
@@ -194,20 +153,145 @@ pub fn execute_bond(
         }
     };
 
-    external_call_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: token_address.to_string(),
-        msg: to_json_binary(&mint_msg)?,
-        funds: vec![],
-    }));
+    external_call_msgs.append(&mut mint_messages(
+        &token_address,
+        &contract_address,
+        &recipient,
+        callback,
+        mint_amount,
+    )?);
 
     let res = Response::new()
         .add_messages(external_call_msgs)
+        .add_messages(draw_msg)
         .add_attributes(vec![
             attr("action", "mint"),
             attr("from", sender),
+            attr("recipient", recipient),
             attr("bonded", payment.amount),
             attr("minted", mint_amount),
         ]);
 
     Ok(res)
 }
+
+/// Returns the coin sent to bond.
+/// coin must have be sent along with transaction and it should be in underlying coin denom
+pub(crate) fn bonded_coin(info: &MessageInfo, coin_denom: &str) -> StdResult<Coin> {
+    if info.funds.len() > 1usize {
+        return Err(StdError::generic_err(
+            "More than one coin is sent; only one asset is supported",
+        ));
+    }
+
+    info.funds
+        .iter()
+        .find(|x| x.denom == coin_denom && x.amount > Uint128::zero())
+        .cloned()
+        .ok_or_else(|| {
+            StdError::generic_err(format!("No {} assets are provided to bond", coin_denom))
+        })
+}
+
+/// Delegates `coin` to the validators of the registry.
+pub(crate) fn delegation_messages(
+    deps: &DepsMut,
+    config: &Config,
+    coin: Coin,
+) -> StdResult<Vec<CosmosMsg>> {
+    let validators_registry_contract = if let Some(v) = &config.validators_registry_contract {
+        v
+    } else {
+        return Err(StdError::generic_err(
+            "Validators registry contract address is empty",
+        ));
+    };
+    let validators: Vec<ValidatorResponse> =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: deps
+                .api
+                .addr_humanize(validators_registry_contract)?
+                .to_string(),
+            msg: to_json_binary(&QueryValidators::GetValidatorsForDelegation {})?,
+        }))?;
+
+    if validators.is_empty() {
+        return Err(StdError::generic_err("Validators registry is empty"));
+    }
+
+    let (_remaining_buffered_balance, delegations) =
+        calculate_delegations(coin.amount, validators.as_slice())?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for i in 0..delegations.len() {
+        if delegations[i].is_zero() {
+            continue;
+        }
+        messages.push(CosmosMsg::Staking(StakingMsg::Delegate {
+            validator: validators[i].address.clone(),
+            amount: Coin::new(delegations[i].u128(), coin.denom.as_str()),
+        }));
+    }
+    Ok(messages)
+}
+
+/// Mints `amount` tokens to the recipient.
+/// With a callback the tokens are minted to the hub first and then sent to the
+/// recipient contract, so that it receives the minted amount along with the hook.
+pub(crate) fn mint_messages(
+    token_address: &Addr,
+    contract_address: &Addr,
+    recipient: &Addr,
+    callback: Option<Binary>,
+    amount: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mint_msg = Cw20ExecuteMsg::Mint {
+        recipient: if callback.is_some() {
+            contract_address.to_string()
+        } else {
+            recipient.to_string()
+        },
+        amount,
+    };
+    let mut messages = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token_address.to_string(),
+        msg: to_json_binary(&mint_msg)?,
+        funds: vec![],
+    })];
+
+    if let Some(callback) = callback {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token_address.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: recipient.to_string(),
+                amount,
+                msg: callback,
+            })?,
+            funds: vec![],
+        }));
+    }
+    Ok(messages)
+}
+
+/// Returns the amount of tokens minted for `amount` of the underlying coin
+/// and the peg recovery fee taken from it.
+/// `total_supply` is the supply of the token that is minted.
+pub(crate) fn calculate_mint_amount(
+    state: &State,
+    params: &Parameters,
+    requested_bsei_with_fee: Uint128,
+    total_supply: Uint128,
+    bond_type: &BondType,
+    amount: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    match bond_type {
+        BondType::BSei => {
+            Peg::bsei(state, params, requested_bsei_with_fee).mint_amount(total_supply, amount)
+        }
+        BondType::StSei => Ok((
+            decimal_division(amount, state.stsei_exchange_rate),
+            Uint128::zero(),
+        )),
+        BondType::BondRewards => Ok((Uint128::zero(), Uint128::zero())),
+    }
+}
@@ -13,14 +13,19 @@
 // limitations under the License.
 
 use cosmwasm_std::{
-    attr, CosmosMsg, Decimal, DepsMut, DistributionMsg, Env, MessageInfo, Response, StdError,
-    StdResult, Addr,
+    attr, to_json_binary, CosmosMsg, Decimal, DepsMut, DistributionMsg, Env, MessageInfo,
+    Response, StdError, StdResult, Addr, Uint128, WasmMsg,
 };
 
-use basset::hub::Parameters;
+use basset::hub::{EpochSource, Parameters};
+use basset::reward::ExecuteMsg as RewardExecuteMsg;
+use basset::token::TokenConfigMsg;
 
 use crate::state::{read_old_unbond_wait_lists, CONFIG, PARAMETERS, read_new_owner, store_new_owner};
 
+// the keeper fee is meant as a small tip for the caller of distribute_released
+const MAX_KEEPER_FEE_PERCENT: u64 = 1;
+
 /// Update general parameters
 /// Only creator/owner is allowed to execute
 #[allow(clippy::too_many_arguments)]
@@ -34,6 +39,10 @@ pub fn execute_update_params(
     er_threshold: Option<Decimal>,
     reward_denom: Option<String>,
     paused: Option<bool>,
+    max_unbond_requests: Option<u32>,
+    keeper_fee: Option<Decimal>,
+    epoch_source: Option<EpochSource>,
+    insurance_draw_cap: Option<Uint128>,
 ) -> StdResult<Response> {
     // only owner can send this message.
     let config = CONFIG.load(deps.storage)?;
@@ -50,6 +59,20 @@ pub fn execute_update_params(
         ));
     }
 
+    if max_unbond_requests == Some(0) {
+        return Err(StdError::generic_err(
+            "max_unbond_requests must be greater than 0",
+        ));
+    }
+
+    let max_keeper_fee = Decimal::percent(MAX_KEEPER_FEE_PERCENT);
+    if keeper_fee.is_some() && keeper_fee.unwrap().gt(&max_keeper_fee) {
+        return Err(StdError::generic_err(format!(
+            "keeper_fee can not be greater than {}%",
+            MAX_KEEPER_FEE_PERCENT
+        )));
+    }
+
     if paused.is_some() && !paused.unwrap() || paused.is_none() {
         let old_unbond_wait_list_entries = read_old_unbond_wait_lists(deps.storage, Some(1u32))?;
         if !old_unbond_wait_list_entries.is_empty() {
@@ -69,6 +92,10 @@ pub fn execute_update_params(
             .min(Decimal::one()),
         reward_denom: reward_denom.unwrap_or(params.reward_denom),
         paused,
+        max_unbond_requests: max_unbond_requests.or(params.max_unbond_requests),
+        keeper_fee: keeper_fee.or(params.keeper_fee),
+        epoch_source: epoch_source.or(params.epoch_source),
+        insurance_draw_cap: insurance_draw_cap.or(params.insurance_draw_cap),
     };
 
     PARAMETERS.save(deps.storage, &new_params)?;
@@ -109,7 +136,7 @@ pub fn accept_ownership(deps: DepsMut, info: MessageInfo) -> StdResult<Response>
 }
 
 #[allow(clippy::too_many_arguments)]
-/// Update the config. Update the owner, reward and token contracts.
+/// Update the config. Update the owner, reward, token and insurance fund contracts.
 /// Only creator/owner is allowed to execute
 pub fn execute_update_config(
     deps: DepsMut,
@@ -122,6 +149,7 @@ pub fn execute_update_config(
     validators_registry_contract: Option<String>,
     rewards_contract: Option<String>,
     update_reward_index_addr: Option<String>,
+    insurance_fund_contract: Option<String>,
 ) -> StdResult<Response> {
     // only owner must be able to send this message.
     let conf = CONFIG.load(deps.storage)?;
@@ -146,6 +174,9 @@ pub fn execute_update_config(
         messages.push(msg);
     }
 
+    // the bSei token and its reward contract cache each other's address
+    let push_bsei_contracts = bsei_token_contract.is_some() || rewards_contract.is_some();
+
     if let Some(token) = bsei_token_contract {
         let token_raw = deps.api.addr_canonicalize(&token)?;
 
@@ -208,6 +239,37 @@ pub fn execute_update_config(
         })?;
     }
 
+    if let Some(insurance_fund) = insurance_fund_contract {
+        let insurance_fund_raw = deps.api.addr_canonicalize(&insurance_fund)?;
+        CONFIG.update(deps.storage, |mut last_config| -> StdResult<_> {
+            last_config.insurance_fund_contract = Some(insurance_fund_raw);
+            Ok(last_config)
+        })?;
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    if let (true, Some(token), Some(rewards)) = (
+        push_bsei_contracts,
+        config.bsei_token_contract,
+        config.rewards_contract,
+    ) {
+        let token = deps.api.addr_humanize(&token)?.to_string();
+        let rewards = deps.api.addr_humanize(&rewards)?.to_string();
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token.clone(),
+            msg: to_json_binary(&TokenConfigMsg::UpdateRewardContract {
+                reward_contract: rewards.clone(),
+            })?,
+            funds: vec![],
+        }));
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: rewards,
+            msg: to_json_binary(&RewardExecuteMsg::UpdateTokenContract {
+                token_contract: token,
+            })?,
+            funds: vec![],
+        }));
+    }
 
     let res = Response::new()
         .add_messages(messages)
@@ -23,24 +23,48 @@ use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenI
 
 use basset::hub::ExecuteMsg::SwapHook;
 use basset::hub::{
-    AllHistoryResponse, BondType, Config, ConfigResponse, CurrentBatch, CurrentBatchResponse,
-    InstantiateMsg, MigrateMsg, Parameters, QueryMsg, State, StateResponse, UnbondHistoryResponse,
-    UnbondRequestsResponse, WithdrawableUnbondedResponse, NewOwnerResponse,
+    AllHistoryResponse, BatchRequest, BatchRequestsResponse, BondType, Config, ConfigResponse,
+    CurrentBatch, CurrentBatchResponse, ConvertDirection, InstantiateMsg, MigrateMsg, Parameters, QueryMsg, SimulateBondResponse,
+    SimulateConvertResponse, SimulateUnbondResponse, State, StateResponse, TokenType,
+    UnbondHistoryResponse, UnbondRequestDetails, UnbondRequestStatus,
+    UnbondRequestsDetailedResponse, UnbondRequestsResponse, WithdrawableUnbondedResponse,
+    NewOwnerResponse, NextBatchTimeResponse, TokenClass, TokenClassesResponse,
+    BatchSlashingResponse, SlashingEvent, SlashingEventsResponse, SlashingSource,
+    RewardPolicy, BSEI_CLASS, STSEI_CLASS,
 };
 use basset::hub::{Cw20HookMsg, ExecuteMsg};
 use basset_sei_rewards_dispatcher::msg::ExecuteMsg::DispatchRewards;
 use basset_sei_rewards_dispatcher::msg::ExecuteMsg::SwapToRewardDenom;
 
-use crate::bond::execute_bond;
+use crate::bond::{calculate_mint_amount, execute_bond};
 use crate::config::{
     accept_ownership, execute_update_config, execute_update_params, set_new_owner,
 };
-use crate::convert::{convert_bsei_stsei, convert_stsei_bsei};
+use crate::gov::{
+    execute_cast_proposal_vote, execute_register_proposal, execute_vote_proposal,
+    query_proposal_tally, query_proposal_vote,
+};
+use crate::convert::{calculate_convert_amount, convert_bsei_stsei, convert_stsei_bsei};
 use crate::state::{
-    all_unbond_history, get_unbond_requests, migrate_unbond_wait_lists, query_get_finished_amount,
-    store_new_owner, NewOwnerAddr, CONFIG, CURRENT_BATCH, PARAMETERS, STATE, read_new_owner,
+    all_unbond_history, get_batch_requests, get_unbond_requests, get_unbond_requests_page,
+    index_unbond_wait_lists, migrate_unbond_wait_lists, query_get_finished_amount,
+    read_unbond_history,
+    store_new_owner, NewOwnerAddr, CONFIG, CURRENT_BATCH, DEFAULT_MAX_UNBOND_REQUESTS, PARAMETERS,
+    STATE, read_new_owner, read_slashing_events, store_slashing_event, token_classes_page,
+};
+use crate::insurance::{
+    claim_insurance, execute_restore_bond, query_insurance_draws,
+};
+use crate::token_class::{
+    execute_bond_class, execute_register_token_class, execute_unbond_class,
+    execute_update_token_class, query_total_class_issued, read_classes, store_classes,
+    token_class_by_contract, apply_built_in_class, bonded_shares, total_bonded,
+};
+use crate::unbond::{
+    calculate_bsei_unbond_amount, estimate_release_time, execute_distribute_released,
+    execute_process_batch, execute_process_released_batches, execute_unbond, execute_unbond_stsei,
+    execute_withdraw_unbonded, next_batch_time,
 };
-use crate::unbond::{execute_unbond, execute_unbond_stsei, execute_withdraw_unbonded};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -64,6 +88,7 @@ pub fn instantiate(
         airdrop_registry_contract: None,
         stsei_token_contract: None,
         rewards_contract: None,
+        insurance_fund_contract: None,
     };
     CONFIG.save(deps.storage, &data)?;
 
@@ -94,6 +119,10 @@ pub fn instantiate(
         er_threshold: msg.er_threshold.min(Decimal::one()),
         reward_denom: msg.reward_denom,
         paused: Some(false),
+        max_unbond_requests: Some(DEFAULT_MAX_UNBOND_REQUESTS),
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
 
     PARAMETERS.save(deps.storage, &params)?;
@@ -130,6 +159,16 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         }
     }
 
+    if let ExecuteMsg::IndexUnbondWaitLists { start_after, limit } = msg {
+        if params.paused.unwrap_or(false) {
+            return execute_index_unbond_wait_lists(deps, start_after, limit);
+        } else {
+            return Err(StdError::generic_err(
+                "index unbond wait lists must paused the contract first.",
+            ));
+        }
+    }
+
     if let ExecuteMsg::UpdateParams {
         epoch_period,
         unbonding_period,
@@ -137,6 +176,10 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         er_threshold,
         reward_denom,
         paused,
+        max_unbond_requests,
+        keeper_fee,
+        epoch_source,
+        insurance_draw_cap,
     } = msg
     {
         return execute_update_params(
@@ -149,6 +192,10 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             er_threshold,
             reward_denom,
             paused,
+            max_unbond_requests,
+            keeper_fee,
+            epoch_source,
+            insurance_draw_cap,
         );
     }
 
@@ -158,14 +205,93 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
 
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
-        ExecuteMsg::Bond {} => execute_bond(deps, env, info, BondType::BSei),
-        ExecuteMsg::BondForStSei {} => execute_bond(deps, env, info, BondType::StSei),
-        ExecuteMsg::BondRewards {} => execute_bond(deps, env, info, BondType::BondRewards),
+        ExecuteMsg::Bond {
+            recipient,
+            callback,
+            min_mint_amount,
+        } => execute_bond(
+            deps,
+            env,
+            info,
+            BondType::BSei,
+            recipient,
+            callback,
+            min_mint_amount,
+        ),
+        ExecuteMsg::BondForStSei {
+            recipient,
+            callback,
+            min_mint_amount,
+        } => execute_bond(
+            deps,
+            env,
+            info,
+            BondType::StSei,
+            recipient,
+            callback,
+            min_mint_amount,
+        ),
+        ExecuteMsg::BondRewards {} => {
+            execute_bond(deps, env, info, BondType::BondRewards, None, None, None)
+        }
         ExecuteMsg::UpdateGlobalIndex { airdrop_hooks } => {
             execute_update_global(deps, env, info, airdrop_hooks)
         }
-        ExecuteMsg::WithdrawUnbonded {} => execute_withdraw_unbonded(deps, env, info),
+        ExecuteMsg::WithdrawUnbonded {
+            limit,
+            batch_ids,
+            recipient,
+            msg,
+            rebond_as,
+        } => execute_withdraw_unbonded(
+            deps, env, info, limit, batch_ids, recipient, msg, rebond_as,
+        ),
+        ExecuteMsg::ProcessReleasedBatches { limit } => {
+            execute_process_released_batches(deps, env, limit)
+        }
+        ExecuteMsg::DistributeReleased { batch_id, limit } => {
+            execute_distribute_released(deps, env, info, batch_id, limit)
+        }
+        ExecuteMsg::ProcessBatch {} => execute_process_batch(deps, env),
+        ExecuteMsg::RegisterTokenClass {
+            name,
+            token_contract,
+            reward_policy,
+            peg_recovery_fee,
+            er_threshold,
+        } => execute_register_token_class(
+            deps,
+            info,
+            name,
+            token_contract,
+            reward_policy,
+            peg_recovery_fee,
+            er_threshold,
+        ),
+        ExecuteMsg::UpdateTokenClass {
+            name,
+            peg_recovery_fee,
+            er_threshold,
+        } => execute_update_token_class(deps, info, name, peg_recovery_fee, er_threshold),
+        ExecuteMsg::BondForClass {
+            class,
+            recipient,
+            callback,
+            min_mint_amount,
+        } => execute_bond_class(deps, env, info, class, recipient, callback, min_mint_amount),
         ExecuteMsg::CheckSlashing {} => execute_slashing(deps, env),
+        ExecuteMsg::RestoreBond {} => execute_restore_bond(deps, env, info),
+        ExecuteMsg::RegisterProposal {
+            proposal_id,
+            voting_end,
+        } => execute_register_proposal(deps, env, info, proposal_id, voting_end),
+        ExecuteMsg::VoteProposal {
+            proposal_id,
+            options,
+        } => execute_vote_proposal(deps, env, info, proposal_id, options),
+        ExecuteMsg::CastProposalVote { proposal_id } => {
+            execute_cast_proposal_vote(deps, env, proposal_id)
+        }
         ExecuteMsg::UpdateParams {
             epoch_period,
             unbonding_period,
@@ -173,6 +299,10 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             er_threshold,
             reward_denom,
             paused,
+            max_unbond_requests,
+            keeper_fee,
+            epoch_source,
+            insurance_draw_cap,
         } => execute_update_params(
             deps,
             env,
@@ -183,6 +313,10 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             er_threshold,
             reward_denom,
             paused,
+            max_unbond_requests,
+            keeper_fee,
+            epoch_source,
+            insurance_draw_cap,
         ),
         ExecuteMsg::UpdateConfig {
             rewards_dispatcher_contract,
@@ -192,6 +326,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             stsei_token_contract,
             rewards_contract,
             update_reward_index_addr,
+            insurance_fund_contract,
         } => execute_update_config(
             deps,
             env,
@@ -203,6 +338,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             validators_registry_contract,
             rewards_contract,
             update_reward_index_addr,
+            insurance_fund_contract,
         ),
         ExecuteMsg::SetOwner { new_owner_addr } => {
             let api = deps.api;
@@ -242,9 +378,22 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             redelegations,
         } => execute_redelegate_proxy(deps, env, info, src_validator, redelegations),
         ExecuteMsg::MigrateUnbondWaitList { limit: _ } => Err(StdError::generic_err("forbidden")),
+        ExecuteMsg::IndexUnbondWaitLists { .. } => Err(StdError::generic_err("forbidden")),
     }
 }
 
+fn execute_index_unbond_wait_lists(
+    deps: DepsMut,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+) -> StdResult<Response> {
+    let last_key = index_unbond_wait_lists(deps.storage, start_after, limit)?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "index_unbond_wait_lists"),
+        attr("last_key", last_key.map(|key| key.to_base64()).unwrap_or_default()),
+    ]))
+}
+
 pub fn execute_redelegate_proxy(
     deps: DepsMut,
     _env: Env,
@@ -287,6 +436,23 @@ pub fn receive_cw20(
 ) -> StdResult<Response> {
     let contract_addr = deps.api.addr_canonicalize(info.sender.as_str())?;
 
+    // the registered token classes share one code path
+    if let Some(class) = token_class_by_contract(deps.storage, &info.sender)? {
+        return match from_json(&cw20_msg.msg)? {
+            Cw20HookMsg::Unbond { min_unbond_amount } => execute_unbond_class(
+                deps,
+                env,
+                class.name,
+                cw20_msg.amount,
+                cw20_msg.sender,
+                min_unbond_amount,
+            ),
+            Cw20HookMsg::Convert { .. } => Err(StdError::generic_err(
+                "the tokens of a token class can not be converted",
+            )),
+        };
+    }
+
     // only token contract can execute this message
     let conf = CONFIG.load(deps.storage)?;
 
@@ -307,20 +473,44 @@ pub fn receive_cw20(
     };
 
     match from_json(&cw20_msg.msg)? {
-        Cw20HookMsg::Unbond {} => {
+        Cw20HookMsg::Unbond { min_unbond_amount } => {
             if contract_addr == bsei_contract_addr {
-                execute_unbond(deps, env, cw20_msg.amount, cw20_msg.sender)
+                execute_unbond(
+                    deps,
+                    env,
+                    cw20_msg.amount,
+                    cw20_msg.sender,
+                    min_unbond_amount,
+                )
             } else if contract_addr == stsei_contract_addr {
-                execute_unbond_stsei(deps, env, cw20_msg.amount, cw20_msg.sender)
+                execute_unbond_stsei(
+                    deps,
+                    env,
+                    cw20_msg.amount,
+                    cw20_msg.sender,
+                    min_unbond_amount,
+                )
             } else {
                 Err(StdError::generic_err("unauthorized"))
             }
         }
-        Cw20HookMsg::Convert {} => {
+        Cw20HookMsg::Convert { min_mint_amount } => {
             if contract_addr == bsei_contract_addr {
-                convert_bsei_stsei(deps, env, cw20_msg.amount, cw20_msg.sender)
+                convert_bsei_stsei(
+                    deps,
+                    env,
+                    cw20_msg.amount,
+                    cw20_msg.sender,
+                    min_mint_amount,
+                )
             } else if contract_addr == stsei_contract_addr {
-                convert_stsei_bsei(deps, env, cw20_msg.amount, cw20_msg.sender)
+                convert_stsei_bsei(
+                    deps,
+                    env,
+                    cw20_msg.amount,
+                    cw20_msg.sender,
+                    min_mint_amount,
+                )
             } else {
                 Err(StdError::generic_err("unauthorized"))
             }
@@ -386,12 +576,23 @@ pub fn execute_update_global(
     let mut withdraw_msgs = withdraw_all_rewards(&deps, env.contract.address.to_string())?;
     messages.append(&mut withdraw_msgs);
 
+    // the dispatcher pays the rewards of the dispatched classes and bonds the rest
+    // back, which `split_class_rewards` shares among the other classes
     let state = STATE.load(deps.storage)?;
+    let current_batch = CURRENT_BATCH.load(deps.storage)?;
+    let mut bsei_total_bonded = Uint128::zero();
+    let mut stsei_total_bonded = Uint128::zero();
+    for class in read_classes(deps.as_ref(), &state, &current_batch)? {
+        match class.reward_policy {
+            RewardPolicy::Dispatched => bsei_total_bonded += class.total_bond_amount,
+            _ => stsei_total_bonded += class.total_bond_amount,
+        }
+    }
 
     // Send Swap message to reward contract
     let swap_msg = SwapToRewardDenom {
-        stsei_total_bonded: state.total_bond_stsei_amount,
-        bsei_total_bonded: state.total_bond_bsei_amount,
+        stsei_total_bonded,
+        bsei_total_bonded,
     };
 
     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -438,10 +639,17 @@ fn withdraw_all_rewards(deps: &DepsMut, delegator: String) -> StdResult<Vec<Cosm
 }
 
 fn query_actual_state(deps: Deps, env: Env) -> StdResult<State> {
+    Ok(query_actual_state_with_classes(deps, env)?.0)
+}
+
+/// The state and all the token classes after the slashing check.
+fn query_actual_state_with_classes(deps: Deps, env: Env) -> StdResult<(State, Vec<TokenClass>)> {
     let mut state = STATE.load(deps.storage)?;
+    let mut current_batch = CURRENT_BATCH.load(deps.storage)?;
+    let mut classes = read_classes(deps, &state, &current_batch)?;
     let delegations = deps.querier.query_all_delegations(env.contract.address)?;
     if delegations.is_empty() {
-        return Ok(state);
+        return Ok((state, classes));
     }
 
     //read params
@@ -457,37 +665,81 @@ fn query_actual_state(deps: Deps, env: Env) -> StdResult<State> {
     }
 
     // Check the amount that contract thinks is bonded
-    let state_total_bonded = state.total_bond_bsei_amount + state.total_bond_stsei_amount;
+    let state_total_bonded = total_bonded(&classes);
     if state_total_bonded.is_zero() {
-        return Ok(state);
+        return Ok((state, classes));
     }
 
-    // Need total issued for updating the exchange rate
-    let bsei_total_issued = query_total_bsei_issued(deps)?;
-    let stsei_total_issued = query_total_stsei_issued(deps)?;
-    let current_batch = CURRENT_BATCH.load(deps.storage)?;
-    let current_requested_bsei_with_fee = current_batch.requested_bsei_with_fee;
-    let current_requested_stsei = current_batch.requested_stsei;
-
+    // the classes are slashed in proportion to their bonded amount
     if state_total_bonded.u128() > actual_total_bonded.u128() {
-        let bsei_bond_ratio = Decimal::from_ratio(state.total_bond_bsei_amount, state_total_bonded);
-        state.total_bond_bsei_amount = actual_total_bonded * bsei_bond_ratio;
-        state.total_bond_stsei_amount =
-            actual_total_bonded.checked_sub(state.total_bond_bsei_amount)?;
+        let shares = bonded_shares(actual_total_bonded, &classes);
+        for (class, share) in classes.iter_mut().zip(shares) {
+            class.total_bond_amount = share;
+        }
     }
-    state.update_bsei_exchange_rate(bsei_total_issued, current_requested_bsei_with_fee);
-    state.update_stsei_exchange_rate(stsei_total_issued, current_requested_stsei);
-    Ok(state)
+    for class in classes.iter_mut() {
+        let total_issued = query_total_class_issued(deps, class)?;
+        class.update_exchange_rate(total_issued);
+        apply_built_in_class(&mut state, &mut current_batch, class);
+    }
+    Ok((state, classes))
 }
 
 /// Check whether slashing has happened
 /// This is used for checking slashing while bonding or unbonding
-pub fn slashing(deps: &mut DepsMut, env: Env) -> StdResult<State> {
-    let state = query_actual_state(deps.as_ref(), env)?;
+/// A new loss comes with the message drawing it from the insurance fund, which
+/// the caller must send.
+pub fn slashing(deps: &mut DepsMut, env: Env) -> StdResult<(State, Option<CosmosMsg>)> {
+    let time = env.block.time.seconds();
+    let prev_state = STATE.load(deps.storage)?;
+    let mut current_batch = CURRENT_BATCH.load(deps.storage)?;
+    let prev_classes = read_classes(deps.as_ref(), &prev_state, &current_batch)?;
+    let (mut state, classes) = query_actual_state_with_classes(deps.as_ref(), env)?;
+
+    // record the slashing, so that it can be accounted for later on
+    let bonded_before = total_bonded(&prev_classes);
+    let bonded_after = total_bonded(&classes);
+    let mut draw_msg = None;
+    if bonded_after < bonded_before {
+        let (mut bsei_slashed, mut stsei_slashed, mut classes_slashed) =
+            (Uint128::zero(), Uint128::zero(), Uint128::zero());
+        for (prev, class) in prev_classes.iter().zip(&classes) {
+            let slashed = prev.total_bond_amount.saturating_sub(class.total_bond_amount);
+            match class.name.as_str() {
+                BSEI_CLASS => bsei_slashed += slashed,
+                STSEI_CLASS => stsei_slashed += slashed,
+                _ => classes_slashed += slashed,
+            }
+        }
+        // the undelegated batches take their losses when released
+        let unbonding_batches = state.last_processed_batch + 1..current_batch.id;
+        let (first_batch, last_batch) = if unbonding_batches.is_empty() {
+            (None, None)
+        } else {
+            (Some(unbonding_batches.start), Some(unbonding_batches.end - 1))
+        };
+        store_slashing_event(
+            deps.storage,
+            SlashingEvent {
+                id: 0,
+                time,
+                source: SlashingSource::Delegations,
+                amount_before: bonded_before,
+                amount_after: bonded_after,
+                bsei_slashed,
+                stsei_slashed,
+                classes_slashed,
+                first_batch,
+                last_batch,
+            },
+        )?;
+        draw_msg = claim_insurance(deps, bonded_before - bonded_after)?;
+    }
 
+    store_classes(deps.storage, &mut state, &mut current_batch, &classes)?;
     STATE.save(deps.storage, &state)?;
 
-    Ok(state)
+    Ok((state, draw_msg))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -585,8 +837,8 @@ pub fn swap_hook(
 /// Handler for tracking slashing
 pub fn execute_slashing(mut deps: DepsMut, env: Env) -> StdResult<Response> {
     // call slashing and
-    let state = slashing(&mut deps, env)?;
-    Ok(Response::new().add_attributes(vec![
+    let (state, draw_msg) = slashing(&mut deps, env)?;
+    Ok(Response::new().add_messages(draw_msg).add_attributes(vec![
         attr("action", "check_slashing"),
         attr(
             "new_bsei_exchange_rate",
@@ -607,15 +859,67 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
         QueryMsg::State {} => to_json_binary(&query_state(deps, env)?),
         QueryMsg::CurrentBatch {} => to_json_binary(&query_current_batch(deps)?),
-        QueryMsg::WithdrawableUnbonded { address } => {
-            to_json_binary(&query_withdrawable_unbonded(deps, address, env)?)
+        QueryMsg::NextBatchTime {} => to_json_binary(&query_next_batch_time(deps, env)?),
+        QueryMsg::ProposalVote { proposal_id, voter } => {
+            to_json_binary(&query_proposal_vote(deps, proposal_id, voter)?)
+        }
+        QueryMsg::ProposalTally { proposal_id } => {
+            to_json_binary(&query_proposal_tally(deps, proposal_id)?)
+        }
+        QueryMsg::SlashingEvents { start_after, limit } => {
+            to_json_binary(&query_slashing_events(deps, start_after, limit)?)
+        }
+        QueryMsg::BatchSlashing { batch_id } => {
+            to_json_binary(&query_batch_slashing(deps, batch_id)?)
+        }
+        QueryMsg::InsuranceDraws { start_after, limit } => {
+            to_json_binary(&query_insurance_draws(deps, start_after, limit)?)
+        }
+        QueryMsg::TokenClasses { start_after, limit } => {
+            to_json_binary(&query_token_classes(deps, start_after, limit)?)
         }
+        QueryMsg::WithdrawableUnbonded {
+            address,
+            start_after,
+            limit,
+        } => to_json_binary(&query_withdrawable_unbonded(
+            deps,
+            address,
+            env,
+            start_after,
+            limit,
+        )?),
         QueryMsg::Parameters {} => to_json_binary(&query_params(deps)?),
         QueryMsg::UnbondRequests { address } => to_json_binary(&query_unbond_requests(deps, address)?),
+        QueryMsg::BatchRequests {
+            batch_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_batch_requests(deps, batch_id, start_after, limit)?),
+        QueryMsg::UnbondRequestsDetailed {
+            address,
+            start_after,
+            limit,
+        } => to_json_binary(&query_unbond_requests_detailed(
+            deps,
+            env,
+            address,
+            start_after,
+            limit,
+        )?),
         QueryMsg::AllHistory { start_from, limit } => {
             to_json_binary(&query_unbond_requests_limitation(deps, start_from, limit)?)
         }
         QueryMsg::NewOwner {} => to_json_binary(&query_new_owner(deps)?),
+        QueryMsg::SimulateBond { bond_type, amount } => {
+            to_json_binary(&query_simulate_bond(deps, env, bond_type, amount)?)
+        }
+        QueryMsg::SimulateUnbond { token, amount } => {
+            to_json_binary(&query_simulate_unbond(deps, env, token, amount)?)
+        }
+        QueryMsg::SimulateConvert { direction, amount } => {
+            to_json_binary(&query_simulate_convert(deps, env, direction, amount)?)
+        }
     }
 }
 
@@ -683,6 +987,14 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         bsei_token_contract: bsei_token.clone(),
         airdrop_registry_contract: airdrop,
         stsei_token_contract: stsei_token,
+        insurance_fund_contract: config
+            .insurance_fund_contract
+            .map(|fund| deps.api.addr_humanize(&fund).map(|fund| fund.to_string()))
+            .transpose()?,
+        rewards_contract: config
+            .rewards_contract
+            .map(|rewards| deps.api.addr_humanize(&rewards).map(|rewards| rewards.to_string()))
+            .transpose()?,
 
         token_contract: bsei_token,
     })
@@ -706,6 +1018,96 @@ fn query_state(deps: Deps, env: Env) -> StdResult<StateResponse> {
     Ok(res)
 }
 
+fn query_simulate_bond(
+    deps: Deps,
+    env: Env,
+    bond_type: TokenType,
+    amount: Uint128,
+) -> StdResult<SimulateBondResponse> {
+    let state = query_actual_state(deps, env)?;
+    let params = PARAMETERS.load(deps.storage)?;
+    let current_batch = CURRENT_BATCH.load(deps.storage)?;
+    let (total_supply, exchange_rate) = match bond_type {
+        TokenType::BSei => (
+            query_total_bsei_issued(deps).unwrap_or_default(),
+            state.bsei_exchange_rate,
+        ),
+        TokenType::StSei => (
+            query_total_stsei_issued(deps).unwrap_or_default(),
+            state.stsei_exchange_rate,
+        ),
+    };
+    let (mint_amount, peg_fee) = calculate_mint_amount(
+        &state,
+        &params,
+        current_batch.requested_bsei_with_fee,
+        total_supply,
+        &bond_type.into(),
+        amount,
+    )?;
+    Ok(SimulateBondResponse {
+        mint_amount,
+        peg_fee,
+        exchange_rate,
+    })
+}
+
+fn query_simulate_unbond(
+    deps: Deps,
+    env: Env,
+    token: TokenType,
+    amount: Uint128,
+) -> StdResult<SimulateUnbondResponse> {
+    let now = env.block.time.seconds();
+    let state = query_actual_state(deps, env)?;
+    let params = PARAMETERS.load(deps.storage)?;
+    let current_batch = CURRENT_BATCH.load(deps.storage)?;
+    let (unbond_amount, peg_fee, exchange_rate) = match token {
+        TokenType::BSei => {
+            let (unbond_amount, peg_fee) = calculate_bsei_unbond_amount(
+                &state,
+                &params,
+                current_batch.requested_bsei_with_fee,
+                query_total_bsei_issued(deps)?,
+                amount,
+            )?;
+            (unbond_amount, peg_fee, state.bsei_exchange_rate)
+        }
+        TokenType::StSei => (amount, Uint128::zero(), state.stsei_exchange_rate),
+    };
+    Ok(SimulateUnbondResponse {
+        unbond_amount,
+        peg_fee,
+        underlying_amount: unbond_amount * exchange_rate,
+        exchange_rate,
+        estimated_release_time: estimate_release_time(&deps.querier, &state, &params, now)?,
+    })
+}
+
+fn query_simulate_convert(
+    deps: Deps,
+    env: Env,
+    direction: ConvertDirection,
+    amount: Uint128,
+) -> StdResult<SimulateConvertResponse> {
+    let state = query_actual_state(deps, env)?;
+    let params = PARAMETERS.load(deps.storage)?;
+    let current_batch = CURRENT_BATCH.load(deps.storage)?;
+    let (mint_amount, peg_fee, underlying_amount) = calculate_convert_amount(
+        &state,
+        &params,
+        current_batch.requested_bsei_with_fee,
+        query_total_bsei_issued(deps)?,
+        &direction,
+        amount,
+    )?;
+    Ok(SimulateConvertResponse {
+        mint_amount,
+        peg_fee,
+        underlying_amount,
+    })
+}
+
 fn query_current_batch(deps: Deps) -> StdResult<CurrentBatchResponse> {
     let current_batch = CURRENT_BATCH.load(deps.storage)?;
     Ok(CurrentBatchResponse {
@@ -717,17 +1119,32 @@ fn query_current_batch(deps: Deps) -> StdResult<CurrentBatchResponse> {
     })
 }
 
+fn query_next_batch_time(deps: Deps, env: Env) -> StdResult<NextBatchTimeResponse> {
+    let current_batch = CURRENT_BATCH.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let params = PARAMETERS.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    Ok(NextBatchTimeResponse {
+        batch_id: current_batch.id,
+        next_batch_time: next_batch_time(&deps.querier, &state, &params, now)?,
+    })
+}
+
 fn query_withdrawable_unbonded(
     deps: Deps,
     address: String,
     env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
 ) -> StdResult<WithdrawableUnbondedResponse> {
     let params = PARAMETERS.load(deps.storage)?;
     let historical_time = env.block.time.seconds() - params.unbonding_period;
-    let all_requests = query_get_finished_amount(deps.storage, address, historical_time)?;
+    let (all_requests, next_start_after) =
+        query_get_finished_amount(deps.storage, address, historical_time, start_after, limit)?;
 
     let withdrawable = WithdrawableUnbondedResponse {
         withdrawable: all_requests,
+        next_start_after,
     };
     Ok(withdrawable)
 }
@@ -772,6 +1189,137 @@ fn query_unbond_requests(deps: Deps, address: String) -> StdResult<UnbondRequest
     Ok(res)
 }
 
+fn query_batch_requests(
+    deps: Deps,
+    batch_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<BatchRequestsResponse> {
+    let requests = get_batch_requests(deps.storage, batch_id, start_after, limit)?
+        .into_iter()
+        .map(|(address, request)| BatchRequest {
+            address,
+            bsei_amount: request.bsei_amount,
+            stsei_amount: request.stsei_amount,
+            classes: request.classes,
+        })
+        .collect();
+    Ok(BatchRequestsResponse { batch_id, requests })
+}
+
+fn query_unbond_requests_detailed(
+    deps: Deps,
+    env: Env,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<UnbondRequestsDetailedResponse> {
+    let now = env.block.time.seconds();
+    let (state, classes) = query_actual_state_with_classes(deps, env)?;
+    let params = PARAMETERS.load(deps.storage)?;
+    let requests = get_unbond_requests_page(deps.storage, address.clone(), start_after, limit)?
+        .into_iter()
+        .map(|(batch_id, request)| {
+            let details = match read_unbond_history(deps.storage, batch_id) {
+                Ok(history) => UnbondRequestDetails {
+                    batch_id,
+                    status: if history.released {
+                        UnbondRequestStatus::Released
+                    } else {
+                        UnbondRequestStatus::Unbonding
+                    },
+                    bsei_amount: request.bsei_amount,
+                    stsei_amount: request.stsei_amount,
+                    expected_release_time: history.time + params.unbonding_period,
+                    bsei_exchange_rate: history.bsei_applied_exchange_rate,
+                    stsei_exchange_rate: history.stsei_applied_exchange_rate,
+                    estimated_amount: history.withdraw_amount(&request),
+                    classes: request.classes,
+                },
+                // the current batch has no history until it is undelegated
+                Err(_) => UnbondRequestDetails {
+                    batch_id,
+                    status: UnbondRequestStatus::PendingEpoch,
+                    bsei_amount: request.bsei_amount,
+                    stsei_amount: request.stsei_amount,
+                    expected_release_time: estimate_release_time(
+                        &deps.querier,
+                        &state,
+                        &params,
+                        now,
+                    )?,
+                    bsei_exchange_rate: state.bsei_exchange_rate,
+                    stsei_exchange_rate: state.stsei_exchange_rate,
+                    estimated_amount: request.classes.iter().flatten().fold(
+                        request.bsei_amount * state.bsei_exchange_rate
+                            + request.stsei_amount * state.stsei_exchange_rate,
+                        |total, requested| {
+                            let rate = classes
+                                .iter()
+                                .find(|class| class.name == requested.class)
+                                .map_or_else(Decimal::zero, |class| class.exchange_rate);
+                            total + requested.amount * rate
+                        },
+                    ),
+                    classes: request.classes,
+                },
+            };
+            Ok(details)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(UnbondRequestsDetailedResponse { address, requests })
+}
+
+fn query_slashing_events(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SlashingEventsResponse> {
+    let events = read_slashing_events(deps.storage, start_after, limit)?;
+    Ok(SlashingEventsResponse { events })
+}
+
+fn query_batch_slashing(deps: Deps, batch_id: u64) -> StdResult<BatchSlashingResponse> {
+    let history = read_unbond_history(deps.storage, batch_id)?;
+    let bsei_expected_amount = history.bsei_amount * history.bsei_applied_exchange_rate;
+    let stsei_expected_amount = history.stsei_amount * history.stsei_applied_exchange_rate;
+    let bsei_released_amount = history.bsei_amount * history.bsei_withdraw_rate;
+    let stsei_released_amount = history.stsei_amount * history.stsei_withdraw_rate;
+    let slashed_amount = history.classes.iter().flatten().fold(
+        (bsei_expected_amount + stsei_expected_amount)
+            .saturating_sub(bsei_released_amount + stsei_released_amount),
+        |total, class| {
+            total
+                + (class.amount * class.applied_exchange_rate)
+                    .saturating_sub(class.amount * class.withdraw_rate)
+        },
+    );
+    Ok(BatchSlashingResponse {
+        batch_id,
+        released: history.released,
+        bsei_expected_amount,
+        stsei_expected_amount,
+        bsei_released_amount,
+        stsei_released_amount,
+        slashed_amount,
+    })
+}
+
+fn query_token_classes(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokenClassesResponse> {
+    let state = STATE.load(deps.storage)?;
+    let current_batch = CURRENT_BATCH.load(deps.storage)?;
+    let classes = token_classes_page(
+        read_classes(deps, &state, &current_batch)?,
+        start_after,
+        limit,
+    );
+    Ok(TokenClassesResponse { classes })
+}
+
 fn query_unbond_requests_limitation(
     deps: Deps,
     start: Option<u64>,
@@ -797,6 +1345,8 @@ fn query_unbond_requests_limitation(
             amount: r.bsei_amount,
             applied_exchange_rate: r.bsei_applied_exchange_rate,
             withdraw_rate: r.bsei_withdraw_rate,
+
+            classes: r.classes.clone(),
         })
         .collect();
 
@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::bond::calculate_mint_amount;
 use crate::contract::{query_total_bsei_issued, query_total_stsei_issued, slashing};
 use crate::math::decimal_division;
 use crate::state::{CONFIG, CURRENT_BATCH, PARAMETERS, STATE};
+use crate::unbond::calculate_bsei_unbond_amount;
+use basset::hub::{BondType, ConvertDirection, Parameters, State};
 use cosmwasm_std::{
     attr, to_json_binary, CosmosMsg, DepsMut, Env, Response, StdError, StdResult, Uint128, WasmMsg,
 };
@@ -26,12 +29,11 @@ pub fn convert_stsei_bsei(
     env: Env,
     stsei_amount: Uint128,
     sender: String,
+    min_mint_amount: Option<Uint128>,
 ) -> StdResult<Response> {
     let conf = CONFIG.load(deps.storage)?;
-    let state = slashing(&mut deps, env)?;
+    let (state, draw_msg) = slashing(&mut deps, env)?;
     let params = PARAMETERS.load(deps.storage)?;
-    let threshold = params.er_threshold;
-    let recovery_fee = params.peg_recovery_fee;
 
     let stsei_contract = deps.api.addr_humanize(
         &conf
@@ -44,23 +46,21 @@ pub fn convert_stsei_bsei(
             .ok_or_else(|| StdError::generic_err("bsei contract must be registred"))?,
     )?;
 
-    let denom_equiv = state.stsei_exchange_rate.mul(stsei_amount);
-
-    let bsei_to_mint = decimal_division(denom_equiv, state.bsei_exchange_rate);
     let current_batch = CURRENT_BATCH.load(deps.storage)?;
     let requested_bsei_with_fee = current_batch.requested_bsei_with_fee;
     let requested_stsei = current_batch.requested_stsei;
 
     let total_bsei_supply = query_total_bsei_issued(deps.as_ref())?;
     let total_stsei_supply = query_total_stsei_issued(deps.as_ref())?;
-    let mut bsei_mint_amount_with_fee = bsei_to_mint;
-    if state.bsei_exchange_rate < threshold {
-        let max_peg_fee = bsei_to_mint * recovery_fee;
-        let required_peg_fee = (total_bsei_supply + bsei_to_mint + requested_bsei_with_fee)
-            - (state.total_bond_bsei_amount + denom_equiv);
-        let peg_fee = Uint128::min(max_peg_fee, required_peg_fee);
-        bsei_mint_amount_with_fee = bsei_to_mint.checked_sub(peg_fee)?;
-    }
+    let (bsei_mint_amount_with_fee, _peg_fee, denom_equiv) = calculate_convert_amount(
+        &state,
+        &params,
+        requested_bsei_with_fee,
+        total_bsei_supply,
+        &ConvertDirection::StSeiToBSei,
+        stsei_amount,
+    )?;
+    check_min_mint_amount(bsei_mint_amount_with_fee, min_mint_amount)?;
 
     STATE.update(deps.storage, |mut prev_state| -> StdResult<_> {
         prev_state.total_bond_bsei_amount += denom_equiv;
@@ -94,14 +94,17 @@ pub fn convert_stsei_bsei(
         burn_message(stsei_contract.to_string(), stsei_amount)?,
     ];
 
-    let res = Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "convert_stsei"),
-        attr("from", sender),
-        attr("bsei_exchange_rate", state.bsei_exchange_rate.to_string()),
-        attr("stsei_exchange_rate", state.stsei_exchange_rate.to_string()),
-        attr("stsei_amount", stsei_amount),
-        attr("bsei_amount", bsei_mint_amount_with_fee),
-    ]);
+    let res = Response::new()
+        .add_messages(messages)
+        .add_messages(draw_msg)
+        .add_attributes(vec![
+            attr("action", "convert_stsei"),
+            attr("from", sender),
+            attr("bsei_exchange_rate", state.bsei_exchange_rate.to_string()),
+            attr("stsei_exchange_rate", state.stsei_exchange_rate.to_string()),
+            attr("stsei_amount", stsei_amount),
+            attr("bsei_amount", bsei_mint_amount_with_fee),
+        ]);
     Ok(res)
 }
 
@@ -110,9 +113,10 @@ pub fn convert_bsei_stsei(
     env: Env,
     bsei_amount: Uint128,
     sender: String,
+    min_mint_amount: Option<Uint128>,
 ) -> StdResult<Response> {
     let conf = CONFIG.load(deps.storage)?;
-    let state = slashing(&mut deps, env)?;
+    let (state, draw_msg) = slashing(&mut deps, env)?;
     let stsei_contract = deps.api.addr_humanize(
         &conf
             .stsei_token_contract
@@ -125,8 +129,6 @@ pub fn convert_bsei_stsei(
     )?;
 
     let params = PARAMETERS.load(deps.storage)?;
-    let threshold = params.er_threshold;
-    let recovery_fee = params.peg_recovery_fee;
 
     let current_batch = CURRENT_BATCH.load(deps.storage)?;
     let requested_bsei_with_fee = current_batch.requested_bsei_with_fee;
@@ -136,20 +138,15 @@ pub fn convert_bsei_stsei(
     let total_stsei_supply = query_total_stsei_issued(deps.as_ref())?;
 
     // Apply peg recovery fee
-    let bsei_amount_with_fee: Uint128;
-    if state.bsei_exchange_rate < threshold {
-        let max_peg_fee = bsei_amount * recovery_fee;
-        let required_peg_fee = (total_bsei_supply + current_batch.requested_bsei_with_fee)
-            .checked_sub(state.total_bond_bsei_amount)?;
-        let peg_fee = Uint128::min(max_peg_fee, required_peg_fee);
-        bsei_amount_with_fee = bsei_amount.checked_sub(peg_fee)?;
-    } else {
-        bsei_amount_with_fee = bsei_amount;
-    }
-
-    let denom_equiv = state.bsei_exchange_rate.mul(bsei_amount_with_fee);
-
-    let stsei_to_mint = decimal_division(denom_equiv, state.stsei_exchange_rate);
+    let (stsei_to_mint, _peg_fee, denom_equiv) = calculate_convert_amount(
+        &state,
+        &params,
+        current_batch.requested_bsei_with_fee,
+        total_bsei_supply,
+        &ConvertDirection::BSeiToStSei,
+        bsei_amount,
+    )?;
+    check_min_mint_amount(stsei_to_mint, min_mint_amount)?;
 
     STATE.update(deps.storage, |mut prev_state| -> StdResult<_> {
         prev_state.total_bond_bsei_amount = prev_state.total_bond_bsei_amount.checked_sub(denom_equiv)
@@ -181,17 +178,73 @@ pub fn convert_bsei_stsei(
         burn_message(bsei_contract.to_string(), bsei_amount)?,
     ];
 
-    let res = Response::new().add_messages(messages).add_attributes(vec![
-        attr("action", "convert_stsei"),
-        attr("from", sender),
-        attr("bsei_exchange_rate", state.bsei_exchange_rate.to_string()),
-        attr("stsei_exchange_rate", state.stsei_exchange_rate.to_string()),
-        attr("bsei_amount", bsei_amount),
-        attr("stsei_amount", stsei_to_mint),
-    ]);
+    let res = Response::new()
+        .add_messages(messages)
+        .add_messages(draw_msg)
+        .add_attributes(vec![
+            attr("action", "convert_stsei"),
+            attr("from", sender),
+            attr("bsei_exchange_rate", state.bsei_exchange_rate.to_string()),
+            attr("stsei_exchange_rate", state.stsei_exchange_rate.to_string()),
+            attr("bsei_amount", bsei_amount),
+            attr("stsei_amount", stsei_to_mint),
+        ]);
     Ok(res)
 }
 
+/// Returns the amount of tokens minted for `amount` of the other token, the peg
+/// recovery fee in bSei and the amount of the underlying coin that is moved.
+pub(crate) fn calculate_convert_amount(
+    state: &State,
+    params: &Parameters,
+    requested_bsei_with_fee: Uint128,
+    total_bsei_supply: Uint128,
+    direction: &ConvertDirection,
+    amount: Uint128,
+) -> StdResult<(Uint128, Uint128, Uint128)> {
+    match direction {
+        ConvertDirection::StSeiToBSei => {
+            let denom_equiv = state.stsei_exchange_rate.mul(amount);
+            let (bsei_mint_amount_with_fee, peg_fee) = calculate_mint_amount(
+                state,
+                params,
+                requested_bsei_with_fee,
+                total_bsei_supply,
+                &BondType::BSei,
+                denom_equiv,
+            )?;
+            Ok((bsei_mint_amount_with_fee, peg_fee, denom_equiv))
+        }
+        ConvertDirection::BSeiToStSei => {
+            let (bsei_amount_with_fee, peg_fee) = calculate_bsei_unbond_amount(
+                state,
+                params,
+                requested_bsei_with_fee,
+                total_bsei_supply,
+                amount,
+            )?;
+            let denom_equiv = state.bsei_exchange_rate.mul(bsei_amount_with_fee);
+            let stsei_to_mint = decimal_division(denom_equiv, state.stsei_exchange_rate);
+            Ok((stsei_to_mint, peg_fee, denom_equiv))
+        }
+    }
+}
+
+pub(crate) fn check_min_mint_amount(
+    mint_amount: Uint128,
+    min_mint_amount: Option<Uint128>,
+) -> StdResult<()> {
+    if let Some(min_mint_amount) = min_mint_amount {
+        if mint_amount < min_mint_amount {
+            return Err(StdError::generic_err(format!(
+                "Minted amount {} is less than the minimum {}",
+                mint_amount, min_mint_amount
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn mint_message(contract: String, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
     let mint_msg = Cw20ExecuteMsg::Mint { recipient, amount };
     Ok(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -0,0 +1,294 @@
+// Copyright 2021 Anchor Protocol. Modified by Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The holders vote on chain governance proposals with the bonded value of
+//! their bSei and stSei. Once the voting ends, the hub casts the aggregate as a
+//! weighted vote of its whole delegation.
+//!
+//! The balances and exchange rates are snapshotted when a proposal is
+//! registered, so moving tokens to another address does not add voting power.
+
+use crate::state::{GovProposal, HolderVote, CONFIG, GOV_PROPOSALS, GOV_VOTES, STATE};
+use basset::hub::{OptionPower, ProposalTallyResponse, ProposalVoteResponse};
+use basset::token::SnapshotQueryMsg;
+use cosmwasm_std::{
+    attr, to_json_binary, Addr, CanonicalAddr, Decimal, Deps, DepsMut, Env, GovMsg,
+    MessageInfo, QueryRequest, Response, StdError, StdResult, Storage, Uint128, VoteOption,
+    WasmQuery, WeightedVoteOption,
+};
+use cw20::BalanceResponse;
+
+/// Open the holders' voting on a proposal.
+/// Only creator/owner is allowed to execute
+pub fn execute_register_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    voting_end: u64,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if sender_raw != config.creator {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if GOV_PROPOSALS.may_load(deps.storage, proposal_id)?.is_some() {
+        return Err(StdError::generic_err(format!(
+            "Proposal {} is already registered",
+            proposal_id
+        )));
+    }
+    if voting_end <= env.block.time.seconds() {
+        return Err(StdError::generic_err("voting_end must be in the future"));
+    }
+
+    // the balances at the start of the block cannot change anymore
+    let state = STATE.load(deps.storage)?;
+    let proposal = GovProposal {
+        voting_end,
+        snapshot_height: env.block.height,
+        bsei_exchange_rate: state.bsei_exchange_rate,
+        stsei_exchange_rate: state.stsei_exchange_rate,
+        cast: false,
+        tally: vec![],
+    };
+    GOV_PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_proposal"),
+        attr("proposal_id", proposal_id.to_string()),
+        attr("voting_end", voting_end.to_string()),
+        attr("snapshot_height", env.block.height.to_string()),
+    ]))
+}
+
+/// Vote with the bonded value of the sender's tokens at the snapshot.
+/// A new vote of the same holder replaces the previous one.
+pub fn execute_vote_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+    options: Vec<WeightedVoteOption>,
+) -> StdResult<Response> {
+    let mut proposal = load_proposal(deps.storage, proposal_id)?;
+    if proposal.cast || env.block.time.seconds() >= proposal.voting_end {
+        return Err(StdError::generic_err(format!(
+            "The voting of proposal {} has ended",
+            proposal_id
+        )));
+    }
+    check_vote_options(&options)?;
+
+    let power = query_voting_power(deps.as_ref(), &proposal, &info.sender)?;
+    if power.is_zero() {
+        return Err(StdError::generic_err("No bSei or stSei balance to vote with"));
+    }
+
+    if let Some(previous) = GOV_VOTES.may_load(deps.storage, (proposal_id, &info.sender))? {
+        for (option, option_power) in split_power(&previous) {
+            let tally = proposal
+                .tally
+                .iter_mut()
+                .find(|t| t.option == option)
+                .ok_or_else(|| StdError::generic_err("the tally misses a voted option"))?;
+            tally.power = tally.power.checked_sub(option_power)?;
+        }
+    }
+
+    let vote = HolderVote { power, options };
+    for (option, option_power) in split_power(&vote) {
+        match proposal.tally.iter_mut().find(|t| t.option == option) {
+            Some(tally) => tally.power += option_power,
+            None => proposal.tally.push(OptionPower {
+                option,
+                power: option_power,
+            }),
+        }
+    }
+    GOV_VOTES.save(deps.storage, (proposal_id, &info.sender), &vote)?;
+    GOV_PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "vote_proposal"),
+        attr("proposal_id", proposal_id.to_string()),
+        attr("voter", info.sender),
+        attr("power", power),
+    ]))
+}
+
+/// Cast the aggregated vote once the voting has ended.
+/// Permissionless
+pub fn execute_cast_proposal_vote(
+    deps: DepsMut,
+    env: Env,
+    proposal_id: u64,
+) -> StdResult<Response> {
+    let mut proposal = load_proposal(deps.storage, proposal_id)?;
+    if proposal.cast {
+        return Err(StdError::generic_err(format!(
+            "The vote on proposal {} has already been cast",
+            proposal_id
+        )));
+    }
+    if env.block.time.seconds() < proposal.voting_end {
+        return Err(StdError::generic_err(format!(
+            "The voting of proposal {} ends at {}",
+            proposal_id, proposal.voting_end
+        )));
+    }
+
+    let voted: Vec<&OptionPower> = proposal.tally.iter().filter(|t| !t.power.is_zero()).collect();
+    let total_power = voted.iter().fold(Uint128::zero(), |total, t| total + t.power);
+    if total_power.is_zero() {
+        return Err(StdError::generic_err(format!(
+            "No votes on proposal {}",
+            proposal_id
+        )));
+    }
+
+    // the weights must sum up to exactly one, so the last option takes the rest
+    let mut options: Vec<WeightedVoteOption> = vec![];
+    let mut remaining_weight = Decimal::one();
+    for (i, tally) in voted.iter().enumerate() {
+        let weight = if i + 1 == voted.len() {
+            remaining_weight
+        } else {
+            Decimal::from_ratio(tally.power, total_power)
+        };
+        remaining_weight -= weight;
+        options.push(WeightedVoteOption {
+            option: tally.option.clone(),
+            weight,
+        });
+    }
+
+    proposal.cast = true;
+    GOV_PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new()
+        .add_message(GovMsg::VoteWeighted {
+            proposal_id,
+            options,
+        })
+        .add_attributes(vec![
+            attr("action", "cast_proposal_vote"),
+            attr("proposal_id", proposal_id.to_string()),
+            attr("total_power", total_power),
+        ]))
+}
+
+pub fn query_proposal_vote(
+    deps: Deps,
+    proposal_id: u64,
+    voter: String,
+) -> StdResult<ProposalVoteResponse> {
+    load_proposal(deps.storage, proposal_id)?;
+    let voter_addr = deps.api.addr_validate(&voter)?;
+    let vote = GOV_VOTES
+        .may_load(deps.storage, (proposal_id, &voter_addr))?
+        .unwrap_or(HolderVote {
+            power: Uint128::zero(),
+            options: vec![],
+        });
+    Ok(ProposalVoteResponse {
+        proposal_id,
+        voter,
+        power: vote.power,
+        options: vote.options,
+    })
+}
+
+pub fn query_proposal_tally(deps: Deps, proposal_id: u64) -> StdResult<ProposalTallyResponse> {
+    let proposal = load_proposal(deps.storage, proposal_id)?;
+    let total_power = proposal
+        .tally
+        .iter()
+        .fold(Uint128::zero(), |total, t| total + t.power);
+    Ok(ProposalTallyResponse {
+        proposal_id,
+        voting_end: proposal.voting_end,
+        snapshot_height: proposal.snapshot_height,
+        cast: proposal.cast,
+        total_power,
+        tally: proposal.tally,
+    })
+}
+
+fn load_proposal(storage: &dyn Storage, proposal_id: u64) -> StdResult<GovProposal> {
+    GOV_PROPOSALS
+        .may_load(storage, proposal_id)?
+        .ok_or_else(|| {
+            StdError::generic_err(format!("Proposal {} is not registered", proposal_id))
+        })
+}
+
+/// The options must be distinct, with positive weights summing up to one.
+fn check_vote_options(options: &[WeightedVoteOption]) -> StdResult<()> {
+    if options.is_empty() {
+        return Err(StdError::generic_err("At least one vote option is required"));
+    }
+    let mut total_weight = Decimal::zero();
+    for (i, option) in options.iter().enumerate() {
+        if option.weight.is_zero() {
+            return Err(StdError::generic_err("Vote option weights must be positive"));
+        }
+        if options[..i].iter().any(|o| o.option == option.option) {
+            return Err(StdError::generic_err("Vote options must be distinct"));
+        }
+        total_weight += option.weight;
+    }
+    if total_weight != Decimal::one() {
+        return Err(StdError::generic_err("Vote option weights must sum up to 1"));
+    }
+    Ok(())
+}
+
+/// The power given to each option of the vote.
+fn split_power(vote: &HolderVote) -> Vec<(VoteOption, Uint128)> {
+    vote.options
+        .iter()
+        .map(|o| (o.option.clone(), vote.power * o.weight))
+        .collect()
+}
+
+/// The bonded value of the bSei and stSei held by `voter` at the snapshot of the proposal.
+fn query_voting_power(deps: Deps, proposal: &GovProposal, voter: &Addr) -> StdResult<Uint128> {
+    let config = CONFIG.load(deps.storage)?;
+    let height = proposal.snapshot_height;
+    let bsei_balance = query_token_balance_at(deps, &config.bsei_token_contract, voter, height)?;
+    let stsei_balance = query_token_balance_at(deps, &config.stsei_token_contract, voter, height)?;
+    Ok(bsei_balance * proposal.bsei_exchange_rate + stsei_balance * proposal.stsei_exchange_rate)
+}
+
+fn query_token_balance_at(
+    deps: Deps,
+    token_contract: &Option<CanonicalAddr>,
+    address: &Addr,
+    height: u64,
+) -> StdResult<Uint128> {
+    let token_contract = match token_contract {
+        Some(token_contract) => deps.api.addr_humanize(token_contract)?,
+        None => return Ok(Uint128::zero()),
+    };
+    let balance: BalanceResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: token_contract.to_string(),
+        msg: to_json_binary(&SnapshotQueryMsg::BalanceAt {
+            address: address.to_string(),
+            height,
+        })?,
+    }))?;
+    Ok(balance.balance)
+}
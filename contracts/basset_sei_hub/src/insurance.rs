@@ -0,0 +1,137 @@
+// Copyright 2021 Anchor Protocol. Modified by Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The optional insurance fund covers the slashing of the delegations. A loss
+//! noticed by `slashing` is claimed from the fund, up to the draw cap, by the
+//! message that noticed it. The fund sends what it holds of the claim back with
+//! `RestoreBond`, and the hub rebonds the coins for the holders. The part of a
+//! claim the fund could not cover is recorded, but never drawn again.
+//!
+//! The shortfall of a released unbond batch is not claimed. The unbonded tokens
+//! have been burned already, so rebonding coins for it would raise the exchange
+//! rates of the remaining holders instead of paying the unbonding ones.
+
+use crate::bond::{bonded_coin, delegation_messages};
+use crate::contract::slashing;
+use crate::state::{
+    read_insurance_draws, store_insurance_draw, CONFIG, CURRENT_BATCH, INSURANCE_CLAIM,
+    PARAMETERS, STATE,
+};
+use crate::token_class::{
+    bonded_shares, query_total_class_issued, read_classes, store_classes, total_bonded,
+};
+use basset::hub::{InsuranceDraw, InsuranceDrawsResponse};
+use basset_sei_insurance_fund::msg::ExecuteMsg as InsuranceExecuteMsg;
+use cosmwasm_std::{
+    attr, to_json_binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+
+/// Claim a slashing `loss` from the insurance fund, up to the draw cap, and return the
+/// message drawing it. The new claim replaces what is left of the previous one.
+/// Nothing is claimed while no fund is registered.
+pub(crate) fn claim_insurance(deps: &mut DepsMut, loss: Uint128) -> StdResult<Option<CosmosMsg>> {
+    let fund = match CONFIG.load(deps.storage)?.insurance_fund_contract {
+        Some(fund) => fund,
+        None => return Ok(None),
+    };
+    let params = PARAMETERS.load(deps.storage)?;
+    let claimed = params.insurance_draw_cap.map_or(loss, |cap| loss.min(cap));
+    if claimed.is_zero() {
+        return Ok(None);
+    }
+    INSURANCE_CLAIM.save(deps.storage, &claimed)?;
+    Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: deps.api.addr_humanize(&fund)?.to_string(),
+        msg: to_json_binary(&InsuranceExecuteMsg::Draw {
+            denom: params.underlying_coin_denom,
+            amount: claimed,
+        })?,
+        funds: vec![],
+    })))
+}
+
+/// Rebond the coins drawn from the insurance fund. They are shared among the token
+/// classes in proportion to their bonded amounts, which raises the exchange rates
+/// back.
+/// Only the insurance fund is allowed to execute
+pub fn execute_restore_bond(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let fund = config.insurance_fund_contract.clone().ok_or_else(|| {
+        StdError::generic_err("the insurance fund contract must have been registered")
+    })?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != fund {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let coin_denom = PARAMETERS.load(deps.storage)?.underlying_coin_denom;
+    let payment = bonded_coin(&info, &coin_denom)?;
+    let time = env.block.time.seconds();
+
+    // the payment settles the current claim, before a new loss can replace it
+    let claim = INSURANCE_CLAIM.may_load(deps.storage)?.unwrap_or_default();
+    INSURANCE_CLAIM.save(deps.storage, &claim.saturating_sub(payment.amount))?;
+
+    // check slashing
+    let (mut state, draw_msg) = slashing(&mut deps, env)?;
+
+    let mut current_batch = CURRENT_BATCH.load(deps.storage)?;
+    let mut classes = read_classes(deps.as_ref(), &state, &current_batch)?;
+    if total_bonded(&classes).is_zero() {
+        return Err(StdError::generic_err("there is no bonded amount to restore"));
+    }
+
+    let shares = bonded_shares(payment.amount, &classes);
+    for (class, share) in classes.iter_mut().zip(shares) {
+        class.total_bond_amount += share;
+        let total_issued = query_total_class_issued(deps.as_ref(), class)?;
+        class.update_exchange_rate(total_issued);
+    }
+    store_classes(deps.storage, &mut state, &mut current_batch, &classes)?;
+    STATE.save(deps.storage, &state)?;
+
+    store_insurance_draw(
+        deps.storage,
+        InsuranceDraw {
+            id: 0,
+            time,
+            amount: payment.amount,
+            bsei_exchange_rate: state.bsei_exchange_rate,
+            stsei_exchange_rate: state.stsei_exchange_rate,
+        },
+    )?;
+
+    let messages = delegation_messages(&deps, &config, payment.clone())?;
+    Ok(Response::new().add_messages(messages).add_messages(draw_msg).add_attributes(vec![
+        attr("action", "restore_bond"),
+        attr("amount", payment.amount),
+        attr("new_bsei_exchange_rate", state.bsei_exchange_rate.to_string()),
+        attr("new_stsei_exchange_rate", state.stsei_exchange_rate.to_string()),
+    ]))
+}
+
+pub(crate) fn query_insurance_draws(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<InsuranceDrawsResponse> {
+    Ok(InsuranceDrawsResponse {
+        uncovered_claim: INSURANCE_CLAIM.may_load(deps.storage)?.unwrap_or_default(),
+        draws: read_insurance_draws(deps.storage, start_after, limit)?,
+    })
+}
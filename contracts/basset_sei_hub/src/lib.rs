@@ -18,8 +18,11 @@ pub mod state;
 mod bond;
 mod config;
 mod math;
+mod token_class;
 mod unbond;
 
 mod convert;
+mod gov;
+mod insurance;
 #[cfg(test)]
 mod testing;
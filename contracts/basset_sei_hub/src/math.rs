@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use cosmwasm_std::{Decimal, Uint128};
+use basset::hub::{Parameters, State, TokenClass};
+use cosmwasm_std::{Decimal, StdResult, Uint128};
 
 const DECIMAL_FRACTIONAL: u128 = 1_000_000_000_000_000_000u128;
 
@@ -22,6 +23,72 @@ pub fn decimal_division(a: Uint128, b: Decimal) -> Uint128 {
     decimal * Uint128::from(DECIMAL_FRACTIONAL)
 }
 
+/// The figures of a token that the peg recovery fee depends on.
+pub(crate) struct Peg {
+    pub exchange_rate: Decimal,
+    pub er_threshold: Decimal,
+    pub peg_recovery_fee: Decimal,
+    pub total_bond_amount: Uint128,
+    pub requested_with_fee: Uint128,
+}
+
+impl Peg {
+    pub fn bsei(state: &State, params: &Parameters, requested_bsei_with_fee: Uint128) -> Self {
+        Peg {
+            exchange_rate: state.bsei_exchange_rate,
+            er_threshold: params.er_threshold,
+            peg_recovery_fee: params.peg_recovery_fee,
+            total_bond_amount: state.total_bond_bsei_amount,
+            requested_with_fee: requested_bsei_with_fee,
+        }
+    }
+
+    pub fn class(class: &TokenClass) -> Self {
+        Peg {
+            exchange_rate: class.exchange_rate,
+            er_threshold: class.er_threshold,
+            peg_recovery_fee: class.peg_recovery_fee,
+            total_bond_amount: class.total_bond_amount,
+            requested_with_fee: class.requested_amount,
+        }
+    }
+
+    /// Returns the tokens minted for `amount` of the underlying coin and the
+    /// peg recovery fee taken from them.
+    pub fn mint_amount(
+        &self,
+        total_supply: Uint128,
+        amount: Uint128,
+    ) -> StdResult<(Uint128, Uint128)> {
+        let mint_amount = decimal_division(amount, self.exchange_rate);
+        let mut peg_fee = Uint128::zero();
+        if self.exchange_rate < self.er_threshold {
+            let max_peg_fee = mint_amount * self.peg_recovery_fee;
+            let required_peg_fee = (total_supply + mint_amount + self.requested_with_fee)
+                - (self.total_bond_amount + amount);
+            peg_fee = Uint128::min(max_peg_fee, required_peg_fee);
+        }
+        Ok((mint_amount.checked_sub(peg_fee)?, peg_fee))
+    }
+
+    /// Returns the tokens that are unbonded out of `amount` and the peg
+    /// recovery fee taken from them.
+    pub fn unbond_amount(
+        &self,
+        total_supply: Uint128,
+        amount: Uint128,
+    ) -> StdResult<(Uint128, Uint128)> {
+        let mut peg_fee = Uint128::zero();
+        if self.exchange_rate < self.er_threshold {
+            let max_peg_fee = amount * self.peg_recovery_fee;
+            let required_peg_fee =
+                (total_supply + self.requested_with_fee).checked_sub(self.total_bond_amount)?;
+            peg_fee = Uint128::min(max_peg_fee, required_peg_fee);
+        }
+        Ok((amount.checked_sub(peg_fee)?, peg_fee))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -14,17 +14,22 @@
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
 
 use cosmwasm_std::{
-    attr,from_json, to_json_vec, Decimal, Order, Response, StdError, StdResult, Storage, Uint128, CanonicalAddr,
+    attr,from_json, to_json_vec, Binary, Decimal, Empty, Order, Response, StdError, StdResult, Storage,
+    Uint128, CanonicalAddr, Addr, WeightedVoteOption,
 };
 use cosmwasm_storage::{Bucket, PrefixedStorage, ReadonlyBucket, ReadonlyPrefixedStorage, Singleton, ReadonlySingleton};
 
-use cw_storage_plus::Item;
+use cw_storage_plus::{Bound, Item, Map};
+
+use cosmwasm_bignumber::Uint256;
 
 use basset::hub::{
-    Config, CurrentBatch, OldConfig, OldCurrentBatch, OldState, Parameters, State, UnbondHistory,
-    UnbondRequest, UnbondType, UnbondWaitEntity,
+    ClassUnbondAmount, Config, CurrentBatch, InsuranceDraw, OldConfig, OldCurrentBatch, OldState,
+    OptionPower, Parameters, SlashingEvent, State, TokenClass, UnbondHistory, UnbondRequest,
+    UnbondType, UnbondWaitEntity, BSEI_CLASS, STSEI_CLASS,
 };
 
 pub const CONFIG: Item<Config> = Item::new("\u{0}\u{6}config");
@@ -32,12 +37,32 @@ pub const PARAMETERS: Item<Parameters> = Item::new("\u{0}\u{b}parameteres");
 pub const CURRENT_BATCH: Item<CurrentBatch> = Item::new("\u{0}\u{d}current_batch");
 pub const STATE: Item<State> = Item::new("\u{0}\u{5}state");
 
+pub const RELEASING_BATCHES: Item<ReleasingBatches> = Item::new("releasing_batches");
+
+/// The registered token classes by name
+pub const TOKEN_CLASSES: Map<&str, TokenClass> = Map::new("token_classes");
+
+/// The recorded slashing events, by id
+pub const SLASHING_EVENTS: Map<u64, SlashingEvent> = Map::new("slashing_events");
+
+/// The part of the last insurance claim that the fund has not covered yet
+pub const INSURANCE_CLAIM: Item<Uint128> = Item::new("insurance_claim");
+/// The draws from the insurance fund, by id
+pub const INSURANCE_DRAWS: Map<u64, InsuranceDraw> = Map::new("insurance_draws");
+
+/// The governance proposals open to the holders' vote, by proposal id
+pub const GOV_PROPOSALS: Map<u64, GovProposal> = Map::new("gov_proposals");
+/// The holders' votes, by proposal id and voter
+pub const GOV_VOTES: Map<(u64, &Addr), HolderVote> = Map::new("gov_votes");
+
 pub const OLD_CONFIG: Item<OldConfig> = Item::new("\u{0}\u{6}config");
 pub const OLD_CURRENT_BATCH: Item<OldCurrentBatch> = Item::new("\u{0}\u{d}current_batch");
 pub const OLD_STATE: Item<OldState> = Item::new("\u{0}\u{5}state");
 
 pub static OLD_PREFIX_WAIT_MAP: &[u8] = b"wait";
 pub static NEW_PREFIX_WAIT_MAP: &[u8] = b"v2_wait";
+pub static PREFIX_BATCH_WAIT_MAP: &[u8] = b"batch_wait";
+pub static PREFIX_USER_WAIT_MAP: &[u8] = b"user_wait";
 pub static UNBOND_HISTORY_MAP: &[u8] = b"history_map";
 pub static PREFIX_AIRDROP_INFO: &[u8] = b"airedrop_info";
 pub static VALIDATORS: &[u8] = b"validators";
@@ -46,8 +71,98 @@ pub static KEY_NEWOWNER: &[u8] = b"newowner";
 
 pub const MAX_DEFAULT_RANGE_LIMIT: u32 = 1000;
 
+/// Used when `max_unbond_requests` has not been set in the parameters.
+pub const DEFAULT_MAX_UNBOND_REQUESTS: u32 = 50;
+
+/// Every class is visited on each bond, unbond and slashing check.
+pub const MAX_TOKEN_CLASSES: usize = 10;
+
 
 
+/// A chain governance proposal the holders vote on through the hub.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GovProposal {
+    pub voting_end: u64,
+    /// The votes are weighed with the balances at the start of this block
+    pub snapshot_height: u64,
+    /// The exchange rates at the snapshot, valuing the snapshotted balances
+    pub bsei_exchange_rate: Decimal,
+    pub stsei_exchange_rate: Decimal,
+    pub cast: bool,
+    /// The voting power given to each option
+    pub tally: Vec<OptionPower>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HolderVote {
+    pub power: Uint128,
+    pub options: Vec<WeightedVoteOption>,
+}
+
+/// The matured batches that are being released over several calls.
+/// The window starts right after `State::last_processed_batch`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct ReleasingBatches {
+    /// The last batch that has been added to the window.
+    pub last_batch: u64,
+    /// Set once all matured batches have been added and the received amounts are known.
+    pub sealed: bool,
+    pub bsei_total_unbonded_amount: Uint256,
+    pub stsei_total_unbonded_amount: Uint256,
+    pub bsei_actual_unbonded_amount: Uint256,
+    pub stsei_actual_unbonded_amount: Uint256,
+    pub classes: Option<Vec<ReleasingClass>>,
+}
+
+impl ReleasingBatches {
+    /// The amounts of each token class in the window, bSei and stSei first.
+    pub fn releasing_classes(&self) -> Vec<ReleasingClass> {
+        let mut classes = vec![
+            ReleasingClass {
+                class: BSEI_CLASS.to_string(),
+                total_unbonded_amount: self.bsei_total_unbonded_amount,
+                actual_unbonded_amount: self.bsei_actual_unbonded_amount,
+            },
+            ReleasingClass {
+                class: STSEI_CLASS.to_string(),
+                total_unbonded_amount: self.stsei_total_unbonded_amount,
+                actual_unbonded_amount: self.stsei_actual_unbonded_amount,
+            },
+        ];
+        classes.extend(self.classes.iter().flatten().cloned());
+        classes
+    }
+
+    /// Set the amounts of a token class in the window.
+    pub fn set_releasing_class(&mut self, releasing: ReleasingClass) {
+        match releasing.class.as_str() {
+            BSEI_CLASS => {
+                self.bsei_total_unbonded_amount = releasing.total_unbonded_amount;
+                self.bsei_actual_unbonded_amount = releasing.actual_unbonded_amount;
+            }
+            STSEI_CLASS => {
+                self.stsei_total_unbonded_amount = releasing.total_unbonded_amount;
+                self.stsei_actual_unbonded_amount = releasing.actual_unbonded_amount;
+            }
+            _ => {
+                let classes = self.classes.get_or_insert_with(Vec::new);
+                match classes.iter_mut().find(|c| c.class == releasing.class) {
+                    Some(class) => *class = releasing,
+                    None => classes.push(releasing),
+                }
+            }
+        }
+    }
+}
+
+/// The amounts of a token class in the releasing window.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReleasingClass {
+    pub class: String,
+    pub total_unbonded_amount: Uint256,
+    pub actual_unbonded_amount: Uint256,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct NewOwnerAddr {
     pub new_owner_addr: CanonicalAddr, 
@@ -63,27 +178,65 @@ pub fn read_new_owner(storage: &dyn Storage) -> StdResult<NewOwnerAddr> {
 
 /// Store undelegation wait list per each batch
 /// HashMap<user's address, <batch_id, requested_amount>
+/// A new batch entry is rejected once the user has `max_requests` open requests.
 pub fn store_unbond_wait_list(
     storage: &mut dyn Storage,
     batch_id: u64,
     sender_address: String,
     amount: Uint128,
     unbond_type: UnbondType,
+    max_requests: u32,
 ) -> StdResult<()> {
     let batch = to_json_vec(&batch_id)?;
     let addr = to_json_vec(&sender_address)?;
     let mut position_indexer: Bucket<UnbondWaitEntity> =
         Bucket::multilevel(storage, &[NEW_PREFIX_WAIT_MAP, &addr]);
+
+    if position_indexer.may_load(&batch)?.is_none() {
+        let open_requests = position_indexer
+            .range(None, None, Order::Ascending)
+            .take(max_requests as usize)
+            .count();
+        if open_requests >= max_requests as usize {
+            return Err(StdError::generic_err(format!(
+                "Too many open unbond requests, the limit is {}. Withdraw the released ones first",
+                max_requests
+            )));
+        }
+    }
+
     position_indexer.update(&batch, |asked_already| -> StdResult<UnbondWaitEntity> {
         let mut wl = asked_already.unwrap_or_default();
         match unbond_type {
             UnbondType::BSei => wl.bsei_amount += amount,
             UnbondType::StSei => wl.stsei_amount += amount,
+            UnbondType::Class(class) => {
+                let classes = wl.classes.get_or_insert_with(Vec::new);
+                match classes.iter_mut().find(|requested| requested.class == class) {
+                    Some(requested) => requested.amount += amount,
+                    None => classes.push(ClassUnbondAmount { class, amount }),
+                }
+            }
         }
         Ok(wl)
     })?;
 
-    Ok(())
+    store_batch_wait_index(storage, batch_id, &sender_address)
+}
+
+/// Store the reverse index of the wait list
+/// HashMap<batch_id, <user's address>>
+/// and the numeric index of the user's batches
+/// HashMap<user's address, <big endian batch_id>>
+fn store_batch_wait_index(
+    storage: &mut dyn Storage,
+    batch_id: u64,
+    sender_address: &str,
+) -> StdResult<()> {
+    Bucket::<Empty>::multilevel(storage, &[PREFIX_BATCH_WAIT_MAP, &batch_id.to_be_bytes()])
+        .save(sender_address.as_bytes(), &Empty {})?;
+    Bucket::<Empty>::multilevel(storage, &[PREFIX_USER_WAIT_MAP, sender_address.as_bytes()])
+        .save(&batch_id.to_be_bytes(), &Empty {})
 }
 
 /// Remove unbond batch id from user's wait list
@@ -95,13 +248,85 @@ pub fn remove_unbond_wait_list(
     let addr = to_json_vec(&sender_address)?;
     let mut position_indexer: Bucket<UnbondWaitEntity> =
         Bucket::multilevel(storage, &[NEW_PREFIX_WAIT_MAP, &addr]);
-    for b in batch_id {
-        let batch = to_json_vec(&b)?;
+    for b in &batch_id {
+        let batch = to_json_vec(b)?;
         position_indexer.remove(&batch);
     }
+    for b in batch_id {
+        Bucket::<Empty>::multilevel(storage, &[PREFIX_BATCH_WAIT_MAP, &b.to_be_bytes()])
+            .remove(sender_address.as_bytes());
+        Bucket::<Empty>::multilevel(storage, &[PREFIX_USER_WAIT_MAP, sender_address.as_bytes()])
+            .remove(&b.to_be_bytes());
+    }
     Ok(())
 }
 
+/// Return a page of the users that have a request in the batch, ordered by address.
+pub fn get_batch_requests(
+    storage: &dyn Storage,
+    batch_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, UnbondWaitEntity)>> {
+    let lim = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|address| {
+        let mut key = address.into_bytes();
+        key.push(0);
+        key
+    });
+    let index: ReadonlyBucket<Empty> =
+        ReadonlyBucket::multilevel(storage, &[PREFIX_BATCH_WAIT_MAP, &batch_id.to_be_bytes()]);
+    index
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(lim)
+        .map(|item| {
+            let (k, _) = item?;
+            let address = String::from_utf8(k)
+                .map_err(|_| StdError::generic_err("invalid address in the batch index"))?;
+            let request = read_unbond_wait_list(storage, batch_id, address.clone())?;
+            Ok((address, request))
+        })
+        .collect()
+}
+
+/// Add a raw wait list key to the reverse index.
+/// The key is made of the length prefixed json address and the json batch id.
+fn index_wait_list_key(storage: &mut dyn Storage, key: &[u8]) -> StdResult<()> {
+    if key.len() < 2 {
+        return Err(StdError::generic_err("invalid wait list key"));
+    }
+    let addr_len = u16::from_be_bytes([key[0], key[1]]) as usize;
+    if key.len() < 2 + addr_len {
+        return Err(StdError::generic_err("invalid wait list key"));
+    }
+    let address: String = from_json(&key[2..2 + addr_len])?;
+    let batch_id: u64 = from_json(&key[2 + addr_len..])?;
+    store_batch_wait_index(storage, batch_id, &address)
+}
+
+/// Add a page of the existing wait list entries to the reverse index.
+/// Returns the last indexed key, to be used as `start_after` of the next page.
+pub fn index_unbond_wait_lists(
+    storage: &mut dyn Storage,
+    start_after: Option<Binary>,
+    limit: Option<u32>,
+) -> StdResult<Option<Binary>> {
+    let start = start_after.map(|key| {
+        let mut key = key.to_vec();
+        key.push(0);
+        key
+    });
+    let keys = ReadonlyBucket::<UnbondWaitEntity>::multilevel(storage, &[NEW_PREFIX_WAIT_MAP])
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit.unwrap_or(MAX_DEFAULT_RANGE_LIMIT) as usize)
+        .map(|item| item.map(|(k, _)| k))
+        .collect::<StdResult<Vec<Vec<u8>>>>()?;
+    for key in &keys {
+        index_wait_list_key(storage, key)?;
+    }
+    Ok(keys.last().map(|key| Binary::from(key.as_slice())))
+}
+
 pub fn read_unbond_wait_list(
     storage: &dyn Storage,
     batch_id: u64,
@@ -128,27 +353,107 @@ pub fn get_unbond_requests(storage: &dyn Storage, sender_addr: String) -> StdRes
     Ok(requests)
 }
 
-/// Return all requested unbond amount.
+/// Return a page of the unbond requests of a user, ordered by batch id.
+pub fn get_unbond_requests_page(
+    storage: &dyn Storage,
+    sender_addr: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, UnbondWaitEntity)>> {
+    let lim = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    user_wait_batches(storage, &sender_addr, start_after, lim)?
+        .into_iter()
+        .map(|batch_id| {
+            let request = read_unbond_wait_list(storage, batch_id, sender_addr.clone())?;
+            Ok((batch_id, request))
+        })
+        .collect()
+}
+
+/// Return at most `limit` batch ids of the user's requests after `start_after`, in numeric
+/// order. The requests stored before the user index are listed once they have been indexed.
+fn user_wait_batches(
+    storage: &dyn Storage,
+    sender_addr: &str,
+    start_after: Option<u64>,
+    limit: usize,
+) -> StdResult<Vec<u64>> {
+    let start = match start_after {
+        Some(u64::MAX) => return Ok(vec![]),
+        Some(batch_id) => Some((batch_id + 1).to_be_bytes()),
+        None => None,
+    };
+    let index: ReadonlyBucket<Empty> =
+        ReadonlyBucket::multilevel(storage, &[PREFIX_USER_WAIT_MAP, sender_addr.as_bytes()]);
+    index
+        .range(start.as_ref().map(|key| key.as_slice()), None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, _) = item?;
+            let key: [u8; 8] = k
+                .as_slice()
+                .try_into()
+                .map_err(|_| StdError::generic_err("invalid batch id in the user index"))?;
+            Ok(u64::from_be_bytes(key))
+        })
+        .collect()
+}
+
+/// Return the requested unbond amount of released batches.
 /// This needs to be called after process withdraw rate function.
 /// If the batch is released, this will return user's requested
 /// amount proportional to withdraw rate.
+/// At most `limit` released batches are collected. If `batch_ids` is given,
+/// only these batches are considered and all of them must have been released.
 pub fn get_finished_amount(
     storage: &dyn Storage,
     sender_addr: String,
+    batch_ids: Option<Vec<u64>>,
+    limit: Option<u32>,
 ) -> StdResult<(Uint128, Vec<u64>)> {
     let vec = to_json_vec(&sender_addr)?;
+    let lim = limit.unwrap_or(MAX_LIMIT).min(MAX_LIMIT) as usize;
     let mut withdrawable_amount: Uint128 = Uint128::zero();
     let mut deprecated_batches: Vec<u64> = vec![];
     let res: ReadonlyBucket<UnbondWaitEntity> =
         ReadonlyBucket::multilevel(storage, &[NEW_PREFIX_WAIT_MAP, &vec]);
+
+    if let Some(mut batch_ids) = batch_ids {
+        batch_ids.sort_unstable();
+        batch_ids.dedup();
+        if batch_ids.len() > lim {
+            return Err(StdError::generic_err(format!(
+                "Can not withdraw more than {} batches at once",
+                lim
+            )));
+        }
+        for user_batch in batch_ids {
+            let v = res.may_load(&to_json_vec(&user_batch)?)?.ok_or_else(|| {
+                StdError::generic_err(format!("No unbond request found for batch {}", user_batch))
+            })?;
+            let h = read_unbond_history(storage, user_batch)?;
+            if !h.released {
+                return Err(StdError::generic_err(format!(
+                    "Batch {} has not been released yet",
+                    user_batch
+                )));
+            }
+            withdrawable_amount += h.withdraw_amount(&v);
+            deprecated_batches.push(user_batch);
+        }
+        return Ok((withdrawable_amount, deprecated_batches));
+    }
+
     for item in res.range(None, None, Order::Ascending) {
+        if deprecated_batches.len() >= lim {
+            break;
+        }
         let (k, v) = item?;
         let user_batch: u64 =from_json(&k)?;
         let history = read_unbond_history(storage, user_batch);
         if let Ok(h) = history {
             if h.released {
-                withdrawable_amount +=
-                    v.stsei_amount * h.stsei_withdraw_rate + v.bsei_amount * h.bsei_withdraw_rate;
+                withdrawable_amount += h.withdraw_amount(&v);
                 deprecated_batches.push(user_batch);
             }
         }
@@ -156,28 +461,35 @@ pub fn get_finished_amount(
     Ok((withdrawable_amount, deprecated_batches))
 }
 
-/// Return the finished amount for all batches that has been before the given block time.
+/// Return the finished amount for the batches that have been before the given block time.
+/// At most `limit` wait list entries are scanned in batch id order, starting after the
+/// `start_after` batch. The batch id to continue from is returned if the page has been filled up.
 pub fn query_get_finished_amount(
     storage: &dyn Storage,
     sender_addr: String,
     block_time: u64,
-) -> StdResult<Uint128> {
-    let vec = to_json_vec(&sender_addr)?;
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<(Uint128, Option<u64>)> {
+    let lim = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
     let mut withdrawable_amount: Uint128 = Uint128::zero();
-    let res: ReadonlyBucket<UnbondWaitEntity> =
-        ReadonlyBucket::multilevel(storage, &[NEW_PREFIX_WAIT_MAP, &vec]);
-    for item in res.range(None, None, Order::Ascending) {
-        let (k, v) = item?;
-        let user_batch: u64 =from_json(&k)?;
+    let mut scanned: usize = 0;
+    let mut last_batch: Option<u64> = None;
+    for user_batch in user_wait_batches(storage, &sender_addr, start_after, lim)? {
+        let v = read_unbond_wait_list(storage, user_batch, sender_addr.clone())?;
         let history = read_unbond_history(storage, user_batch);
         if let Ok(h) = history {
             if h.time < block_time {
-                withdrawable_amount +=
-                    v.stsei_amount * h.stsei_withdraw_rate + v.bsei_amount * h.bsei_withdraw_rate;
+                withdrawable_amount += h.withdraw_amount(&v);
             }
         }
+        scanned += 1;
+        last_batch = Some(user_batch);
     }
-    Ok(withdrawable_amount)
+
+    let next_start_after = if scanned == lim { last_batch } else { None };
+    Ok((withdrawable_amount, next_start_after))
 }
 
 /// Store unbond history map
@@ -209,6 +521,83 @@ pub fn read_unbond_history(storage: &dyn Storage, epoc_id: u64) -> StdResult<Unb
 const MAX_LIMIT: u32 = 100;
 const DEFAULT_LIMIT: u32 = 10;
 
+pub fn read_token_classes(storage: &dyn Storage) -> StdResult<Vec<TokenClass>> {
+    TOKEN_CLASSES
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, class)| class))
+        .collect()
+}
+
+/// Return a page of the token classes, in the order of `classes`.
+pub fn token_classes_page(
+    mut classes: Vec<TokenClass>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Vec<TokenClass> {
+    let lim = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    if let Some(start_after) = start_after {
+        let start = classes
+            .iter()
+            .position(|class| class.name == start_after)
+            .map_or(classes.len(), |position| position + 1);
+        classes.drain(..start);
+    }
+    classes.truncate(lim);
+    classes
+}
+
+/// Record a slashing event under the next id and return the id.
+pub fn store_slashing_event(storage: &mut dyn Storage, mut event: SlashingEvent) -> StdResult<u64> {
+    let last_id = SLASHING_EVENTS
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?;
+    event.id = last_id.map_or(1, |id| id + 1);
+    SLASHING_EVENTS.save(storage, event.id, &event)?;
+    Ok(event.id)
+}
+
+/// Return a page of the slashing events, ordered by id.
+pub fn read_slashing_events(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<SlashingEvent>> {
+    let lim = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    SLASHING_EVENTS
+        .range(storage, start, None, Order::Ascending)
+        .take(lim)
+        .map(|item| item.map(|(_, event)| event))
+        .collect()
+}
+
+/// Record a draw from the insurance fund under the next id and return the id.
+pub fn store_insurance_draw(storage: &mut dyn Storage, mut draw: InsuranceDraw) -> StdResult<u64> {
+    let last_id = INSURANCE_DRAWS
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?;
+    draw.id = last_id.map_or(1, |id| id + 1);
+    INSURANCE_DRAWS.save(storage, draw.id, &draw)?;
+    Ok(draw.id)
+}
+
+/// Return a page of the insurance fund draws, ordered by id.
+pub fn read_insurance_draws(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<InsuranceDraw>> {
+    let lim = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    INSURANCE_DRAWS
+        .range(storage, start, None, Order::Ascending)
+        .take(lim)
+        .map(|item| item.map(|(_, draw)| draw))
+        .collect()
+}
+
 /// Return all unbond_history from UnbondHistory map
 #[allow(clippy::needless_lifetimes)]
 pub fn all_unbond_history(
@@ -302,6 +691,7 @@ pub fn migrate_unbond_wait_lists(
             let unbond_wait_entity = UnbondWaitEntity {
                 bsei_amount: amount,
                 stsei_amount: Uint128::zero(),
+                classes: None,
             };
             new_unbond_wait_list.save(&key, &unbond_wait_entity)?;
             removed_keys.push(key);
@@ -311,6 +701,10 @@ pub fn migrate_unbond_wait_lists(
         (removed_keys, num_migrated_entries)
     };
 
+    for key in &removed_keys {
+        index_wait_list_key(storage, key)?;
+    }
+
     let mut old_unbond_wait_list: Bucket<Uint128> =
         Bucket::multilevel(storage, &[OLD_PREFIX_WAIT_MAP]);
     for key in removed_keys {
@@ -360,6 +754,7 @@ pub fn migrate_unbond_history(storage: &mut dyn Storage) -> StdResult<()> {
                     stsei_applied_exchange_rate: Decimal::one(),
                     stsei_withdraw_rate: Decimal::one(),
                     released: old_history.released,
+                    classes: None,
                 };
                 Ok(new_history)
             })
@@ -14,19 +14,20 @@
 
 use std::collections::HashMap;
 
-use basset::common::{QueryTaxWrapper, QueryTaxMsg, TaxRateResponse, TaxCapResponse};
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     from_json, to_json_binary, to_json_vec, AllBalanceResponse, Api, BalanceResponse,
     BankQuery, Coin, ContractResult, CustomQuery, Empty, FullDelegation, OwnedDeps, Querier,
     QuerierResult, QueryRequest, StdError, StdResult, SystemError, SystemResult, Uint128,
-    Validator, WasmQuery, Addr, Decimal,
+    Validator, WasmQuery, Addr,
 };
 use cosmwasm_storage::to_length_prefixed;
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+use sei_cosmwasm::{Epoch, EpochResponse, SeiQuery, SeiQueryWrapper};
 use serde::de::DeserializeOwned;
 
 use basset::hub::Config;
+use basset::token::SnapshotQueryMsg;
 use basset_sei_validators_registry::registry::ValidatorResponse as RegistryValidator;
 
 pub const VALIDATORS_REGISTRY: &str = "validators_registry";
@@ -45,43 +46,18 @@ pub fn mock_dependencies(
     }
 }
 
-
-#[derive(Clone, Default)]
-pub struct TaxQuerier {
-    rate: Decimal,
-    caps: HashMap<String, Uint128>,
-}
-
-impl TaxQuerier {
-    pub fn _new(rate: Decimal, caps: &[(&String, &Uint128)]) -> Self {
-        TaxQuerier {
-            rate,
-            caps: _caps_to_map(caps),
-        }
-    }
-}
-
-
-pub(crate) fn _caps_to_map(caps: &[(&String, &Uint128)]) -> HashMap<String, Uint128> {
-    let mut owner_map: HashMap<String, Uint128> = HashMap::new();
-    for (denom, cap) in caps.iter() {
-        owner_map.insert(denom.to_string(), **cap);
-    }
-    owner_map
-}
-
 pub struct WasmMockQuerier {
-    base: MockQuerier<QueryTaxWrapper>,
+    base: MockQuerier<SeiQueryWrapper>,
     token_querier: TokenQuerier,
     balance_querier: BalanceQuerier,
-    tax_querier: TaxQuerier,
+    sei_epoch: Option<Epoch>,
     validators: Vec<RegistryValidator>,
 }
 
 impl Querier for WasmMockQuerier {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
         // MockQuerier doesn't support Custom, so we ignore it completely here
-        let request: QueryRequest<QueryTaxWrapper> = match from_json(bin_request) {
+        let request: QueryRequest<SeiQueryWrapper> = match from_json(bin_request) {
             Ok(v) => v,
             Err(e) => {
                 return QuerierResult::Err(SystemError::InvalidRequest {
@@ -95,36 +71,43 @@ impl Querier for WasmMockQuerier {
 }
 
 impl WasmMockQuerier {
-    pub fn handle_query(&self, request: &QueryRequest<QueryTaxWrapper>) -> QuerierResult {
+    pub fn handle_query(&self, request: &QueryRequest<SeiQueryWrapper>) -> QuerierResult {
         match &request {
-            QueryRequest::Custom(QueryTaxWrapper { query_data }) => {
-                match query_data {
-                    QueryTaxMsg::TaxRate {} => {
-                        let res = TaxRateResponse {
-                            rate: self.tax_querier.rate,
+            QueryRequest::Custom(SeiQueryWrapper { query_data, .. }) => match query_data {
+                SeiQuery::Epoch {} => match &self.sei_epoch {
+                    Some(epoch) => {
+                        let res = EpochResponse {
+                            epoch: epoch.clone(),
                         };
                         SystemResult::Ok(ContractResult::from(to_json_binary(&res)))
                     }
-                    QueryTaxMsg::TaxCap { denom } => {
-                        let cap = self
-                            .tax_querier
-                            .caps
-                            .get(denom)
-                            .copied()
-                            .unwrap_or_default();
-                        let res = TaxCapResponse { cap };
-                        SystemResult::Ok(ContractResult::from(to_json_binary(&res)))
-                        }
-                      _ => panic!("DO NOT ENTER HERE"),
-                    }
-                } 
-            
+                    None => SystemResult::Err(SystemError::UnsupportedRequest {
+                        kind: "epoch".to_string(),
+                    }),
+                },
+                _ => unimplemented!(),
+            },
             QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
                 if contract_addr == VALIDATORS_REGISTRY {
                     let mut validators = self.validators.clone();
                     validators.sort_by(|v1, v2| v1.total_delegated.cmp(&v2.total_delegated));
                     return SystemResult::Ok(ContractResult::from(to_json_binary(&validators)));
                 }
+                if let Ok(SnapshotQueryMsg::BalanceAt { address, height }) = from_json(msg) {
+                    // a balance without a snapshot at `height` has not changed since
+                    let balance = self
+                        .token_querier
+                        .balances_at
+                        .get(&height)
+                        .or(Some(&self.token_querier.balances))
+                        .and_then(|balances| balances.get(contract_addr))
+                        .and_then(|balances| balances.get(&address))
+                        .copied()
+                        .unwrap_or_default();
+                    return SystemResult::Ok(ContractResult::Ok(
+                        to_json_binary(&Cw20BalanceResponse { balance }).unwrap(),
+                    ));
+                }
                 match from_json(msg).unwrap() {
                     Cw20QueryMsg::TokenInfo {} => {
                         let balances: &HashMap<String, Uint128> =
@@ -228,6 +211,7 @@ impl WasmMockQuerier {
                         rewards_contract: Some(
                             api.addr_canonicalize(&String::from("rewards")).unwrap(),
                         ),
+                        insurance_fund_contract: None,
                     };
                     QuerierResult::Ok(ContractResult::from(to_json_binary(
                         &to_json_binary(&config).unwrap(),
@@ -351,14 +335,8 @@ impl BalanceQuerier {
 #[derive(Clone, Default)]
 pub struct TokenQuerier {
     balances: HashMap<String, HashMap<String, Uint128>>,
-}
-
-impl TokenQuerier {
-    pub fn new(balances: &[(&String, &[(&String, &Uint128)])]) -> Self {
-        TokenQuerier {
-            balances: balances_to_map(balances),
-        }
-    }
+    /// The balance snapshots, by height
+    balances_at: HashMap<u64, HashMap<String, HashMap<String, Uint128>>>,
 }
 
 pub(crate) fn native_balances_to_map(balances: &[(String, Coin)]) -> HashMap<String, Coin> {
@@ -389,10 +367,10 @@ pub(crate) fn balances_to_map(
 }
 
 impl WasmMockQuerier {
-    pub fn new(base: MockQuerier<QueryTaxWrapper>) -> Self {
+    pub fn new(base: MockQuerier<SeiQueryWrapper>) -> Self {
         WasmMockQuerier {
             base,
-            tax_querier: TaxQuerier::default(),
+            sei_epoch: None,
             token_querier: TokenQuerier::default(),
             balance_querier: BalanceQuerier::default(),
             validators: vec![],
@@ -405,10 +383,32 @@ impl WasmMockQuerier {
 
     // configure the mint whitelist mock basset
     pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
-        self.token_querier = TokenQuerier::new(balances);
+        self.token_querier.balances = balances_to_map(balances);
+    }
+
+    // the balances at the start of the block `height`
+    pub fn with_token_balances_at(
+        &mut self,
+        height: u64,
+        balances: &[(&String, &[(&String, &Uint128)])],
+    ) {
+        self.token_querier
+            .balances_at
+            .insert(height, balances_to_map(balances));
     }
 
     pub fn add_validator(&mut self, validator: RegistryValidator) {
         self.validators.push(validator);
     }
+
+    // configure the epoch returned by the Sei epoch module
+    pub fn with_sei_epoch(&mut self, current_epoch: u64, duration_seconds: u64) {
+        self.sei_epoch = Some(Epoch {
+            genesis_time: "2023-01-01T00:00:00Z".to_string(),
+            duration: duration_seconds * 1_000_000_000,
+            current_epoch,
+            current_epoch_start_time: "2023-01-01T00:00:00Z".to_string(),
+            current_epoch_height: current_epoch as i64,
+        });
+    }
 }
@@ -36,9 +36,10 @@ use std::str::FromStr;
 use cosmwasm_std::testing::{mock_env, mock_info};
 use cosmwasm_std::testing::{MockApi, MockStorage};
 use cosmwasm_std::{
-    coin, coins, from_json, to_json_binary, to_json_vec, Addr, Api, BankMsg, Coin, CosmosMsg, Decimal,
-    DepsMut, DistributionMsg, Env, FullDelegation, MessageInfo, OwnedDeps, Querier, QueryRequest,
-    Response, StakingMsg, StdError, StdResult, Storage, Uint128, Validator, WasmMsg, WasmQuery,
+    coin, coins, from_json, to_json_binary, to_json_vec, Addr, Api, BankMsg, Binary, Coin, CosmosMsg, Decimal,
+    DepsMut, DistributionMsg, Env, FullDelegation, GovMsg, MessageInfo, OwnedDeps, Querier,
+    QueryRequest, Response, StakingMsg, StdError, StdResult, Storage, SubMsg, Uint128,
+    Validator, VoteOption, WasmMsg, WasmQuery, WeightedVoteOption,
 };
 use cosmwasm_storage::Bucket;
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
@@ -46,8 +47,10 @@ use cw20_base::msg::ExecuteMsg::{Burn, Mint};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use basset::airdrop::ExecuteMsg::{FabricateANCClaim, FabricateMIRClaim};
+use basset::airdrop::ExecuteMsg::FabricateClaim;
 use basset::airdrop::PairHandleMsg;
+use basset::reward::ExecuteMsg as RewardExecuteMsg;
+use basset::token::TokenConfigMsg;
 use basset::hub::Cw20HookMsg::Unbond;
 use basset::hub::ExecuteMsg::{CheckSlashing, Receive, SetOwner, UpdateConfig, UpdateParams};
 use basset::hub::QueryMsg::{
@@ -55,10 +58,18 @@ use basset::hub::QueryMsg::{
     WithdrawableUnbonded,
 };
 use basset::hub::{
-    AllHistoryResponse, ConfigResponse, CurrentBatchResponse, Cw20HookMsg, ExecuteMsg,
-    InstantiateMsg, Parameters, StateResponse, UnbondRequestsResponse, UnbondWaitEntity,
-    WithdrawableUnbondedResponse,
+    AllHistoryResponse, BatchRequestsResponse, ClassUnbondAmount, ClassUnbondHistory,
+    ConfigResponse, CurrentBatchResponse, Cw20HookMsg, ExecuteMsg,
+    ConvertDirection, InstantiateMsg, Parameters, QueryMsg, SimulateBondResponse,
+    EpochSource, NextBatchTimeResponse, SimulateConvertResponse, SimulateUnbondResponse,
+    InsuranceDraw, InsuranceDrawsResponse,
+    BatchSlashingResponse, OptionPower, ProposalTallyResponse, ProposalVoteResponse,
+    RewardPolicy, SlashingEvent, SlashingEventsResponse, SlashingSource, StateResponse,
+    TokenClassesResponse, TokenType, UnbondHistory, UnbondType,
+    UnbondRequestDetails, UnbondRequestStatus, UnbondRequestsDetailedResponse,
+    UnbondRequestsResponse, UnbondWaitEntity, UnbondedReceiveMsg, WithdrawableUnbondedResponse,
 };
+use basset_sei_insurance_fund::msg::ExecuteMsg as InsuranceExecuteMsg;
 use basset_sei_rewards_dispatcher::msg::ExecuteMsg::{DispatchRewards, SwapToRewardDenom};
 use basset_sei_validators_registry::msg::QueryMsg as QueryValidators;
 use basset_sei_validators_registry::registry::ValidatorResponse as RegistryValidator;
@@ -66,7 +77,10 @@ use crate::config::accept_ownership;
 
 use crate::contract::{execute, instantiate, query};
 use crate::math::decimal_division;
-use crate::state::{read_unbond_wait_list, CONFIG, OLD_PREFIX_WAIT_MAP, PARAMETERS, STATE, read_new_owner};
+use crate::state::{
+    read_new_owner, read_unbond_history, read_unbond_wait_list, store_unbond_history,
+    store_unbond_wait_list, CONFIG, NEW_PREFIX_WAIT_MAP, OLD_PREFIX_WAIT_MAP, PARAMETERS, STATE,
+};
 use crate::unbond::{execute_unbond, execute_unbond_stsei};
 
 use super::mock_querier::{mock_dependencies as dependencies, WasmMockQuerier};
@@ -129,9 +143,11 @@ pub fn initialize<S: Storage, A: Api, Q: Querier>(
         validators_registry_contract: Some(String::from("validators_registry")),
         rewards_contract: Some(String::from("rewards")),
         update_reward_index_addr: Some("update_reward_index_addr".to_string()),
+        insurance_fund_contract: None,
     };
     let res = execute(deps.as_mut(), mock_env(), owner_info, register_msg).unwrap();
-    assert_eq!(1, res.messages.len());
+    // the withdraw address, then the bSei token and reward contracts cache each other
+    assert_eq!(3, res.messages.len());
 }
 
 pub fn do_register_validator(
@@ -157,7 +173,11 @@ pub fn do_bond(
         }))
         .unwrap();
 
-    let bond = ExecuteMsg::Bond {};
+    let bond = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&addr, &[coin(amount.u128(), "usei")]);
     let res = execute(deps.as_mut(), mock_env(), info, bond).unwrap();
@@ -177,7 +197,11 @@ pub fn do_bond_stsei(
         }))
         .unwrap();
 
-    let bond = ExecuteMsg::BondForStSei {};
+    let bond = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&addr, &[coin(amount.u128(), "usei")]);
     let res = execute(deps.as_mut(), mock_env(), info, bond).unwrap();
@@ -191,7 +215,9 @@ pub fn do_unbond(
     info: MessageInfo,
     amount: Uint128,
 ) -> Response {
-    let successful_bond = Unbond {};
+    let successful_bond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: addr,
         amount,
@@ -272,6 +298,8 @@ fn proper_initialization() {
         bsei_token_contract: None,
         airdrop_registry_contract: None,
         stsei_token_contract: None,
+        insurance_fund_contract: None,
+        rewards_contract: None,
 
         token_contract: None,
     };
@@ -353,7 +381,11 @@ fn proper_bond() {
     do_register_validator(&mut deps, validator2);
     do_register_validator(&mut deps, validator3);
 
-    let bond_msg = ExecuteMsg::Bond {};
+    let bond_msg = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&addr1, &[coin(bond_amount.u128(), "usei")]);
 
@@ -420,7 +452,11 @@ fn proper_bond() {
 
     // no-send funds
     let bob = String::from("bob");
-    let failed_bond = ExecuteMsg::Bond {};
+    let failed_bond = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[]);
     let res = execute(deps.as_mut(), mock_env(), info, failed_bond);
@@ -431,7 +467,11 @@ fn proper_bond() {
 
     //send other tokens than sei funds
     let bob = String::from("bob");
-    let failed_bond = ExecuteMsg::Bond {};
+    let failed_bond = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(10, "ukrt")]);
     let res = execute(deps.as_mut(), mock_env(), info, failed_bond.clone());
@@ -486,7 +526,11 @@ fn proper_bond_for_st_sei() {
     do_register_validator(&mut deps, validator2);
     do_register_validator(&mut deps, validator3);
 
-    let bond_msg = ExecuteMsg::BondForStSei {};
+    let bond_msg = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&addr1, &[coin(bond_amount.u128(), "usei")]);
 
@@ -553,7 +597,11 @@ fn proper_bond_for_st_sei() {
 
     // no-send funds
     let bob = String::from("bob");
-    let failed_bond = ExecuteMsg::BondForStSei {};
+    let failed_bond = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[]);
     let res = execute(deps.as_mut(), mock_env(), info, failed_bond);
@@ -564,7 +612,11 @@ fn proper_bond_for_st_sei() {
 
     //send other tokens than sei funds
     let bob = String::from("bob");
-    let failed_bond = ExecuteMsg::BondForStSei {};
+    let failed_bond = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(10, "ukrt")]);
     let res = execute(deps.as_mut(), mock_env(), info, failed_bond.clone());
@@ -619,7 +671,11 @@ fn proper_bond_rewards() {
     do_register_validator(&mut deps, validator2);
     do_register_validator(&mut deps, validator3);
 
-    let bond_msg = ExecuteMsg::BondForStSei {};
+    let bond_msg = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&addr1, &[coin(bond_amount.u128(), "usei")]);
 
@@ -1064,7 +1120,9 @@ pub fn proper_receive() {
     assert!(res.is_err());
 
     // unauthorized
-    let failed_unbond = Unbond {};
+    let failed_unbond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: addr1.clone(),
         amount: Uint128::from(10u64),
@@ -1076,7 +1134,9 @@ pub fn proper_receive() {
     assert_eq!(res.unwrap_err(), StdError::generic_err("unauthorized"));
 
     // successful call
-    let successful_unbond = Unbond {};
+    let successful_unbond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: addr1.clone(),
         amount: Uint128::from(10u64),
@@ -1134,7 +1194,11 @@ pub fn proper_unbond() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond = ExecuteMsg::Bond {};
+    let bond = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(10, "usei")]);
 
@@ -1178,7 +1242,9 @@ pub fn proper_unbond() {
     assert_eq!(query_state.total_bond_bsei_amount, Uint128::from(10u64));
 
     // successful call
-    let successful_bond = Unbond {};
+    let successful_bond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: bob.clone(),
         amount: Uint128::from(1u64),
@@ -1196,7 +1262,9 @@ pub fn proper_unbond() {
     assert_eq!(Uint128::from(1u64), wait_list.bsei_amount);
 
     //successful call
-    let successful_bond = Unbond {};
+    let successful_bond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: bob.clone(),
         amount: Uint128::from(5u64),
@@ -1242,7 +1310,9 @@ pub fn proper_unbond() {
     //pushing time forward to check the unbond message
     env.block.time = env.block.time.plus_seconds(31);
 
-    let successful_bond = Unbond {};
+    let successful_bond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: bob.clone(),
         amount: Uint128::from(2u64),
@@ -1365,7 +1435,9 @@ pub fn proper_receive_stsei() {
     assert!(res.is_err());
 
     // unauthorized
-    let failed_unbond = Unbond {};
+    let failed_unbond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: addr1.clone(),
         amount: Uint128::from(10u64),
@@ -1377,7 +1449,9 @@ pub fn proper_receive_stsei() {
     assert_eq!(res.unwrap_err(), StdError::generic_err("unauthorized"));
 
     // successful call
-    let successful_unbond = Unbond {};
+    let successful_unbond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: addr1,
         amount: Uint128::from(10u64),
@@ -1435,7 +1509,11 @@ pub fn proper_unbond_stsei() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond = ExecuteMsg::BondForStSei {};
+    let bond = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(10, "usei")]);
 
@@ -1480,7 +1558,9 @@ pub fn proper_unbond_stsei() {
     assert_eq!(query_state.total_bond_stsei_amount, Uint128::from(10u64));
 
     // successful call
-    let successful_bond = Unbond {};
+    let successful_bond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: bob.clone(),
         amount: Uint128::from(1u64),
@@ -1498,7 +1578,9 @@ pub fn proper_unbond_stsei() {
     assert_eq!(Uint128::from(1u64), wait_list.stsei_amount);
 
     //successful call
-    let successful_bond = Unbond {};
+    let successful_bond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: bob.clone(),
         amount: Uint128::from(5u64),
@@ -1545,7 +1627,9 @@ pub fn proper_unbond_stsei() {
     //pushing time forward to check the unbond message
     env.block.time = env.block.time.plus_seconds(31);
 
-    let successful_bond = Unbond {};
+    let successful_bond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: bob.clone(),
         amount: Uint128::from(2u64),
@@ -1874,8 +1958,35 @@ pub fn proper_slashing() {
         from_json(&query(deps.as_ref(), mock_env(), ex_rate).unwrap()).unwrap();
     assert_eq!(query_exchange_rate.bsei_exchange_rate, expected_er);
 
+    // the slashing is recorded
+    let slashing_events = QueryMsg::SlashingEvents {
+        start_after: None,
+        limit: None,
+    };
+    let res: SlashingEventsResponse =
+        from_json(query(deps.as_ref(), mock_env(), slashing_events).unwrap()).unwrap();
+    assert_eq!(
+        res.events,
+        vec![SlashingEvent {
+            id: 1,
+            time: mock_env().block.time.seconds(),
+            source: SlashingSource::Delegations,
+            amount_before: Uint128::from(1000u64),
+            amount_after: Uint128::from(900u64),
+            bsei_slashed: Uint128::from(100u64),
+            stsei_slashed: Uint128::zero(),
+            classes_slashed: Uint128::zero(),
+            first_batch: None,
+            last_batch: None,
+        }]
+    );
+
     //bond again to see the update exchange rate
-    let second_bond = ExecuteMsg::Bond {};
+    let second_bond = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&addr1, &[coin(1000, "usei")]);
 
@@ -1945,6 +2056,7 @@ pub fn proper_slashing() {
         env.clone(),
         Uint128::from(500u64),
         addr1.clone(),
+        None,
     )
     .unwrap();
 
@@ -1962,6 +2074,7 @@ pub fn proper_slashing() {
         env.clone(),
         Uint128::from(500u64),
         addr1.clone(),
+        None,
     )
     .unwrap();
     let msgs: CosmosMsg = CosmosMsg::Staking(StakingMsg::Undelegate {
@@ -1994,7 +2107,13 @@ pub fn proper_slashing() {
 
     env.block.time = env.block.time.plus_seconds(90);
     //check withdrawUnbonded message
-    let withdraw_unbond_msg = ExecuteMsg::WithdrawUnbonded {};
+    let withdraw_unbond_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let wdraw_unbonded_res = execute(deps.as_mut(), env, info, withdraw_unbond_msg).unwrap();
     assert_eq!(wdraw_unbonded_res.messages.len(), 1);
 
@@ -2063,7 +2182,11 @@ pub fn proper_slashing_stsei() {
     assert_eq!(query_exchange_rate.stsei_exchange_rate.to_string(), "0.9");
 
     //bond again to see the update exchange rate
-    let second_bond = ExecuteMsg::BondForStSei {};
+    let second_bond = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&addr1, &[coin(900, "usei")]);
 
@@ -2123,6 +2246,7 @@ pub fn proper_slashing_stsei() {
         env.clone(),
         Uint128::from(500u64),
         addr1.clone(),
+        None,
     )
     .unwrap();
 
@@ -2137,6 +2261,7 @@ pub fn proper_slashing_stsei() {
         env.clone(),
         Uint128::from(500u64),
         addr1.clone(),
+        None,
     )
     .unwrap();
     let msgs: CosmosMsg = CosmosMsg::Staking(StakingMsg::Undelegate {
@@ -2168,7 +2293,13 @@ pub fn proper_slashing_stsei() {
 
     env.block.time = env.block.time.plus_seconds(90);
     //check withdrawUnbonded message
-    let withdraw_unbond_msg = ExecuteMsg::WithdrawUnbonded {};
+    let withdraw_unbond_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let wdraw_unbonded_res = execute(deps.as_mut(), env, info, withdraw_unbond_msg).unwrap();
     assert_eq!(wdraw_unbonded_res.messages.len(), 1);
 
@@ -2218,7 +2349,11 @@ pub fn proper_withdraw_unbonded() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::Bond {};
+    let bond_msg = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(100, "usei")]);
 
@@ -2242,7 +2377,14 @@ pub fn proper_withdraw_unbonded() {
 
     set_delegation(&mut deps.querier, validator, 100, "usei");
 
-    let res = execute_unbond(deps.as_mut(), mock_env(), Uint128::from(10u64), bob.clone()).unwrap();
+    let res = execute_unbond(
+        deps.as_mut(),
+        mock_env(),
+        Uint128::from(10u64),
+        bob.clone(),
+        None,
+    )
+    .unwrap();
     assert_eq!(1, res.messages.len());
 
     deps.querier.with_token_balances(&[
@@ -2263,7 +2405,13 @@ pub fn proper_withdraw_unbonded() {
     //set the block time 30 seconds from now.
     env.block.time = env.block.time.plus_seconds(31);
 
-    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {};
+    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let wdraw_unbonded_res = execute(
         deps.as_mut(),
         env.clone(),
@@ -2283,6 +2431,7 @@ pub fn proper_withdraw_unbonded() {
         env.clone(),
         Uint128::from(10u64),
         bob.clone(),
+        None,
     )
     .unwrap();
     assert_eq!(res.messages.len(), 2);
@@ -2294,6 +2443,8 @@ pub fn proper_withdraw_unbonded() {
     //this query should be zero since the undelegated period is not passed
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), mock_env(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -2333,6 +2484,8 @@ pub fn proper_withdraw_unbonded() {
     //check with query
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), env.clone(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -2355,6 +2508,8 @@ pub fn proper_withdraw_unbonded() {
     //it should be removed
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with: WithdrawableUnbondedResponse =
         from_json(&query(deps.as_ref(), mock_env(), withdrawable).unwrap()).unwrap();
@@ -2408,7 +2563,11 @@ pub fn proper_withdraw_unbonded_stsei() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::BondForStSei {};
+    let bond_msg = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(100, "usei")]);
 
@@ -2439,8 +2598,14 @@ pub fn proper_withdraw_unbonded_stsei() {
 
     set_delegation(&mut deps.querier, validator, 200, "usei");
 
-    let res =
-        execute_unbond_stsei(deps.as_mut(), mock_env(), Uint128::from(10u64), bob.clone()).unwrap();
+    let res = execute_unbond_stsei(
+        deps.as_mut(),
+        mock_env(),
+        Uint128::from(10u64),
+        bob.clone(),
+        None,
+    )
+    .unwrap();
     assert_eq!(1, res.messages.len());
 
     deps.querier.with_token_balances(&[
@@ -2461,7 +2626,13 @@ pub fn proper_withdraw_unbonded_stsei() {
     //set the block time 30 seconds from now.
     env.block.time = env.block.time.plus_seconds(31);
 
-    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {};
+    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let wdraw_unbonded_res = execute(
         deps.as_mut(),
         env.clone(),
@@ -2481,6 +2652,7 @@ pub fn proper_withdraw_unbonded_stsei() {
         env.clone(),
         Uint128::from(10u64),
         bob.clone(),
+        None,
     )
     .unwrap();
     assert_eq!(res.messages.len(), 2);
@@ -2501,6 +2673,8 @@ pub fn proper_withdraw_unbonded_stsei() {
     //this query should be zero since the undelegated period is not passed
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), mock_env(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -2540,6 +2714,8 @@ pub fn proper_withdraw_unbonded_stsei() {
     //check with query
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), env.clone(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -2562,6 +2738,8 @@ pub fn proper_withdraw_unbonded_stsei() {
     //it should be removed
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with: WithdrawableUnbondedResponse =
         from_json(&query(deps.as_ref(), mock_env(), withdrawable).unwrap()).unwrap();
@@ -2612,8 +2790,16 @@ pub fn proper_withdraw_unbonded_both_tokens() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::Bond {};
-    let bond_for_stsei_msg = ExecuteMsg::BondForStSei {};
+    let bond_msg = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
+    let bond_for_stsei_msg = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(100, "usei")]);
 
@@ -2665,6 +2851,7 @@ pub fn proper_withdraw_unbonded_both_tokens() {
         mock_env(),
         Uint128::from(100u64),
         bob.clone(),
+        None,
     )
     .unwrap();
     assert_eq!(1, res.messages.len());
@@ -2682,6 +2869,7 @@ pub fn proper_withdraw_unbonded_both_tokens() {
         env.clone(),
         Uint128::from(100u64),
         bob.clone(),
+        None,
     )
     .unwrap();
     assert_eq!(2, res.messages.len());
@@ -2702,6 +2890,8 @@ pub fn proper_withdraw_unbonded_both_tokens() {
     //this query should be zero since the undelegated period is not passed
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), mock_env(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -2743,12 +2933,20 @@ pub fn proper_withdraw_unbonded_both_tokens() {
     //check with query
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), env.clone(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
     assert_eq!(res.withdrawable, Uint128::from(300u64));
 
-    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {};
+    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let success_res = execute(deps.as_mut(), env, info, wdraw_unbonded_msg).unwrap();
 
     assert_eq!(success_res.messages.len(), 1);
@@ -2766,6 +2964,8 @@ pub fn proper_withdraw_unbonded_both_tokens() {
     //it should be removed
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with: WithdrawableUnbondedResponse =
         from_json(&query(deps.as_ref(), mock_env(), withdrawable).unwrap()).unwrap();
@@ -2821,7 +3021,11 @@ pub fn proper_withdraw_unbonded_respect_slashing() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::Bond {};
+    let bond_msg = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(bond_amount.u128(), "usei")]);
 
@@ -2845,7 +3049,7 @@ pub fn proper_withdraw_unbonded_respect_slashing() {
 
     set_delegation(&mut deps.querier, validator, bond_amount.u128(), "usei");
 
-    let res = execute_unbond(deps.as_mut(), mock_env(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), mock_env(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(1, res.messages.len());
     deps.querier.with_token_balances(&[
         (&String::from("token"), &[(&bob, &Uint128::from(9500u64))]),
@@ -2865,7 +3069,13 @@ pub fn proper_withdraw_unbonded_respect_slashing() {
 
     //set the block time 30 seconds from now.
     env.block.time = env.block.time.plus_seconds(31);
-    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {};
+    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let wdraw_unbonded_res = execute(
         deps.as_mut(),
         env.clone(),
@@ -2879,7 +3089,7 @@ pub fn proper_withdraw_unbonded_respect_slashing() {
     );
 
     // trigger undelegation message
-    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(2, res.messages.len());
     deps.querier
         .with_token_balances(&[(&String::from("token"), &[(&bob, &Uint128::from(9000u64))])]);
@@ -2887,6 +3097,8 @@ pub fn proper_withdraw_unbonded_respect_slashing() {
     //this query should be zero since the undelegated period is not passed
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), mock_env(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -2919,6 +3131,8 @@ pub fn proper_withdraw_unbonded_respect_slashing() {
     //this query does not reflect the actual withdrawable
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), env.clone(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -2938,8 +3152,36 @@ pub fn proper_withdraw_unbonded_respect_slashing() {
         _ => panic!("Unexpected message: {:?}", sent_message),
     }
 
+    // the losses of the released batch are recorded
+    let slashing_events = QueryMsg::SlashingEvents {
+        start_after: None,
+        limit: None,
+    };
+    let res: SlashingEventsResponse =
+        from_json(query(deps.as_ref(), mock_env(), slashing_events).unwrap()).unwrap();
+    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events[0].source, SlashingSource::Unbonding);
+    assert_eq!(res.events[0].amount_before, Uint128::from(1000u64));
+    assert_eq!(res.events[0].amount_after, Uint128::from(900u64));
+    assert_eq!(res.events[0].bsei_slashed, Uint128::from(100u64));
+    assert_eq!(res.events[0].first_batch, Some(1));
+    assert_eq!(res.events[0].last_batch, Some(1));
+
+    let res: BatchSlashingResponse = from_json(
+        query(deps.as_ref(), mock_env(), QueryMsg::BatchSlashing { batch_id: 1 }).unwrap(),
+    )
+    .unwrap();
+    assert!(res.released);
+    assert_eq!(res.bsei_expected_amount, Uint128::from(1000u64));
+    assert_eq!(res.bsei_released_amount, Uint128::from(899u64));
+    assert_eq!(res.slashed_amount, Uint128::from(101u64));
+
     // there should not be any result
-    let withdrawable = WithdrawableUnbonded { address: bob };
+    let withdrawable = WithdrawableUnbonded {
+        address: bob,
+        start_after: None,
+        limit: None,
+    };
     let query_with: WithdrawableUnbondedResponse =
         from_json(&query(deps.as_ref(), mock_env(), withdrawable).unwrap()).unwrap();
     assert_eq!(query_with.withdrawable, Uint128::from(0u64));
@@ -2973,7 +3215,11 @@ pub fn proper_withdraw_unbonded_respect_slashing_stsei() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::BondForStSei {};
+    let bond_msg = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(bond_amount.u128(), "usei")]);
 
@@ -2997,7 +3243,14 @@ pub fn proper_withdraw_unbonded_respect_slashing_stsei() {
 
     set_delegation(&mut deps.querier, validator, bond_amount.u128(), "usei");
 
-    let res = execute_unbond_stsei(deps.as_mut(), mock_env(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond_stsei(
+        deps.as_mut(),
+        mock_env(),
+        unbond_amount,
+        bob.clone(),
+        None,
+    )
+    .unwrap();
     assert_eq!(1, res.messages.len());
     deps.querier.with_token_balances(&[
         (&stsei_token_contract, &[(&bob, &Uint128::from(9500u64))]),
@@ -3017,7 +3270,13 @@ pub fn proper_withdraw_unbonded_respect_slashing_stsei() {
 
     //set the block time 30 seconds from now.
     env.block.time = env.block.time.plus_seconds(31);
-    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {};
+    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let wdraw_unbonded_res = execute(
         deps.as_mut(),
         env.clone(),
@@ -3031,7 +3290,14 @@ pub fn proper_withdraw_unbonded_respect_slashing_stsei() {
     );
 
     // trigger undelegation message
-    let res = execute_unbond_stsei(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond_stsei(
+        deps.as_mut(),
+        env.clone(),
+        unbond_amount,
+        bob.clone(),
+        None,
+    )
+    .unwrap();
     assert_eq!(2, res.messages.len());
     deps.querier
         .with_token_balances(&[(&stsei_token_contract, &[(&bob, &Uint128::from(9000u64))])]);
@@ -3039,6 +3305,8 @@ pub fn proper_withdraw_unbonded_respect_slashing_stsei() {
     //this query should be zero since the undelegated period is not passed
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), mock_env(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -3071,6 +3339,8 @@ pub fn proper_withdraw_unbonded_respect_slashing_stsei() {
     //this query does not reflect the actual withdrawable
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), env.clone(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -3091,7 +3361,11 @@ pub fn proper_withdraw_unbonded_respect_slashing_stsei() {
     }
 
     // there should not be any result
-    let withdrawable = WithdrawableUnbonded { address: bob };
+    let withdrawable = WithdrawableUnbonded {
+        address: bob,
+        start_after: None,
+        limit: None,
+    };
     let query_with: WithdrawableUnbondedResponse =
         from_json(&query(deps.as_ref(), mock_env(), withdrawable).unwrap()).unwrap();
     assert_eq!(query_with.withdrawable, Uint128::from(0u64));
@@ -3125,7 +3399,11 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::Bond {};
+    let bond_msg = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(bond_amount.u128(), "usei")]);
 
@@ -3149,7 +3427,7 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing() {
 
     set_delegation(&mut deps.querier, validator, bond_amount.u128(), "usei");
 
-    let res = execute_unbond(deps.as_mut(), mock_env(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), mock_env(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(1, res.messages.len());
 
     deps.querier.with_token_balances(&[
@@ -3176,7 +3454,13 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing() {
     assert_eq!(query_batch.requested_bsei_with_fee, unbond_amount);
 
     env.block.time = env.block.time.plus_seconds(1000);
-    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {};
+    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let wdraw_unbonded_res = execute(
         deps.as_mut(),
         env.clone(),
@@ -3190,7 +3474,7 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing() {
     );
 
     // trigger undelegation message
-    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(2, res.messages.len());
     deps.querier
         .with_token_balances(&[(&String::from("token"), &[(&bob, &Uint128::from(9000u64))])]);
@@ -3218,6 +3502,8 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing() {
     //this query should be zero since the undelegated period is not passed
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), mock_env(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -3249,6 +3535,8 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing() {
     //this query does not reflect the actual withdrawable
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), env.clone(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -3269,7 +3557,11 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing() {
     }
 
     // there should not be any result
-    let withdrawable = WithdrawableUnbonded { address: bob };
+    let withdrawable = WithdrawableUnbonded {
+        address: bob,
+        start_after: None,
+        limit: None,
+    };
     let query_with: WithdrawableUnbondedResponse =
         from_json(&query(deps.as_ref(), mock_env(), withdrawable).unwrap()).unwrap();
     assert_eq!(query_with.withdrawable, Uint128::from(0u64));
@@ -3318,7 +3610,11 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing_stsei() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::BondForStSei {};
+    let bond_msg = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(bond_amount.u128(), "usei")]);
 
@@ -3342,7 +3638,14 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing_stsei() {
 
     set_delegation(&mut deps.querier, validator, bond_amount.u128(), "usei");
 
-    let res = execute_unbond_stsei(deps.as_mut(), mock_env(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond_stsei(
+        deps.as_mut(),
+        mock_env(),
+        unbond_amount,
+        bob.clone(),
+        None,
+    )
+    .unwrap();
     assert_eq!(1, res.messages.len());
 
     deps.querier.with_token_balances(&[
@@ -3369,7 +3672,13 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing_stsei() {
     assert_eq!(query_batch.requested_stsei, unbond_amount);
 
     env.block.time = env.block.time.plus_seconds(1000);
-    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {};
+    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let wdraw_unbonded_res = execute(
         deps.as_mut(),
         mock_env(),
@@ -3383,7 +3692,14 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing_stsei() {
     );
 
     // trigger undelegation message
-    let res = execute_unbond_stsei(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond_stsei(
+        deps.as_mut(),
+        env.clone(),
+        unbond_amount,
+        bob.clone(),
+        None,
+    )
+    .unwrap();
     assert_eq!(2, res.messages.len());
     deps.querier
         .with_token_balances(&[(&stsei_token_contract, &[(&bob, &Uint128::from(9000u64))])]);
@@ -3411,6 +3727,8 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing_stsei() {
     //this query should be zero since the undelegated period is not passed
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), mock_env(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -3442,6 +3760,8 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing_stsei() {
     //this query does not reflect the actual withdrawable
     let withdrawable = WithdrawableUnbonded {
         address: bob.clone(),
+        start_after: None,
+        limit: None,
     };
     let query_with = query(deps.as_ref(), env.clone(), withdrawable).unwrap();
     let res: WithdrawableUnbondedResponse = from_json(&query_with).unwrap();
@@ -3462,7 +3782,11 @@ pub fn proper_withdraw_unbonded_respect_inactivity_slashing_stsei() {
     }
 
     // there should not be any result
-    let withdrawable = WithdrawableUnbonded { address: bob };
+    let withdrawable = WithdrawableUnbonded {
+        address: bob,
+        start_after: None,
+        limit: None,
+    };
     let query_with: WithdrawableUnbondedResponse =
         from_json(&query(deps.as_ref(), mock_env(), withdrawable).unwrap()).unwrap();
     assert_eq!(query_with.withdrawable, Uint128::from(0u64));
@@ -3512,7 +3836,11 @@ pub fn proper_withdraw_unbond_with_dummies() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::Bond {};
+    let bond_msg = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(bond_amount.u128(), "usei")]);
 
@@ -3532,7 +3860,7 @@ pub fn proper_withdraw_unbond_with_dummies() {
         "usei",
     );
 
-    let res = execute_unbond(deps.as_mut(), mock_env(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), mock_env(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(1, res.messages.len());
 
     deps.querier.with_token_balances(&[
@@ -3554,7 +3882,7 @@ pub fn proper_withdraw_unbond_with_dummies() {
     //set the block time 30 seconds from now.
     env.block.time = env.block.time.plus_seconds(31);
     // trigger undelegation message
-    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(2, res.messages.len());
     deps.querier.with_token_balances(&[
         (&String::from("token"), &[(&bob, &Uint128::from(9000u64))]),
@@ -3569,7 +3897,7 @@ pub fn proper_withdraw_unbond_with_dummies() {
         "usei",
     );
 
-    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(1, res.messages.len());
     deps.querier.with_token_balances(&[
         (&String::from("token"), &[(&bob, &Uint128::from(8500u64))]),
@@ -3577,7 +3905,7 @@ pub fn proper_withdraw_unbond_with_dummies() {
     ]);
 
     env.block.time = env.block.time.plus_seconds(31);
-    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(2, res.messages.len());
     deps.querier.with_token_balances(&[
         (&String::from("token"), &[(&bob, &Uint128::from(8000u64))]),
@@ -3594,7 +3922,13 @@ pub fn proper_withdraw_unbond_with_dummies() {
     )]);
 
     env.block.time = env.block.time.plus_seconds(120);
-    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {};
+    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let success_res = execute(deps.as_mut(), env, info, wdraw_unbonded_msg).unwrap();
 
     assert_eq!(success_res.messages.len(), 1);
@@ -3633,7 +3967,11 @@ pub fn proper_withdraw_unbond_with_dummies() {
     }
 
     // there should not be any result
-    let withdrawable = WithdrawableUnbonded { address: bob };
+    let withdrawable = WithdrawableUnbonded {
+        address: bob,
+        start_after: None,
+        limit: None,
+    };
     let query_with: WithdrawableUnbondedResponse =
         from_json(&query(deps.as_ref(), mock_env(), withdrawable).unwrap()).unwrap();
     assert_eq!(query_with.withdrawable, Uint128::from(0u64));
@@ -3668,7 +4006,11 @@ pub fn proper_withdraw_unbond_with_dummies_stsei() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::BondForStSei {};
+    let bond_msg = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(bond_amount.u128(), "usei")]);
 
@@ -3688,7 +4030,14 @@ pub fn proper_withdraw_unbond_with_dummies_stsei() {
         "usei",
     );
 
-    let res = execute_unbond_stsei(deps.as_mut(), mock_env(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond_stsei(
+        deps.as_mut(),
+        mock_env(),
+        unbond_amount,
+        bob.clone(),
+        None,
+    )
+    .unwrap();
     assert_eq!(1, res.messages.len());
 
     deps.querier.with_token_balances(&[
@@ -3710,7 +4059,14 @@ pub fn proper_withdraw_unbond_with_dummies_stsei() {
     //set the block time 30 seconds from now.
     env.block.time = env.block.time.plus_seconds(31);
     // trigger undelegation message
-    let res = execute_unbond_stsei(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond_stsei(
+        deps.as_mut(),
+        env.clone(),
+        unbond_amount,
+        bob.clone(),
+        None,
+    )
+    .unwrap();
     assert_eq!(2, res.messages.len());
     deps.querier.with_token_balances(&[
         (&stsei_token_contract, &[(&bob, &Uint128::from(9000u64))]),
@@ -3725,7 +4081,14 @@ pub fn proper_withdraw_unbond_with_dummies_stsei() {
         "usei",
     );
 
-    let res = execute_unbond_stsei(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond_stsei(
+        deps.as_mut(),
+        env.clone(),
+        unbond_amount,
+        bob.clone(),
+        None,
+    )
+    .unwrap();
     assert_eq!(1, res.messages.len());
     deps.querier.with_token_balances(&[
         (&stsei_token_contract, &[(&bob, &Uint128::from(8500u64))]),
@@ -3733,7 +4096,14 @@ pub fn proper_withdraw_unbond_with_dummies_stsei() {
     ]);
 
     env.block.time = env.block.time.plus_seconds(31);
-    let res = execute_unbond_stsei(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond_stsei(
+        deps.as_mut(),
+        env.clone(),
+        unbond_amount,
+        bob.clone(),
+        None,
+    )
+    .unwrap();
     assert_eq!(2, res.messages.len());
     deps.querier.with_token_balances(&[
         (&stsei_token_contract, &[(&bob, &Uint128::from(8000u64))]),
@@ -3750,7 +4120,13 @@ pub fn proper_withdraw_unbond_with_dummies_stsei() {
     )]);
 
     env.block.time = env.block.time.plus_seconds(120);
-    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {};
+    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let success_res = execute(deps.as_mut(), env, info, wdraw_unbonded_msg).unwrap();
 
     assert_eq!(success_res.messages.len(), 1);
@@ -3789,7 +4165,11 @@ pub fn proper_withdraw_unbond_with_dummies_stsei() {
     }
 
     // there should not be any result
-    let withdrawable = WithdrawableUnbonded { address: bob };
+    let withdrawable = WithdrawableUnbonded {
+        address: bob,
+        start_after: None,
+        limit: None,
+    };
     let query_with: WithdrawableUnbondedResponse =
         from_json(&query(deps.as_ref(), mock_env(), withdrawable).unwrap()).unwrap();
     assert_eq!(query_with.withdrawable, Uint128::from(0u64));
@@ -3813,6 +4193,10 @@ pub fn test_update_params() {
         er_threshold: None,
         paused: Some(false),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
     let owner = String::from("owner1");
     let token_contract = String::from("token");
@@ -3856,6 +4240,10 @@ pub fn test_update_params() {
         er_threshold: Some(Decimal::zero()),
         paused: Some(false),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
 
     //the result must be 1
@@ -3880,6 +4268,10 @@ pub fn test_update_params() {
         er_threshold: None,
         paused: Some(false),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
 
     let creator_info = mock_info(String::from("owner1").as_str(), &[]);
@@ -3897,6 +4289,10 @@ pub fn test_update_params() {
         er_threshold: Some(Decimal::from_str("1.1").unwrap()),
         paused: Some(false),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
 
     //the result must be 1
@@ -3931,6 +4327,10 @@ pub fn proper_recovery_fee() {
         )),
         paused: Some(false),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
     let owner = String::from("owner1");
     let token_contract = String::from("token");
@@ -3965,7 +4365,11 @@ pub fn proper_recovery_fee() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::Bond {};
+    let bond_msg = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     //this will set the balance of the user in token contract
     deps.querier.with_token_balances(&[
@@ -3991,7 +4395,11 @@ pub fn proper_recovery_fee() {
 
     //Bond again to see the applied result
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::Bond {};
+    let bond_msg = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     deps.querier.with_token_balances(&[
         (&String::from("token"), &[(&bob, &bond_amount)]),
@@ -4030,7 +4438,9 @@ pub fn proper_recovery_fee() {
     }
 
     // check unbond message
-    let unbond = Unbond {};
+    let unbond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: token_contract.clone(),
         amount: unbond_amount,
@@ -4059,7 +4469,9 @@ pub fn proper_recovery_fee() {
 
     env.block.time = env.block.time.plus_seconds(60);
 
-    let second_unbond = Unbond {};
+    let second_unbond = Unbond {
+        min_unbond_amount: None,
+    };
     let receive = Receive(Cw20ReceiveMsg {
         sender: token_contract,
         amount: unbond_amount,
@@ -4110,7 +4522,13 @@ pub fn proper_recovery_fee() {
 
     env.block.time = env.block.time.plus_seconds(90);
     //check withdrawUnbonded message
-    let withdraw_unbond_msg = ExecuteMsg::WithdrawUnbonded {};
+    let withdraw_unbond_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let wdraw_unbonded_res = execute(deps.as_mut(), env, token_info, withdraw_unbond_msg).unwrap();
     assert_eq!(wdraw_unbonded_res.messages.len(), 1);
 
@@ -4203,6 +4621,7 @@ pub fn proper_update_config() {
         stsei_token_contract: None,
         rewards_contract: None,
         update_reward_index_addr: None,
+        insurance_fund_contract: None,
     };
     let info = mock_info(&invalid_owner, &[]);
     let res = execute(deps.as_mut(), mock_env(), info, update_config);
@@ -4263,6 +4682,10 @@ pub fn proper_update_config() {
         er_threshold: None,
         paused: Some(false),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
 
     let new_owner_info = mock_info(&new_owner, &[]);
@@ -4277,6 +4700,10 @@ pub fn proper_update_config() {
         er_threshold: None,
         paused: Some(false),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
 
     let new_owner_info = mock_info(&owner, &[]);
@@ -4291,6 +4718,7 @@ pub fn proper_update_config() {
         stsei_token_contract: None,
         rewards_contract: None,
         update_reward_index_addr: None,
+        insurance_fund_contract: None,
     };
     let new_owner_info = mock_info(&new_owner, &[]);
     let res = execute(deps.as_mut(), mock_env(), new_owner_info, update_config).unwrap();
@@ -4317,6 +4745,7 @@ pub fn proper_update_config() {
         stsei_token_contract: None,
         rewards_contract: None,
         update_reward_index_addr: None,
+        insurance_fund_contract: None,
     };
     let new_owner_info = mock_info(&new_owner, &[]);
     let res = execute(deps.as_mut(), mock_env(), new_owner_info, update_config);
@@ -4348,6 +4777,7 @@ pub fn proper_update_config() {
         stsei_token_contract: None,
         rewards_contract: None,
         update_reward_index_addr: None,
+        insurance_fund_contract: None,
     };
     let new_owner_info = mock_info(&new_owner, &[]);
     let res = execute(deps.as_mut(), mock_env(), new_owner_info, update_config).unwrap();
@@ -4369,6 +4799,7 @@ pub fn proper_update_config() {
         stsei_token_contract: None,
         rewards_contract: None,
         update_reward_index_addr: None,
+        insurance_fund_contract: None,
     };
     let new_owner_info = mock_info(&new_owner, &[]);
     let res = execute(deps.as_mut(), mock_env(), new_owner_info, update_config).unwrap();
@@ -4382,6 +4813,46 @@ pub fn proper_update_config() {
         String::from("new registry"),
     );
 
+    // the new bSei reward contract is pushed to the token, and the token to it
+    let update_config = UpdateConfig {
+        rewards_dispatcher_contract: None,
+        airdrop_registry_contract: None,
+        validators_registry_contract: None,
+        bsei_token_contract: None,
+        stsei_token_contract: None,
+        rewards_contract: Some(String::from("new rewards")),
+        update_reward_index_addr: None,
+        insurance_fund_contract: None,
+    };
+    let new_owner_info = mock_info(&new_owner, &[]);
+    let res = execute(deps.as_mut(), mock_env(), new_owner_info, update_config).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: token_contract.clone(),
+                msg: to_json_binary(&TokenConfigMsg::UpdateRewardContract {
+                    reward_contract: String::from("new rewards"),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("new rewards"),
+                msg: to_json_binary(&RewardExecuteMsg::UpdateTokenContract {
+                    token_contract: token_contract.clone(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+
+    // the token resolves its reward contract from the hub config
+    let config_query: ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), Config {}).unwrap()).unwrap();
+    assert_eq!(config_query.rewards_contract.unwrap(), String::from("new rewards"));
+
     let update_config = UpdateConfig {
         rewards_dispatcher_contract: None,
         airdrop_registry_contract: None,
@@ -4390,6 +4861,7 @@ pub fn proper_update_config() {
         stsei_token_contract: Some(stsei_token_contract.clone()),
         rewards_contract: None,
         update_reward_index_addr: None,
+        insurance_fund_contract: None,
     };
     let new_owner_info = mock_info(&new_owner, &[]);
     let res = execute(deps.as_mut(), mock_env(), new_owner_info, update_config);
@@ -4415,6 +4887,7 @@ pub fn proper_update_config() {
         stsei_token_contract: None,
         rewards_contract: None,
         update_reward_index_addr: Some("new_update_index_addr".to_string()),
+        insurance_fund_contract: None,
     };
     let new_owner_info = mock_info(&new_owner, &[]);
     let res = execute(deps.as_mut(), mock_env(), new_owner_info, update_config).unwrap();
@@ -4610,14 +5083,16 @@ fn proper_update_global_index_with_airdrop() {
         (&stsei_token_contract, &[(&addr1, &Uint128::from(10u64))]),
     ]);
 
-    let binary_msg = to_json_binary(&FabricateMIRClaim {
+    let binary_msg = to_json_binary(&FabricateClaim {
+        airdrop_token: String::from("MIR"),
         stage: 0,
         amount: Uint128::from(1000u64),
         proof: vec!["proof".to_string()],
     })
     .unwrap();
 
-    let binary_msg2 = to_json_binary(&FabricateANCClaim {
+    let binary_msg2 = to_json_binary(&FabricateClaim {
+        airdrop_token: String::from("ANC"),
         stage: 0,
         amount: Uint128::from(1000u64),
         proof: vec!["proof".to_string()],
@@ -4771,7 +5246,10 @@ fn test_convert_to_stsei_with_slashing_and_peg_fee() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: sender_addr.clone(),
         amount: Uint128::from(1000u64),
-        msg: to_json_binary(&Cw20HookMsg::Convert {}).unwrap(),
+        msg: to_json_binary(&Cw20HookMsg::Convert {
+            min_mint_amount: None,
+        })
+        .unwrap(),
     });
     let r = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     let applied_exchange_rate = &r
@@ -4896,7 +5374,10 @@ fn test_convert_to_bsei_with_slashing_and_peg_fee() {
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: sender_addr.clone(),
         amount: Uint128::from(1000u64),
-        msg: to_json_binary(&Cw20HookMsg::Convert {}).unwrap(),
+        msg: to_json_binary(&Cw20HookMsg::Convert {
+            min_mint_amount: None,
+        })
+        .unwrap(),
     });
     let r = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     let applied_exchange_rate = &r
@@ -4985,7 +5466,10 @@ fn test_receive_cw20() {
         let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
             sender: sender_addr.clone(),
             amount: Uint128::from(1000u64),
-            msg: to_json_binary(&Cw20HookMsg::Convert {}).unwrap(),
+            msg: to_json_binary(&Cw20HookMsg::Convert {
+                min_mint_amount: None,
+            })
+            .unwrap(),
         });
         let _ = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     }
@@ -4995,7 +5479,10 @@ fn test_receive_cw20() {
         let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
             sender: sender_addr.clone(),
             amount: Uint128::from(1001u64),
-            msg: to_json_binary(&Cw20HookMsg::Convert {}).unwrap(),
+            msg: to_json_binary(&Cw20HookMsg::Convert {
+                min_mint_amount: None,
+            })
+            .unwrap(),
         });
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
         assert_eq!(
@@ -5012,7 +5499,10 @@ fn test_receive_cw20() {
         let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
             sender: sender_addr.clone(),
             amount: Uint128::from(1000u64),
-            msg: to_json_binary(&Cw20HookMsg::Convert {}).unwrap(),
+            msg: to_json_binary(&Cw20HookMsg::Convert {
+                min_mint_amount: None,
+            })
+            .unwrap(),
         });
         let _ = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     }
@@ -5022,7 +5512,10 @@ fn test_receive_cw20() {
         let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
             sender: sender_addr,
             amount: Uint128::from(1001u64),
-            msg: to_json_binary(&Cw20HookMsg::Convert {}).unwrap(),
+            msg: to_json_binary(&Cw20HookMsg::Convert {
+                min_mint_amount: None,
+            })
+            .unwrap(),
         });
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
         assert_eq!(
@@ -5144,6 +5637,10 @@ pub fn test_pause() {
         er_threshold: Some(Decimal::zero()),
         paused: Some(true),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
     let creator_info = mock_info(String::from("owner1").as_str(), &[]);
     execute(deps.as_mut(), mock_env(), creator_info, update_prams).unwrap();
@@ -5158,6 +5655,7 @@ pub fn test_pause() {
         stsei_token_contract: None,
         rewards_contract: None,
         update_reward_index_addr: None,
+        insurance_fund_contract: None,
     };
     let info = mock_info(&owner, &[]);
     let res = execute(deps.as_mut(), mock_env(), info, update_config);
@@ -5174,6 +5672,10 @@ pub fn test_pause() {
         er_threshold: Some(Decimal::zero()),
         paused: Some(false),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
     let creator_info = mock_info(String::from("owner1").as_str(), &[]);
     execute(deps.as_mut(), mock_env(), creator_info, update_prams).unwrap();
@@ -5187,6 +5689,7 @@ pub fn test_pause() {
         stsei_token_contract: None,
         rewards_contract: None,
         update_reward_index_addr: None,
+        insurance_fund_contract: None,
     };
     let info = mock_info(&owner, &[]);
     execute(deps.as_mut(), mock_env(), info, update_config).unwrap();
@@ -5211,6 +5714,10 @@ pub fn test_pause() {
         er_threshold: Some(Decimal::zero()),
         paused: Some(true),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
     let creator_info = mock_info(String::from("owner1").as_str(), &[]);
     execute(deps.as_mut(), mock_env(), creator_info, update_prams).unwrap();
@@ -5223,6 +5730,10 @@ pub fn test_pause() {
         er_threshold: Some(Decimal::zero()),
         paused: Some(false),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
     let creator_info = mock_info(String::from("owner1").as_str(), &[]);
     let res = execute(deps.as_mut(), mock_env(), creator_info, update_prams);
@@ -5239,6 +5750,10 @@ pub fn test_pause() {
         er_threshold: Some(Decimal::zero()),
         paused: None,
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
     let creator_info = mock_info(String::from("owner1").as_str(), &[]);
     let res = execute(deps.as_mut(), mock_env(), creator_info, update_prams);
@@ -5259,6 +5774,10 @@ pub fn test_pause() {
         er_threshold: Some(Decimal::zero()),
         paused: Some(false),
         reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
     };
     let creator_info = mock_info(String::from("owner1").as_str(), &[]);
     execute(deps.as_mut(), mock_env(), creator_info, update_prams).unwrap();
@@ -5291,7 +5810,11 @@ pub fn proper_withdraw_unbond_with_rogue_transfer() {
     do_register_validator(&mut deps, validator.clone());
 
     let bob = String::from("bob");
-    let bond_msg = ExecuteMsg::Bond {};
+    let bond_msg = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
 
     let info = mock_info(&bob, &[coin(bond_amount.u128(), "usei")]);
 
@@ -5311,7 +5834,7 @@ pub fn proper_withdraw_unbond_with_rogue_transfer() {
         "usei",
     );
 
-    let res = execute_unbond(deps.as_mut(), mock_env(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), mock_env(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(1, res.messages.len());
 
     deps.querier.with_token_balances(&[
@@ -5333,7 +5856,7 @@ pub fn proper_withdraw_unbond_with_rogue_transfer() {
     //set the block time 30 seconds from now.
     env.block.time = env.block.time.plus_seconds(31);
     // trigger undelegation message
-    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(2, res.messages.len());
     deps.querier.with_token_balances(&[
         (&String::from("token"), &[(&bob, &Uint128::from(9000u64))]),
@@ -5347,7 +5870,7 @@ pub fn proper_withdraw_unbond_with_rogue_transfer() {
         "usei",
     );
 
-    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(1, res.messages.len());
     deps.querier.with_token_balances(&[
         (&String::from("token"), &[(&bob, &Uint128::from(8500u64))]),
@@ -5355,7 +5878,7 @@ pub fn proper_withdraw_unbond_with_rogue_transfer() {
     ]);
 
     env.block.time = env.block.time.plus_seconds(31);
-    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone()).unwrap();
+    let res = execute_unbond(deps.as_mut(), env.clone(), unbond_amount, bob.clone(), None).unwrap();
     assert_eq!(2, res.messages.len());
     deps.querier.with_token_balances(&[
         (&String::from("token"), &[(&bob, &Uint128::from(8000u64))]),
@@ -5379,7 +5902,13 @@ pub fn proper_withdraw_unbond_with_rogue_transfer() {
     )]);
 
     env.block.time = env.block.time.plus_seconds(120);
-    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {};
+    let wdraw_unbonded_msg = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
     let success_res = execute(deps.as_mut(), env, info, wdraw_unbonded_msg).unwrap();
 
     assert_eq!(success_res.messages.len(), 1);
@@ -5418,8 +5947,2424 @@ pub fn proper_withdraw_unbond_with_rogue_transfer() {
     }
 
     // there should not be any result
-    let withdrawable = WithdrawableUnbonded { address: bob };
+    let withdrawable = WithdrawableUnbonded {
+        address: bob,
+        start_after: None,
+        limit: None,
+    };
     let query_with: WithdrawableUnbondedResponse =
         from_json(&query(deps.as_ref(), mock_env(), withdrawable).unwrap()).unwrap();
     assert_eq!(query_with.withdrawable, Uint128::from(0u64));
 }
+
+/// Covers that the withdrawal is processed in bounded chunks, that only the
+/// requested batches are withdrawn when `batch_ids` is given, and that the
+/// withdrawable query is paginated.
+#[test]
+pub fn proper_withdraw_unbonded_in_chunks() {
+    let mut deps = dependencies(&[]);
+
+    let validator = sample_validator(DEFAULT_VALIDATOR);
+    set_validator_mock(&mut deps.querier);
+
+    let owner = String::from("owner1");
+    let token_contract = String::from("token");
+    let stsei_token_contract = String::from("stsei_token");
+    let reward_contract = String::from("reward");
+
+    initialize(
+        deps.borrow_mut(),
+        owner,
+        reward_contract,
+        token_contract.clone(),
+        stsei_token_contract.clone(),
+    );
+
+    do_register_validator(&mut deps, validator.clone());
+
+    let bob = String::from("bob");
+    do_bond(&mut deps, bob.clone(), Uint128::from(100u64));
+    set_delegation(&mut deps.querier, validator, 100, "usei");
+
+    // bob opens a request in four consecutive batches, alice closes the first three
+    let alice = String::from("alice");
+    let mut env = mock_env();
+    let mut supply = 100u128;
+    for epoch in 0..4 {
+        deps.querier.with_token_balances(&[
+            (&token_contract, &[(&bob, &Uint128::from(supply))]),
+            (&stsei_token_contract, &[]),
+        ]);
+        execute_unbond(
+            deps.as_mut(),
+            env.clone(),
+            Uint128::from(10u64),
+            bob.clone(),
+            None,
+        )
+        .unwrap();
+        supply -= 10;
+        if epoch == 3 {
+            break;
+        }
+
+        env.block.time = env.block.time.plus_seconds(31);
+        deps.querier.with_token_balances(&[
+            (&token_contract, &[(&bob, &Uint128::from(supply))]),
+            (&stsei_token_contract, &[]),
+        ]);
+        execute_unbond(
+            deps.as_mut(),
+            env.clone(),
+            Uint128::from(10u64),
+            alice.clone(),
+            None,
+        )
+        .unwrap();
+        supply -= 10;
+    }
+
+    let res: UnbondRequestsResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            UnbondRequests {
+                address: bob.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.requests.len(), 4);
+
+    env.block.time = env.block.time.plus_seconds(100);
+    deps.querier.with_native_balances(&[(
+        String::from(MOCK_CONTRACT_ADDR),
+        Coin {
+            denom: "usei".to_string(),
+            amount: Uint128::from(60u64),
+        },
+    )]);
+
+    // the query is paginated
+    let page: WithdrawableUnbondedResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            WithdrawableUnbonded {
+                address: bob.clone(),
+                start_after: None,
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(page.withdrawable, Uint128::from(10u64));
+    assert_eq!(page.next_start_after, Some(1));
+
+    let page: WithdrawableUnbondedResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            WithdrawableUnbonded {
+                address: bob.clone(),
+                start_after: page.next_start_after,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(page.withdrawable, Uint128::from(20u64));
+    assert_eq!(page.next_start_after, None);
+
+    // only two batches are withdrawn
+    let info = mock_info(&bob, &[]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::WithdrawUnbonded {
+            limit: Some(2),
+            batch_ids: None,
+            recipient: None,
+            msg: None,
+            rebond_as: None,
+        },
+    )
+    .unwrap();
+    match res.messages[0].msg.clone() {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+            assert_eq!(to_address, bob);
+            assert_eq!(amount, coins(20, "usei"));
+        }
+        _ => panic!("Unexpected message: {:?}", res.messages[0]),
+    }
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::attr("withdrawn_batches", "2")));
+
+    deps.querier.with_native_balances(&[(
+        String::from(MOCK_CONTRACT_ADDR),
+        Coin {
+            denom: "usei".to_string(),
+            amount: Uint128::from(40u64),
+        },
+    )]);
+
+    // batch 4 has not been released yet
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::WithdrawUnbonded {
+            limit: None,
+            batch_ids: Some(vec![3, 4]),
+            recipient: None,
+            msg: None,
+            rebond_as: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Burn requests not found for the specified time period")
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::WithdrawUnbonded {
+            limit: None,
+            batch_ids: Some(vec![3, 3]),
+            recipient: None,
+            msg: None,
+            rebond_as: None,
+        },
+    )
+    .unwrap();
+    match res.messages[0].msg.clone() {
+        CosmosMsg::Bank(BankMsg::Send { amount, .. }) => {
+            assert_eq!(amount, coins(10, "usei"));
+        }
+        _ => panic!("Unexpected message: {:?}", res.messages[0]),
+    }
+
+    let res: UnbondRequestsResponse = from_json(
+        query(
+            deps.as_ref(),
+            env,
+            UnbondRequests {
+                address: bob.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.requests,
+        vec![(4, Uint128::from(10u64), Uint128::zero())]
+    );
+}
+
+/// Covers the pages of the unbond requests following the numeric order of the batch ids.
+#[test]
+pub fn proper_unbond_requests_pages_by_batch_id() {
+    let mut deps = dependencies(&[]);
+    let owner = String::from("owner1");
+    let token_contract = String::from("token");
+    let stsei_token_contract = String::from("stsei_token");
+    let bob = String::from("bob");
+    initialize(
+        deps.borrow_mut(),
+        owner,
+        String::from("reward"),
+        token_contract.clone(),
+        stsei_token_contract.clone(),
+    );
+    deps.querier.with_token_balances(&[
+        (&token_contract, &[(&bob, &Uint128::from(120u64))]),
+        (&stsei_token_contract, &[]),
+    ]);
+
+    let env = mock_env();
+    for batch_id in 1..=12u64 {
+        store_unbond_wait_list(
+            deps.as_mut().storage,
+            batch_id,
+            bob.clone(),
+            Uint128::from(10u64),
+            UnbondType::BSei,
+            20,
+        )
+        .unwrap();
+        let history = UnbondHistory {
+            batch_id,
+            time: env.block.time.seconds() - 10,
+            bsei_amount: Uint128::from(10u64),
+            bsei_applied_exchange_rate: Decimal::one(),
+            bsei_withdraw_rate: Decimal::one(),
+            stsei_amount: Uint128::zero(),
+            stsei_applied_exchange_rate: Decimal::one(),
+            stsei_withdraw_rate: Decimal::one(),
+            released: true,
+            classes: None,
+        };
+        store_unbond_history(deps.as_mut().storage, batch_id, history).unwrap();
+    }
+
+    let page = |start_after: Option<u64>, limit: Option<u32>| -> Vec<u64> {
+        let res: UnbondRequestsDetailedResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::UnbondRequestsDetailed {
+                    address: bob.clone(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        res.requests.iter().map(|request| request.batch_id).collect()
+    };
+    assert_eq!(page(None, Some(9)), (1..=9).collect::<Vec<u64>>());
+    assert_eq!(page(Some(9), None), vec![10, 11, 12]);
+
+    let withdrawable = |start_after: Option<u64>, limit: Option<u32>| {
+        let res: WithdrawableUnbondedResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                WithdrawableUnbonded {
+                    address: bob.clone(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        (res.withdrawable, res.next_start_after)
+    };
+    assert_eq!(withdrawable(None, Some(9)), (Uint128::from(90u64), Some(9)));
+    assert_eq!(withdrawable(Some(9), None), (Uint128::from(30u64), None));
+}
+
+/// Covers that a user can not open more unbond requests than `max_unbond_requests`,
+/// while adding to an already open request is still possible.
+#[test]
+pub fn proper_max_unbond_requests() {
+    let mut deps = dependencies(&[]);
+
+    let validator = sample_validator(DEFAULT_VALIDATOR);
+    set_validator_mock(&mut deps.querier);
+
+    let owner = String::from("owner1");
+    let token_contract = String::from("token");
+    let stsei_token_contract = String::from("stsei_token");
+    let reward_contract = String::from("reward");
+
+    initialize(
+        deps.borrow_mut(),
+        owner.clone(),
+        reward_contract,
+        token_contract.clone(),
+        stsei_token_contract.clone(),
+    );
+
+    let update_params = UpdateParams {
+        epoch_period: None,
+        unbonding_period: None,
+        peg_recovery_fee: None,
+        er_threshold: None,
+        paused: Some(false),
+        reward_denom: None,
+        max_unbond_requests: Some(0),
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(&owner, &[]), update_params);
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("max_unbond_requests must be greater than 0")
+    );
+
+    let update_params = UpdateParams {
+        epoch_period: None,
+        unbonding_period: None,
+        peg_recovery_fee: None,
+        er_threshold: None,
+        paused: Some(false),
+        reward_denom: None,
+        max_unbond_requests: Some(2),
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(&owner, &[]), update_params).unwrap();
+
+    let params: Parameters =
+        from_json(query(deps.as_ref(), mock_env(), Params {}).unwrap()).unwrap();
+    assert_eq!(params.max_unbond_requests, Some(2));
+
+    do_register_validator(&mut deps, validator.clone());
+
+    let bob = String::from("bob");
+    do_bond(&mut deps, bob.clone(), Uint128::from(100u64));
+    set_delegation(&mut deps.querier, validator, 100, "usei");
+    deps.querier.with_token_balances(&[
+        (&token_contract, &[(&bob, &Uint128::from(100u64))]),
+        (&stsei_token_contract, &[]),
+    ]);
+
+    let alice = String::from("alice");
+    let mut env = mock_env();
+
+    // bob opens requests in batches 1 and 2, alice closes the batches
+    execute_unbond(deps.as_mut(), env.clone(), Uint128::from(10u64), bob.clone(), None).unwrap();
+    execute_unbond(deps.as_mut(), env.clone(), Uint128::from(10u64), bob.clone(), None).unwrap();
+    env.block.time = env.block.time.plus_seconds(31);
+    execute_unbond(deps.as_mut(), env.clone(), Uint128::from(10u64), alice.clone(), None).unwrap();
+    execute_unbond(deps.as_mut(), env.clone(), Uint128::from(10u64), bob.clone(), None).unwrap();
+    env.block.time = env.block.time.plus_seconds(31);
+    execute_unbond(deps.as_mut(), env.clone(), Uint128::from(10u64), alice.clone(), None).unwrap();
+
+    let err = execute_unbond(deps.as_mut(), env.clone(), Uint128::from(10u64), bob.clone(), None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(
+            "Too many open unbond requests, the limit is 2. Withdraw the released ones first"
+        )
+    );
+
+    // other users are not affected
+    execute_unbond(deps.as_mut(), env, Uint128::from(10u64), String::from("carol"), None).unwrap();
+}
+
+fn do_unbond_in_closed_batches(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    batches: u64,
+) -> Env {
+    let validator = sample_validator(DEFAULT_VALIDATOR);
+    set_validator_mock(&mut deps.querier);
+
+    let owner = String::from("owner1");
+    let token_contract = String::from("token");
+    let stsei_token_contract = String::from("stsei_token");
+    let reward_contract = String::from("reward");
+
+    initialize(
+        deps.borrow_mut(),
+        owner,
+        reward_contract,
+        token_contract.clone(),
+        stsei_token_contract.clone(),
+    );
+
+    do_register_validator(deps, validator.clone());
+
+    let bob = String::from("bob");
+    do_bond(deps, bob.clone(), Uint128::from(1000u64));
+    set_delegation(&mut deps.querier, validator, 1000, "usei");
+    deps.querier.with_token_balances(&[
+        (&token_contract, &[(&bob, &Uint128::from(1000u64))]),
+        (&stsei_token_contract, &[]),
+    ]);
+
+    // every batch is closed by the unbond that comes after the epoch
+    let mut env = mock_env();
+    for _ in 0..batches {
+        execute_unbond(
+            deps.as_mut(),
+            env.clone(),
+            Uint128::from(10u64),
+            bob.clone(),
+            None,
+        )
+        .unwrap();
+        env.block.time = env.block.time.plus_seconds(31);
+        execute_unbond(
+            deps.as_mut(),
+            env.clone(),
+            Uint128::from(10u64),
+            bob.clone(),
+            None,
+        )
+        .unwrap();
+    }
+    env
+}
+
+/// Covers that releasing the matured batches over several calls gives the same
+/// withdraw rates as releasing them at once.
+#[test]
+pub fn proper_process_released_batches_in_chunks() {
+    let mut all_at_once = dependencies(&[]);
+    let mut chunked = dependencies(&[]);
+    let mut env = do_unbond_in_closed_batches(&mut all_at_once, 3);
+    do_unbond_in_closed_batches(&mut chunked, 3);
+
+    // 60 usei have been unbonded, 6 of them got slashed
+    env.block.time = env.block.time.plus_seconds(100);
+    for deps in [&mut all_at_once, &mut chunked] {
+        deps.querier.with_native_balances(&[(
+            String::from(MOCK_CONTRACT_ADDR),
+            Coin {
+                denom: "usei".to_string(),
+                amount: Uint128::from(54u64),
+            },
+        )]);
+    }
+
+    let info = mock_info("keeper", &[]);
+    let res = execute(
+        all_at_once.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::ProcessReleasedBatches { limit: None },
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::attr("last_processed_batch", "3")));
+
+    // the matured batches are collected first, then released one by one
+    let expected = [(0, 1), (0, 2), (0, 3), (1, 3), (2, 3), (3, 3)];
+    for (processed, pending) in expected {
+        let res = execute(
+            chunked.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::ProcessReleasedBatches { limit: Some(1) },
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                cosmwasm_std::attr("action", "process_released_batches"),
+                cosmwasm_std::attr("last_processed_batch", processed.to_string()),
+                cosmwasm_std::attr("last_pending_batch", pending.to_string()),
+            ]
+        );
+    }
+
+    let all_batches = AllHistory {
+        start_from: None,
+        limit: None,
+    };
+    let expected_history: AllHistoryResponse =
+        from_json(query(all_at_once.as_ref(), env.clone(), all_batches.clone()).unwrap())
+            .unwrap();
+    let history: AllHistoryResponse =
+        from_json(query(chunked.as_ref(), env.clone(), all_batches).unwrap()).unwrap();
+    assert_eq!(history, expected_history);
+    assert_eq!(history.history.len(), 3);
+    for batch in history.history {
+        assert!(batch.released);
+        assert_eq!(batch.bsei_withdraw_rate, Decimal::from_ratio(9u64, 10u64));
+    }
+
+    let state: StateResponse =
+        from_json(query(chunked.as_ref(), env, State {}).unwrap()).unwrap();
+    assert_eq!(state.prev_hub_balance, Uint128::from(54u64));
+}
+
+/// Covers withdrawing the unbonded coins to another address, to a contract hook
+/// and bonding them again in the same transaction.
+#[test]
+pub fn proper_withdraw_unbonded_to_recipient() {
+    let mut deps = dependencies(&[]);
+    let mut env = do_unbond_in_closed_batches(&mut deps, 1);
+    env.block.time = env.block.time.plus_seconds(100);
+    deps.querier.with_native_balances(&[(
+        String::from(MOCK_CONTRACT_ADDR),
+        Coin {
+            denom: "usei".to_string(),
+            amount: Uint128::from(20u64),
+        },
+    )]);
+
+    let bob = String::from("bob");
+    let vault = String::from("vault");
+    let info = mock_info(&bob, &[]);
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::WithdrawUnbonded {
+            limit: None,
+            batch_ids: None,
+            recipient: Some(vault.clone()),
+            msg: Some(Binary::from(b"{}".to_vec())),
+            rebond_as: Some(TokenType::BSei),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("msg and rebond_as can not be used together")
+    );
+
+    // the hook is called on the recipient with the coins attached
+    let mut hook_deps = dependencies(&[]);
+    do_unbond_in_closed_batches(&mut hook_deps, 1);
+    hook_deps.querier.with_native_balances(&[(
+        String::from(MOCK_CONTRACT_ADDR),
+        Coin {
+            denom: "usei".to_string(),
+            amount: Uint128::from(20u64),
+        },
+    )]);
+    let res = execute(
+        hook_deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::WithdrawUnbonded {
+            limit: None,
+            batch_ids: None,
+            recipient: Some(vault.clone()),
+            msg: Some(Binary::from(b"{}".to_vec())),
+            rebond_as: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(
+        res.messages[0].msg,
+        UnbondedReceiveMsg {
+            sender: bob.clone(),
+            amount: Uint128::from(19u64),
+            msg: Binary::from(b"{}".to_vec()),
+        }
+        .into_cosmos_msg(vault.clone(), coins(19, "usei"))
+        .unwrap()
+    );
+
+    // the coins are bonded again and the minted tokens go to the recipient
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::WithdrawUnbonded {
+            limit: None,
+            batch_ids: None,
+            recipient: Some(vault.clone()),
+            msg: None,
+            rebond_as: Some(TokenType::BSei),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    match res.messages[0].msg.clone() {
+        CosmosMsg::Staking(StakingMsg::Delegate { validator, amount }) => {
+            assert_eq!(validator, DEFAULT_VALIDATOR);
+            assert_eq!(amount, Coin::new(19, "usei"));
+        }
+        _ => panic!("Unexpected message: {:?}", res.messages[0]),
+    }
+    match res.messages[1].msg.clone() {
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+            assert_eq!(
+                from_json::<Cw20ExecuteMsg>(&msg).unwrap(),
+                Cw20ExecuteMsg::Mint {
+                    recipient: vault.clone(),
+                    amount: Uint128::from(19u64),
+                }
+            );
+        }
+        _ => panic!("Unexpected message: {:?}", res.messages[1]),
+    }
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::attr("recipient", vault.clone())));
+    assert!(res.attributes.contains(&cosmwasm_std::attr("action", "mint")));
+
+    // nothing is left for bob
+    let res: UnbondRequestsResponse =
+        from_json(query(deps.as_ref(), env, UnbondRequests { address: bob }).unwrap()).unwrap();
+    assert!(res.requests.is_empty());
+}
+
+/// Covers bonding on behalf of another address, with and without a callback.
+#[test]
+pub fn proper_bond_for_recipient() {
+    let mut deps = dependencies(&[]);
+
+    let validator = sample_validator(DEFAULT_VALIDATOR);
+    set_validator_mock(&mut deps.querier);
+
+    let owner = String::from("owner1");
+    let token_contract = String::from("token");
+    let stsei_token_contract = String::from("stsei_token");
+    let reward_contract = String::from("reward");
+
+    initialize(
+        deps.borrow_mut(),
+        owner,
+        reward_contract,
+        token_contract.clone(),
+        stsei_token_contract.clone(),
+    );
+
+    do_register_validator(&mut deps, validator);
+
+    let router = String::from("router");
+    let vault = String::from("vault");
+    let info = mock_info(&router, &[coin(1000, "usei")]);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::Bond {
+            recipient: Some(vault.clone()),
+            callback: None,
+            min_mint_amount: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token_contract,
+            msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                recipient: vault.clone(),
+                amount: Uint128::from(1000u64),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert!(res.attributes.contains(&cosmwasm_std::attr("from", router)));
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::attr("recipient", vault.clone())));
+
+    // with a callback the hub mints to itself and sends the tokens to the recipient
+    let callback = Binary::from(br#"{"deposit":{}}"#.to_vec());
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::BondForStSei {
+            recipient: Some(vault.clone()),
+            callback: Some(callback.clone()),
+            min_mint_amount: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: stsei_token_contract.clone(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                recipient: MOCK_CONTRACT_ADDR.to_string(),
+                amount: Uint128::from(1000u64),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.messages[2].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: stsei_token_contract,
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: vault,
+                amount: Uint128::from(1000u64),
+                msg: callback,
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+/// Covers that bond, unbond and convert fail when the peg fee or the slashing
+/// lower the outcome below the expected minimum.
+#[test]
+fn proper_min_amount_guards() {
+    let mut deps = dependencies(&coins(2, "token"));
+    let sender_addr = String::from("addr001");
+    let owner = String::from("owner1");
+    let bsei_token_contract = String::from("token");
+    let stsei_token_contract = String::from("stsei_token");
+    let reward_contract = String::from("reward");
+
+    initialize(
+        deps.borrow_mut(),
+        owner,
+        reward_contract,
+        bsei_token_contract.clone(),
+        stsei_token_contract.clone(),
+    );
+    let validator = sample_validator(DEFAULT_VALIDATOR);
+    set_validator_mock(&mut deps.querier);
+    do_register_validator(&mut deps, validator.clone());
+
+    STATE
+        .update(&mut deps.storage, |mut prev_state| -> StdResult<_> {
+            prev_state.total_bond_stsei_amount = Uint128::from(2000u64);
+            prev_state.total_bond_bsei_amount = Uint128::from(2000u64);
+            prev_state.stsei_exchange_rate =
+                Decimal::from_ratio(Uint128::from(12u64), Uint128::from(10u64));
+            Ok(prev_state)
+        })
+        .unwrap();
+    // the slashing brings bsei er to 0.8 and stsei er to 1
+    set_delegation(&mut deps.querier, validator, 3200, "usei");
+    PARAMETERS
+        .update(&mut deps.storage, |mut prev_param| -> StdResult<_> {
+            prev_param.peg_recovery_fee = Decimal::from_str("0.05")?;
+            Ok(prev_param)
+        })
+        .unwrap();
+    deps.querier.with_token_balances(&[
+        (
+            &stsei_token_contract,
+            &[(&sender_addr, &Uint128::from(1600u64))],
+        ),
+        (
+            &bsei_token_contract,
+            &[(&sender_addr, &Uint128::from(2000u64))],
+        ),
+    ]);
+
+    // 1000 usei mint 1000 stsei
+    let info = mock_info(&sender_addr, &[coin(1000, "usei")]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::BondForStSei {
+            recipient: None,
+            callback: None,
+            min_mint_amount: Some(Uint128::from(1001u64)),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Minted amount 1000 is less than the minimum 1001")
+    );
+
+    // 1000 bsei minus the 50 bsei peg fee are worth 760 usei
+    let info = mock_info(&bsei_token_contract, &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        Receive(Cw20ReceiveMsg {
+            sender: sender_addr.clone(),
+            amount: Uint128::from(1000u64),
+            msg: to_json_binary(&Unbond {
+                min_unbond_amount: Some(Uint128::from(761u64)),
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Unbonded amount 760 is less than the minimum 761")
+    );
+
+    // and convert to 760 stsei
+    let convert = |min_mint_amount: u64| {
+        Receive(Cw20ReceiveMsg {
+            sender: sender_addr.clone(),
+            amount: Uint128::from(1000u64),
+            msg: to_json_binary(&Cw20HookMsg::Convert {
+                min_mint_amount: Some(Uint128::from(min_mint_amount)),
+            })
+            .unwrap(),
+        })
+    };
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), convert(761)).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Minted amount 760 is less than the minimum 761")
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, convert(760)).unwrap();
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::attr("stsei_amount", "760")));
+}
+
+/// Covers that the simulation queries follow the slashing and the peg recovery fee.
+#[test]
+fn proper_simulate_queries() {
+    let mut deps = dependencies(&coins(2, "token"));
+    let sender_addr = String::from("addr001");
+    let owner = String::from("owner1");
+    let bsei_token_contract = String::from("token");
+    let stsei_token_contract = String::from("stsei_token");
+    let reward_contract = String::from("reward");
+
+    initialize(
+        deps.borrow_mut(),
+        owner,
+        reward_contract,
+        bsei_token_contract.clone(),
+        stsei_token_contract.clone(),
+    );
+    let validator = sample_validator(DEFAULT_VALIDATOR);
+    set_validator_mock(&mut deps.querier);
+    do_register_validator(&mut deps, validator.clone());
+
+    STATE
+        .update(&mut deps.storage, |mut prev_state| -> StdResult<_> {
+            prev_state.total_bond_stsei_amount = Uint128::from(2000u64);
+            prev_state.total_bond_bsei_amount = Uint128::from(2000u64);
+            prev_state.stsei_exchange_rate =
+                Decimal::from_ratio(Uint128::from(12u64), Uint128::from(10u64));
+            Ok(prev_state)
+        })
+        .unwrap();
+    // the slashing brings bsei er to 0.8 and stsei er to 1
+    set_delegation(&mut deps.querier, validator, 3200, "usei");
+    PARAMETERS
+        .update(&mut deps.storage, |mut prev_param| -> StdResult<_> {
+            prev_param.peg_recovery_fee = Decimal::from_str("0.05")?;
+            Ok(prev_param)
+        })
+        .unwrap();
+    deps.querier.with_token_balances(&[
+        (
+            &stsei_token_contract,
+            &[(&sender_addr, &Uint128::from(1600u64))],
+        ),
+        (
+            &bsei_token_contract,
+            &[(&sender_addr, &Uint128::from(2000u64))],
+        ),
+    ]);
+
+    let simulate_bond = |bond_type: TokenType| QueryMsg::SimulateBond {
+        bond_type,
+        amount: Uint128::from(1000u64),
+    };
+    // 1250 bsei minus min(62, 650) peg fee
+    let res: SimulateBondResponse =
+        from_json(query(deps.as_ref(), mock_env(), simulate_bond(TokenType::BSei)).unwrap())
+            .unwrap();
+    assert_eq!(
+        res,
+        SimulateBondResponse {
+            mint_amount: Uint128::from(1188u64),
+            peg_fee: Uint128::from(62u64),
+            exchange_rate: Decimal::from_ratio(8u64, 10u64),
+        }
+    );
+    let res: SimulateBondResponse =
+        from_json(query(deps.as_ref(), mock_env(), simulate_bond(TokenType::StSei)).unwrap())
+            .unwrap();
+    assert_eq!(res.mint_amount, Uint128::from(1000u64));
+    assert!(res.peg_fee.is_zero());
+
+    // the batch is undelegated after the epoch period and released after the unbonding period
+    let env = mock_env();
+    let res: SimulateUnbondResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::SimulateUnbond {
+                token: TokenType::BSei,
+                amount: Uint128::from(1000u64),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        SimulateUnbondResponse {
+            unbond_amount: Uint128::from(950u64),
+            peg_fee: Uint128::from(50u64),
+            underlying_amount: Uint128::from(760u64),
+            exchange_rate: Decimal::from_ratio(8u64, 10u64),
+            estimated_release_time: env.block.time.seconds() + 31 + 2,
+        }
+    );
+
+    let res: SimulateConvertResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SimulateConvert {
+                direction: ConvertDirection::BSeiToStSei,
+                amount: Uint128::from(1000u64),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        SimulateConvertResponse {
+            mint_amount: Uint128::from(760u64),
+            peg_fee: Uint128::from(50u64),
+            underlying_amount: Uint128::from(760u64),
+        }
+    );
+
+    // the actual conversion mints the simulated amount
+    let info = mock_info(&bsei_token_contract, &[]);
+    let msg = Receive(Cw20ReceiveMsg {
+        sender: sender_addr,
+        amount: Uint128::from(1000u64),
+        msg: to_json_binary(&Cw20HookMsg::Convert {
+            min_mint_amount: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::attr("stsei_amount", "760")));
+}
+
+/// Covers the status, the release time and the estimated amount of each unbond request.
+#[test]
+fn proper_unbond_requests_detailed() {
+    let mut deps = dependencies(&[]);
+    // bob has requests in the undelegated batches 1 and 2 and in the pending batch 3
+    let env = do_unbond_in_closed_batches(&mut deps, 2);
+    execute_unbond(
+        deps.as_mut(),
+        env.clone(),
+        Uint128::from(10u64),
+        String::from("bob"),
+        None,
+    )
+    .unwrap();
+
+    // only batch 1 has matured
+    deps.querier.with_native_balances(&[(
+        String::from(MOCK_CONTRACT_ADDR),
+        Coin {
+            denom: "usei".to_string(),
+            amount: Uint128::from(20u64),
+        },
+    )]);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ProcessReleasedBatches { limit: None },
+    )
+    .unwrap();
+
+    let detailed = |start_after: Option<u64>, limit: Option<u32>| {
+        let res: UnbondRequestsDetailedResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::UnbondRequestsDetailed {
+                    address: String::from("bob"),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        res.requests
+    };
+
+    let now = env.block.time.seconds();
+    let requests = detailed(None, Some(2));
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].batch_id, 1);
+    assert_eq!(requests[0].status, UnbondRequestStatus::Released);
+    assert_eq!(requests[0].bsei_amount, Uint128::from(20u64));
+    assert_eq!(requests[0].expected_release_time, now - 31 + 2);
+    assert_eq!(requests[1].batch_id, 2);
+    assert_eq!(requests[1].status, UnbondRequestStatus::Unbonding);
+    assert_eq!(requests[1].expected_release_time, now + 2);
+    assert_eq!(
+        requests[1].estimated_amount,
+        requests[1].bsei_amount * requests[1].bsei_exchange_rate
+    );
+
+    // the pending request is estimated with the current exchange rate
+    let state: StateResponse =
+        from_json(query(deps.as_ref(), env.clone(), State {}).unwrap()).unwrap();
+    let requests = detailed(Some(2), None);
+    assert_eq!(
+        requests,
+        vec![UnbondRequestDetails {
+            batch_id: 3,
+            status: UnbondRequestStatus::PendingEpoch,
+            bsei_amount: Uint128::from(10u64),
+            stsei_amount: Uint128::zero(),
+            expected_release_time: now + 31 + 2,
+            bsei_exchange_rate: state.bsei_exchange_rate,
+            stsei_exchange_rate: state.stsei_exchange_rate,
+            estimated_amount: Uint128::from(10u64) * state.bsei_exchange_rate,
+            classes: None,
+        }]
+    );
+}
+
+/// Covers listing the users of a batch, including the entries written before the index.
+#[test]
+fn proper_batch_requests() {
+    let mut deps = dependencies(&[]);
+    let env = do_unbond_in_closed_batches(&mut deps, 1);
+    for user in ["carol", "alice"] {
+        execute_unbond(
+            deps.as_mut(),
+            env.clone(),
+            Uint128::from(10u64),
+            String::from(user),
+            None,
+        )
+        .unwrap();
+    }
+
+    let batch_requests = |deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+                          batch_id: u64,
+                          start_after: Option<&str>,
+                          limit: Option<u32>| {
+        let res: BatchRequestsResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::BatchRequests {
+                    batch_id,
+                    start_after: start_after.map(String::from),
+                    limit,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        res.requests
+            .into_iter()
+            .map(|request| request.address)
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(batch_requests(&deps, 1, None, None), vec!["bob"]);
+    assert_eq!(batch_requests(&deps, 2, None, Some(1)), vec!["alice"]);
+    assert_eq!(batch_requests(&deps, 2, Some("alice"), None), vec!["carol"]);
+
+    // the index follows the withdrawals
+    let mut env = env;
+    env.block.time = env.block.time.plus_seconds(100);
+    deps.querier.with_native_balances(&[(
+        String::from(MOCK_CONTRACT_ADDR),
+        Coin {
+            denom: "usei".to_string(),
+            amount: Uint128::from(20u64),
+        },
+    )]);
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info("bob", &[]),
+        ExecuteMsg::WithdrawUnbonded {
+            limit: None,
+            batch_ids: None,
+            recipient: None,
+            msg: None,
+            rebond_as: None,
+        },
+    )
+    .unwrap();
+    assert!(batch_requests(&deps, 1, None, None).is_empty());
+
+    // entries written before the index are added while the contract is paused
+    let mut position_indexer: Bucket<UnbondWaitEntity> = Bucket::multilevel(
+        deps.storage.borrow_mut(),
+        &[NEW_PREFIX_WAIT_MAP, &to_json_vec("dave").unwrap()],
+    );
+    position_indexer
+        .save(&to_json_vec(&2u64).unwrap(), &UnbondWaitEntity::default())
+        .unwrap();
+    let mut old_wait_list: Bucket<Uint128> = Bucket::multilevel(
+        deps.storage.borrow_mut(),
+        &[OLD_PREFIX_WAIT_MAP, &to_json_vec("erin").unwrap()],
+    );
+    old_wait_list
+        .save(&to_json_vec(&2u64).unwrap(), &Uint128::new(42))
+        .unwrap();
+    assert_eq!(batch_requests(&deps, 2, None, None), vec!["alice", "carol"]);
+
+    let index = ExecuteMsg::IndexUnbondWaitLists {
+        start_after: None,
+        limit: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("keeper", &[]), index.clone())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("index unbond wait lists must paused the contract first.")
+    );
+
+    let pause = UpdateParams {
+        epoch_period: None,
+        unbonding_period: None,
+        peg_recovery_fee: None,
+        er_threshold: None,
+        paused: Some(true),
+        reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner1", &[]), pause).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("keeper", &[]), index).unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::MigrateUnbondWaitList { limit: None },
+    )
+    .unwrap();
+    assert_eq!(
+        batch_requests(&deps, 2, None, None),
+        vec!["alice", "carol", "dave", "erin"]
+    );
+}
+
+/// Covers that a keeper can push the funds of a released batch to its users
+/// and receives the configured keeper fee.
+#[test]
+fn proper_distribute_released() {
+    let mut deps = dependencies(&[]);
+    let mut env = do_unbond_in_closed_batches(&mut deps, 1);
+    for user in ["carol", "alice"] {
+        execute_unbond(
+            deps.as_mut(),
+            env.clone(),
+            Uint128::from(300u64),
+            String::from(user),
+            None,
+        )
+        .unwrap();
+    }
+    env.block.time = env.block.time.plus_seconds(31);
+    execute_unbond(
+        deps.as_mut(),
+        env.clone(),
+        Uint128::from(10u64),
+        String::from("bob"),
+        None,
+    )
+    .unwrap();
+
+    let mut update_params = UpdateParams {
+        epoch_period: None,
+        unbonding_period: None,
+        peg_recovery_fee: None,
+        er_threshold: None,
+        paused: Some(false),
+        reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: Some(Decimal::percent(2)),
+        epoch_source: None,
+        insurance_draw_cap: None,
+    };
+    let owner_info = mock_info("owner1", &[]);
+    let err = execute(deps.as_mut(), mock_env(), owner_info.clone(), update_params.clone())
+        .unwrap_err();
+    assert_eq!(err, StdError::generic_err("keeper_fee can not be greater than 1%"));
+    if let UpdateParams { keeper_fee, .. } = &mut update_params {
+        *keeper_fee = Some(Decimal::percent(1));
+    }
+    execute(deps.as_mut(), mock_env(), owner_info, update_params).unwrap();
+
+    let keeper_info = mock_info("keeper", &[]);
+    let distribute = |batch_id: u64, limit: Option<u32>| ExecuteMsg::DistributeReleased {
+        batch_id,
+        limit,
+    };
+
+    // the batch must be released first
+    deps.querier.with_native_balances(&[(
+        String::from(MOCK_CONTRACT_ADDR),
+        Coin {
+            denom: "usei".to_string(),
+            amount: Uint128::from(20u64),
+        },
+    )]);
+    let err = execute(deps.as_mut(), env.clone(), keeper_info.clone(), distribute(2, None))
+        .unwrap_err();
+    assert_eq!(err, StdError::generic_err("Batch 2 has not been released yet"));
+
+    env.block.time = env.block.time.plus_seconds(100);
+    deps.querier.with_native_balances(&[(
+        String::from(MOCK_CONTRACT_ADDR),
+        Coin {
+            denom: "usei".to_string(),
+            amount: Uint128::from(630u64),
+        },
+    )]);
+
+    let res = execute(deps.as_mut(), env.clone(), keeper_info.clone(), distribute(2, Some(1)))
+        .unwrap();
+    let amount = res
+        .attributes
+        .iter()
+        .find(|a| a.key == "amount")
+        .unwrap()
+        .value
+        .parse::<u128>()
+        .unwrap();
+    let tip = Uint128::from(amount) * Decimal::percent(1);
+    assert!(!tip.is_zero());
+    assert!(res.attributes.contains(&cosmwasm_std::attr("keeper_tip", tip)));
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("alice"),
+            amount: coins(amount - tip.u128(), "usei"),
+        })
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("keeper"),
+            amount: coins(tip.u128(), "usei"),
+        })
+    );
+
+    // the remaining users are paid by the next call
+    let res = execute(deps.as_mut(), env.clone(), keeper_info.clone(), distribute(2, None))
+        .unwrap();
+    assert!(res.attributes.contains(&cosmwasm_std::attr("distributed_requests", "2")));
+    assert_eq!(res.messages.len(), 3);
+
+    let err = execute(deps.as_mut(), env.clone(), keeper_info, distribute(2, None)).unwrap_err();
+    assert_eq!(err, StdError::generic_err("No requests left to distribute in batch 2"));
+
+    // the other batches of bob are left for him to withdraw
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("bob", &[]),
+        ExecuteMsg::WithdrawUnbonded {
+            limit: None,
+            batch_ids: None,
+            recipient: None,
+            msg: None,
+            rebond_as: None,
+        },
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&cosmwasm_std::attr("withdrawn_batches", "1")));
+}
+
+/// Covers that anyone can undelegate the current batch once the epoch has passed,
+/// without waiting for another unbond request.
+#[test]
+fn proper_process_batch() {
+    let mut deps = dependencies(&[]);
+    let mut env = do_unbond_in_closed_batches(&mut deps, 0);
+    let keeper_info = mock_info("keeper", &[]);
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        keeper_info.clone(),
+        ExecuteMsg::ProcessBatch {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("The current batch has no unbond requests"));
+
+    execute_unbond(
+        deps.as_mut(),
+        env.clone(),
+        Uint128::from(10u64),
+        String::from("bob"),
+        None,
+    )
+    .unwrap();
+
+    let next_batch: NextBatchTimeResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::NextBatchTime {}).unwrap())
+            .unwrap();
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(next_batch.batch_id, 1);
+    assert_eq!(next_batch.next_batch_time, state.last_unbonded_time + 31);
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        keeper_info.clone(),
+        ExecuteMsg::ProcessBatch {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(format!(
+            "The current batch can not be processed before {}",
+            next_batch.next_batch_time
+        ))
+    );
+
+    env.block.time = env
+        .block
+        .time
+        .plus_seconds(next_batch.next_batch_time - env.block.time.seconds());
+    let res = execute(deps.as_mut(), env.clone(), keeper_info, ExecuteMsg::ProcessBatch {})
+        .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    match &res.messages[0].msg {
+        CosmosMsg::Staking(StakingMsg::Undelegate { validator, .. }) => {
+            assert_eq!(validator, DEFAULT_VALIDATOR)
+        }
+        _ => panic!("Unexpected message: {:?}", res.messages[0].msg),
+    }
+    assert!(res.attributes.contains(&cosmwasm_std::attr("batch_id", "1")));
+
+    let history = read_unbond_history(&deps.storage, 1).unwrap();
+    assert_eq!(history.time, next_batch.next_batch_time);
+    assert!(!history.released);
+
+    let next_batch: NextBatchTimeResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::NextBatchTime {}).unwrap())
+            .unwrap();
+    assert_eq!(next_batch.batch_id, 2);
+    assert_eq!(next_batch.next_batch_time, env.block.time.seconds() + 31);
+}
+
+/// Covers that with the Sei epoch source the batches are closed on the chain epochs
+/// read from the epoch module instead of the block time.
+#[test]
+fn proper_sei_epoch_batches() {
+    let mut deps = dependencies(&[]);
+    let mut env = do_unbond_in_closed_batches(&mut deps, 0);
+    let bob = String::from("bob");
+
+    let update_params = UpdateParams {
+        epoch_period: None,
+        unbonding_period: None,
+        peg_recovery_fee: None,
+        er_threshold: None,
+        paused: Some(false),
+        reward_denom: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: Some(EpochSource::SeiEpoch),
+        insurance_draw_cap: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner1", &[]), update_params).unwrap();
+    let params = PARAMETERS.load(&deps.storage).unwrap();
+    assert_eq!(params.epoch_source, Some(EpochSource::SeiEpoch));
+
+    // 20 seconds long chain epochs, so a batch of 30 seconds lasts 2 epochs
+    deps.querier.with_sei_epoch(10, 20);
+
+    // the first batch falls back to the block time and records the epoch
+    env.block.time = env.block.time.plus_seconds(31);
+    let res = execute_unbond(deps.as_mut(), env.clone(), Uint128::from(10u64), bob.clone(), None)
+        .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.last_unbonded_epoch, Some(10));
+
+    // the block time has passed but the chain is still one epoch later
+    deps.querier.with_sei_epoch(11, 20);
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute_unbond(deps.as_mut(), env.clone(), Uint128::from(10u64), bob, None)
+        .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    let next_batch: NextBatchTimeResponse =
+        from_json(query(deps.as_ref(), env.clone(), QueryMsg::NextBatchTime {}).unwrap())
+            .unwrap();
+    assert_eq!(next_batch.batch_id, 2);
+    assert_eq!(next_batch.next_batch_time, env.block.time.seconds() + 20);
+
+    let keeper_info = mock_info("keeper", &[]);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        keeper_info.clone(),
+        ExecuteMsg::ProcessBatch {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(format!(
+            "The current batch can not be processed before {}",
+            next_batch.next_batch_time
+        ))
+    );
+
+    deps.querier.with_sei_epoch(12, 20);
+    execute(deps.as_mut(), env.clone(), keeper_info, ExecuteMsg::ProcessBatch {}).unwrap();
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.last_unbonded_epoch, Some(12));
+    assert_eq!(state.last_unbonded_time, env.block.time.seconds());
+    read_unbond_history(&deps.storage, 2).unwrap();
+}
+
+/// Covers the registered token classes: bond, reward split, unbond through the
+/// shared batch and withdrawal.
+#[test]
+fn proper_token_class() {
+    let mut deps = dependencies(&[]);
+    set_validator_mock(&mut deps.querier);
+    let validator = sample_validator(DEFAULT_VALIDATOR);
+    let owner = String::from("owner1");
+    let addr1 = String::from("addr1000");
+    let stsei_token_contract = String::from("stsei_token");
+    let reward_dispatcher_contract = String::from("reward_dispatcher");
+    initialize(
+        deps.borrow_mut(),
+        owner.clone(),
+        reward_dispatcher_contract.clone(),
+        String::from("token"),
+        stsei_token_contract.clone(),
+    );
+    do_register_validator(&mut deps, validator.clone());
+
+    let register = |name: &str, token_contract: &str, reward_policy: RewardPolicy| {
+        ExecuteMsg::RegisterTokenClass {
+            name: name.to_string(),
+            token_contract: token_contract.to_string(),
+            reward_policy,
+            peg_recovery_fee: Decimal::zero(),
+            er_threshold: Decimal::one(),
+        }
+    };
+    let owner_info = mock_info(&owner, &[]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&addr1, &[]),
+        register("lsei", "lsei_token", RewardPolicy::Rebase),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        register("lsei", "token", RewardPolicy::Rebase),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("the token contract is already registered"));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        register("lsei", "lsei_token", RewardPolicy::Rebase),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        register("lsei", "other_token", RewardPolicy::Rebase),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("token class lsei is already registered"));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        register("stsei", "other_token", RewardPolicy::Rebase),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("stsei is a built-in token class"));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        register("dsei", "other_token", RewardPolicy::Dispatched),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("the rewards of a registered token class can not be dispatched")
+    );
+
+    let reward_policy = RewardPolicy::RewardBearing {
+        reward_contract: String::from("rsei_reward"),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info,
+        register("rsei", "rsei_token", reward_policy),
+    )
+    .unwrap();
+
+    // bond 100 for each of the classes and for stSei
+    let bond_amount = Uint128::from(100u64);
+    deps.querier.with_token_balances(&[
+        (&String::from("lsei_token"), &[]),
+        (&String::from("rsei_token"), &[]),
+    ]);
+    for class in ["lsei", "rsei"] {
+        let bond = ExecuteMsg::BondForClass {
+            class: class.to_string(),
+            recipient: None,
+            callback: None,
+            min_mint_amount: None,
+        };
+        let info = mock_info(&addr1, &[coin(bond_amount.u128(), "usei")]);
+        let res = execute(deps.as_mut(), mock_env(), info, bond).unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: format!("{}_token", class),
+                msg: to_json_binary(&Mint {
+                    recipient: addr1.clone(),
+                    amount: bond_amount,
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+    let bond_stsei = ExecuteMsg::BondForStSei {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
+    let info = mock_info(&addr1, &[coin(bond_amount.u128(), "usei")]);
+    execute(deps.as_mut(), mock_env(), info, bond_stsei).unwrap();
+    deps.querier.with_token_balances(&[
+        (&String::from("token"), &[]),
+        (&stsei_token_contract, &[(&addr1, &bond_amount)]),
+        (&String::from("lsei_token"), &[(&addr1, &bond_amount)]),
+        (&String::from("rsei_token"), &[(&addr1, &bond_amount)]),
+    ]);
+
+    // the rewards are split by the bonded amount
+    let info = mock_info(&reward_dispatcher_contract, &[coin(300, "usei")]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::BondRewards {}).unwrap();
+    assert_eq!(res.messages.len(), 2);
+    match &res.messages[0].msg {
+        CosmosMsg::Staking(StakingMsg::Delegate { amount, .. }) => {
+            assert_eq!(amount, &coin(200, "usei"))
+        }
+        _ => panic!("Unexpected message: {:?}", res.messages[0].msg),
+    }
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: String::from("rsei_reward"),
+            amount: coins(100, "usei"),
+        })
+    );
+
+    let classes: TokenClassesResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TokenClasses {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    // bSei and stSei are listed first, as built-in classes
+    let names: Vec<&str> = classes.classes.iter().map(|class| class.name.as_str()).collect();
+    assert_eq!(names, vec!["bsei", "stsei", "lsei", "rsei"]);
+    assert_eq!(classes.classes[0].reward_policy, RewardPolicy::Dispatched);
+    assert_eq!(classes.classes[1].reward_policy, RewardPolicy::Rebase);
+    assert_eq!(classes.classes[1].total_bond_amount, Uint128::from(200u64));
+    assert_eq!(classes.classes[1].exchange_rate, Decimal::from_ratio(2u128, 1u128));
+    assert_eq!(classes.classes[2].total_bond_amount, Uint128::from(200u64));
+    assert_eq!(classes.classes[2].exchange_rate, Decimal::from_ratio(2u128, 1u128));
+    assert_eq!(classes.classes[3].total_bond_amount, bond_amount);
+    assert_eq!(classes.classes[3].exchange_rate, Decimal::one());
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.total_bond_stsei_amount, Uint128::from(200u64));
+    assert_eq!(state.stsei_exchange_rate, Decimal::from_ratio(2u128, 1u128));
+
+    let classes: TokenClassesResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TokenClasses {
+                start_after: Some(String::from("stsei")),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(classes.classes.len(), 1);
+    assert_eq!(classes.classes[0].name, "lsei");
+
+    // unbond through the shared batch
+    set_delegation(&mut deps.querier, validator, 500, "usei");
+    let mut env = mock_env();
+    let unbond = |amount: u64, msg: &Cw20HookMsg| {
+        Receive(Cw20ReceiveMsg {
+            sender: addr1.clone(),
+            amount: Uint128::from(amount),
+            msg: to_json_binary(msg).unwrap(),
+        })
+    };
+    let lsei_info = mock_info("lsei_token", &[]);
+    let convert = Cw20HookMsg::Convert {
+        min_mint_amount: None,
+    };
+    let err = execute(deps.as_mut(), env.clone(), lsei_info.clone(), unbond(50, &convert))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("the tokens of a token class can not be converted")
+    );
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        lsei_info,
+        unbond(
+            50,
+            &Unbond {
+                min_unbond_amount: Some(Uint128::from(100u64)),
+            },
+        ),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+    deps.querier.with_token_balances(&[
+        (&String::from("token"), &[]),
+        (&stsei_token_contract, &[(&addr1, &bond_amount)]),
+        (&String::from("lsei_token"), &[(&addr1, &Uint128::from(50u64))]),
+        (&String::from("rsei_token"), &[(&addr1, &bond_amount)]),
+    ]);
+
+    env.block.time = env.block.time.plus_seconds(31);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::ProcessBatch {},
+    )
+    .unwrap();
+    match &res.messages[0].msg {
+        CosmosMsg::Staking(StakingMsg::Undelegate { amount, .. }) => {
+            assert_eq!(amount, &coin(100, "usei"))
+        }
+        _ => panic!("Unexpected message: {:?}", res.messages[0].msg),
+    }
+    let history = read_unbond_history(&deps.storage, 1).unwrap();
+    assert_eq!(
+        history.classes,
+        Some(vec![ClassUnbondHistory {
+            class: String::from("lsei"),
+            amount: Uint128::from(50u64),
+            applied_exchange_rate: Decimal::from_ratio(2u128, 1u128),
+            withdraw_rate: Decimal::from_ratio(2u128, 1u128),
+        }])
+    );
+    let batch: BatchRequestsResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::BatchRequests {
+                batch_id: 1,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        batch.requests[0].classes,
+        Some(vec![ClassUnbondAmount {
+            class: String::from("lsei"),
+            amount: Uint128::from(50u64),
+        }])
+    );
+
+    // a slashed release lowers the withdraw rate of the class
+    env.block.time = env.block.time.plus_seconds(3);
+    deps.querier.with_native_balances(&[(
+        String::from(MOCK_CONTRACT_ADDR),
+        coin(90, "usei"),
+    )]);
+    let withdraw = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
+    let res = execute(deps.as_mut(), env, mock_info(&addr1, &[]), withdraw).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: addr1,
+            // the slashed amount of a batch is rounded up by one
+            amount: coins(89, "usei"),
+        })
+    );
+}
+
+/// bSei, stSei and the registered classes share the slashing, the insurance restore
+/// and the rewards by their bonded amount.
+#[test]
+fn proper_token_class_slashing() {
+    let mut deps = dependencies(&[]);
+    set_validator_mock(&mut deps.querier);
+    let validator = sample_validator(DEFAULT_VALIDATOR);
+    let owner = String::from("owner1");
+    let addr1 = String::from("addr1000");
+    let insurance_fund = String::from("insurance_fund");
+    let reward_dispatcher_contract = String::from("reward_dispatcher");
+    initialize(
+        deps.borrow_mut(),
+        owner.clone(),
+        reward_dispatcher_contract.clone(),
+        String::from("token"),
+        String::from("stsei_token"),
+    );
+    do_register_validator(&mut deps, validator.clone());
+    set_insurance_fund(&mut deps, &owner, &insurance_fund);
+
+    let register = ExecuteMsg::RegisterTokenClass {
+        name: String::from("lsei"),
+        token_contract: String::from("lsei_token"),
+        reward_policy: RewardPolicy::Rebase,
+        peg_recovery_fee: Decimal::zero(),
+        er_threshold: Decimal::one(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(&owner, &[]), register).unwrap();
+
+    // bond 100 for bSei, stSei and the class
+    let bond_amount = Uint128::from(100u64);
+    deps.querier.with_token_balances(&[
+        (&String::from("token"), &[]),
+        (&String::from("stsei_token"), &[]),
+        (&String::from("lsei_token"), &[]),
+    ]);
+    do_bond(&mut deps, addr1.clone(), bond_amount);
+    do_bond_stsei(&mut deps, addr1.clone(), bond_amount);
+    let bond = ExecuteMsg::BondForClass {
+        class: String::from("lsei"),
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
+    let info = mock_info(&addr1, &[coin(bond_amount.u128(), "usei")]);
+    execute(deps.as_mut(), mock_env(), info, bond).unwrap();
+    deps.querier.with_token_balances(&[
+        (&String::from("token"), &[(&addr1, &bond_amount)]),
+        (&String::from("stsei_token"), &[(&addr1, &bond_amount)]),
+        (&String::from("lsei_token"), &[(&addr1, &bond_amount)]),
+    ]);
+
+    // the loss is shared by the bonded amounts
+    set_delegation(&mut deps.querier, validator.clone(), 270, "usei");
+    let res = execute(deps.as_mut(), mock_env(), mock_info(&addr1, &[]), CheckSlashing {})
+        .unwrap();
+    assert_eq!(res.messages, vec![insurance_draw_msg(&insurance_fund, 30)]);
+
+    let token_classes = || QueryMsg::TokenClasses {
+        start_after: None,
+        limit: None,
+    };
+    let classes: TokenClassesResponse =
+        from_json(query(deps.as_ref(), mock_env(), token_classes()).unwrap()).unwrap();
+    for class in &classes.classes {
+        assert_eq!(class.total_bond_amount, Uint128::from(90u64));
+        assert_eq!(class.exchange_rate, Decimal::from_ratio(9u128, 10u128));
+    }
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(state.total_bond_bsei_amount, Uint128::from(90u64));
+    assert_eq!(state.total_bond_stsei_amount, Uint128::from(90u64));
+
+    let slashing_events = QueryMsg::SlashingEvents {
+        start_after: None,
+        limit: None,
+    };
+    let res: SlashingEventsResponse =
+        from_json(query(deps.as_ref(), mock_env(), slashing_events).unwrap()).unwrap();
+    assert_eq!(res.events[0].bsei_slashed, Uint128::from(10u64));
+    assert_eq!(res.events[0].stsei_slashed, Uint128::from(10u64));
+    assert_eq!(res.events[0].classes_slashed, Uint128::from(10u64));
+
+    // the restored coins are shared the same way
+    let info = mock_info(&insurance_fund, &[coin(30, "usei")]);
+    execute(deps.as_mut(), mock_env(), info, ExecuteMsg::RestoreBond {}).unwrap();
+    set_delegation(&mut deps.querier, validator, 300, "usei");
+    let classes: TokenClassesResponse =
+        from_json(query(deps.as_ref(), mock_env(), token_classes()).unwrap()).unwrap();
+    for class in &classes.classes {
+        assert_eq!(class.total_bond_amount, bond_amount);
+        assert_eq!(class.exchange_rate, Decimal::one());
+    }
+
+    // the dispatcher pays bSei and bonds the rewards of the other classes back
+    let info = mock_info("update_reward_index_addr", &[]);
+    let update_global = ExecuteMsg::UpdateGlobalIndex {
+        airdrop_hooks: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, update_global).unwrap();
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: reward_dispatcher_contract,
+            msg: to_json_binary(&SwapToRewardDenom {
+                stsei_total_bonded: Uint128::from(200u64),
+                bsei_total_bonded: bond_amount,
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+/// Covers the holders' votes on a governance proposal and the aggregated vote cast by the hub.
+#[test]
+fn proper_governance_votes() {
+    let mut deps = dependencies(&[]);
+    let owner = String::from("owner1");
+    let addr1 = String::from("addr1000");
+    let addr2 = String::from("addr2000");
+    initialize(
+        deps.borrow_mut(),
+        owner.clone(),
+        String::from("reward_dispatcher"),
+        String::from("token"),
+        String::from("stsei_token"),
+    );
+    deps.querier.with_token_balances(&[
+        (&String::from("token"), &[(&addr1, &Uint128::from(100u64))]),
+        (&String::from("stsei_token"), &[(&addr2, &Uint128::from(50u64))]),
+    ]);
+
+    let mut env = mock_env();
+    let voting_end = env.block.time.seconds() + 100;
+    let register = ExecuteMsg::RegisterProposal {
+        proposal_id: 1,
+        voting_end,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(&addr1, &[]), register.clone())
+        .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    execute(deps.as_mut(), env.clone(), mock_info(&owner, &[]), register).unwrap();
+
+    let weighted = |options: &[(VoteOption, u64)]| {
+        options
+            .iter()
+            .map(|(option, percent)| WeightedVoteOption {
+                option: option.clone(),
+                weight: Decimal::percent(*percent),
+            })
+            .collect::<Vec<_>>()
+    };
+    let vote = |options: &[(VoteOption, u64)]| ExecuteMsg::VoteProposal {
+        proposal_id: 1,
+        options: weighted(options),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&addr1, &[]),
+        vote(&[(VoteOption::Yes, 60)]),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("Vote option weights must sum up to 1"));
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr3000", &[]),
+        vote(&[(VoteOption::Yes, 100)]),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("No bSei or stSei balance to vote with"));
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&addr1, &[]),
+        vote(&[(VoteOption::Yes, 100)]),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&addr2, &[]),
+        vote(&[(VoteOption::Yes, 50), (VoteOption::No, 50)]),
+    )
+    .unwrap();
+    // voting again replaces the previous vote
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&addr1, &[]),
+        vote(&[(VoteOption::No, 100)]),
+    )
+    .unwrap();
+
+    let holder_vote: ProposalVoteResponse = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ProposalVote {
+                proposal_id: 1,
+                voter: addr1.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(holder_vote.power, Uint128::from(100u64));
+    assert_eq!(holder_vote.options, weighted(&[(VoteOption::No, 100)]));
+
+    let cast = ExecuteMsg::CastProposalVote { proposal_id: 1 };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("keeper", &[]), cast.clone())
+        .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(format!("The voting of proposal 1 ends at {}", voting_end))
+    );
+
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&addr2, &[]),
+        vote(&[(VoteOption::No, 100)]),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("The voting of proposal 1 has ended"));
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("keeper", &[]), cast.clone())
+        .unwrap();
+    let yes_weight = Decimal::from_ratio(25u128, 150u128);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Gov(GovMsg::VoteWeighted {
+            proposal_id: 1,
+            options: vec![
+                WeightedVoteOption {
+                    option: VoteOption::Yes,
+                    weight: yes_weight,
+                },
+                WeightedVoteOption {
+                    option: VoteOption::No,
+                    weight: Decimal::one() - yes_weight,
+                },
+            ],
+        })
+    );
+    let err = execute(deps.as_mut(), env.clone(), mock_info("keeper", &[]), cast).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("The vote on proposal 1 has already been cast")
+    );
+
+    let tally: ProposalTallyResponse = from_json(
+        query(deps.as_ref(), env, QueryMsg::ProposalTally { proposal_id: 1 }).unwrap(),
+    )
+    .unwrap();
+    assert!(tally.cast);
+    assert_eq!(tally.total_power, Uint128::from(150u64));
+    assert_eq!(
+        tally.tally,
+        vec![
+            OptionPower {
+                option: VoteOption::Yes,
+                power: Uint128::from(25u64),
+            },
+            OptionPower {
+                option: VoteOption::No,
+                power: Uint128::from(125u64),
+            },
+        ]
+    );
+}
+
+/// Covers the voting power snapshotted when a proposal is registered.
+#[test]
+fn governance_votes_use_snapshot() {
+    let mut deps = dependencies(&[]);
+    let owner = String::from("owner1");
+    let addr1 = String::from("addr1000");
+    let addr2 = String::from("addr2000");
+    initialize(
+        deps.borrow_mut(),
+        owner.clone(),
+        String::from("reward_dispatcher"),
+        String::from("token"),
+        String::from("stsei_token"),
+    );
+    let mut env = mock_env();
+    let snapshot_height = env.block.height;
+    deps.querier.with_token_balances_at(
+        snapshot_height,
+        &[
+            (&String::from("token"), &[(&addr1, &Uint128::from(100u64))]),
+            (&String::from("stsei_token"), &[]),
+        ],
+    );
+
+    let register = |proposal_id: u64, env: &Env| ExecuteMsg::RegisterProposal {
+        proposal_id,
+        voting_end: env.block.time.seconds() + 100,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(&owner, &[]), register(1, &env)).unwrap();
+
+    // addr1 moves its tokens to addr2 after the snapshot
+    env.block.height += 1;
+    deps.querier.with_token_balances(&[
+        (&String::from("token"), &[(&addr2, &Uint128::from(100u64))]),
+        (&String::from("stsei_token"), &[]),
+    ]);
+
+    let vote = |proposal_id: u64| ExecuteMsg::VoteProposal {
+        proposal_id,
+        options: vec![WeightedVoteOption {
+            option: VoteOption::Yes,
+            weight: Decimal::one(),
+        }],
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(&addr1, &[]), vote(1)).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(&addr2, &[]), vote(1)).unwrap_err();
+    assert_eq!(err, StdError::generic_err("No bSei or stSei balance to vote with"));
+
+    let tally: ProposalTallyResponse = from_json(
+        query(deps.as_ref(), env.clone(), QueryMsg::ProposalTally { proposal_id: 1 }).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(tally.snapshot_height, snapshot_height);
+    assert_eq!(tally.total_power, Uint128::from(100u64));
+
+    // a proposal registered after the transfer counts the new balances
+    execute(deps.as_mut(), env.clone(), mock_info(&owner, &[]), register(2, &env)).unwrap();
+    let err = execute(deps.as_mut(), env.clone(), mock_info(&addr1, &[]), vote(2)).unwrap_err();
+    assert_eq!(err, StdError::generic_err("No bSei or stSei balance to vote with"));
+    execute(deps.as_mut(), env.clone(), mock_info(&addr2, &[]), vote(2)).unwrap();
+
+    let tally: ProposalTallyResponse = from_json(
+        query(deps.as_ref(), env, QueryMsg::ProposalTally { proposal_id: 2 }).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(tally.snapshot_height, snapshot_height + 1);
+    assert_eq!(tally.total_power, Uint128::from(100u64));
+}
+
+/// Covers the slashing claims on the insurance fund, their draw cap and the
+/// rebonding of the drawn coins.
+#[test]
+fn proper_insurance_fund() {
+    let mut deps = dependencies(&[]);
+    let validator = sample_validator(DEFAULT_VALIDATOR);
+    set_validator_mock(&mut deps.querier);
+
+    let owner = String::from("owner1");
+    let addr1 = String::from("addr1000");
+    let insurance_fund = String::from("insurance_fund");
+    initialize(
+        deps.borrow_mut(),
+        owner.clone(),
+        String::from("reward"),
+        String::from("token"),
+        String::from("stsei_token"),
+    );
+    do_register_validator(&mut deps, validator.clone());
+    do_bond(&mut deps, addr1.clone(), Uint128::from(1000u64));
+    deps.querier.with_token_balances(&[
+        (&String::from("token"), &[(&addr1, &Uint128::from(1000u64))]),
+        (&String::from("stsei_token"), &[]),
+    ]);
+
+    set_insurance_fund(&mut deps, &owner, &insurance_fund);
+    let update_params = UpdateParams {
+        epoch_period: None,
+        unbonding_period: None,
+        peg_recovery_fee: None,
+        er_threshold: None,
+        reward_denom: None,
+        paused: None,
+        max_unbond_requests: None,
+        keeper_fee: None,
+        epoch_source: None,
+        insurance_draw_cap: Some(Uint128::from(50u64)),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(&owner, &[]), update_params).unwrap();
+
+    // the loss is claimed up to the cap and drawn from the fund
+    set_delegation(&mut deps.querier, validator.clone(), 900, "usei");
+    let res = execute(deps.as_mut(), mock_env(), mock_info(&addr1, &[]), CheckSlashing {})
+        .unwrap();
+    assert_eq!(res.messages, vec![insurance_draw_msg(&insurance_fund, 50)]);
+
+    let restore = ExecuteMsg::RestoreBond {};
+    let info = mock_info(&addr1, &[coin(50, "usei")]);
+    let err = execute(deps.as_mut(), mock_env(), info, restore.clone()).unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let info = mock_info(&insurance_fund, &[coin(50, "usei")]);
+    let res = execute(deps.as_mut(), mock_env(), info, restore).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(StakingMsg::Delegate {
+            validator: validator.address.clone(),
+            amount: coin(50, "usei"),
+        })]
+    );
+    set_delegation(&mut deps.querier, validator, 950, "usei");
+
+    let state: StateResponse =
+        from_json(query(deps.as_ref(), mock_env(), State {}).unwrap()).unwrap();
+    assert_eq!(state.total_bond_bsei_amount, Uint128::from(950u64));
+    assert_eq!(state.bsei_exchange_rate, Decimal::from_ratio(950u64, 1000u64));
+
+    let insurance_draws = QueryMsg::InsuranceDraws {
+        start_after: None,
+        limit: None,
+    };
+    let res: InsuranceDrawsResponse =
+        from_json(query(deps.as_ref(), mock_env(), insurance_draws).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        InsuranceDrawsResponse {
+            uncovered_claim: Uint128::zero(),
+            draws: vec![InsuranceDraw {
+                id: 1,
+                time: mock_env().block.time.seconds(),
+                amount: Uint128::from(50u64),
+                bsei_exchange_rate: Decimal::from_ratio(950u64, 1000u64),
+                stsei_exchange_rate: Decimal::one(),
+            }],
+        }
+    );
+
+    // nothing is left to draw
+    let res = execute(deps.as_mut(), mock_env(), mock_info(&addr1, &[]), CheckSlashing {})
+        .unwrap();
+    assert!(res.messages.is_empty());
+}
+
+fn set_insurance_fund(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    owner: &str,
+    insurance_fund: &str,
+) {
+    let update_config = UpdateConfig {
+        rewards_dispatcher_contract: None,
+        bsei_token_contract: None,
+        airdrop_registry_contract: None,
+        validators_registry_contract: None,
+        stsei_token_contract: None,
+        rewards_contract: None,
+        update_reward_index_addr: None,
+        insurance_fund_contract: Some(insurance_fund.to_string()),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info(owner, &[]), update_config).unwrap();
+}
+
+fn insurance_draw_msg(insurance_fund: &str, amount: u64) -> SubMsg {
+    SubMsg::new(WasmMsg::Execute {
+        contract_addr: insurance_fund.to_string(),
+        msg: to_json_binary(&InsuranceExecuteMsg::Draw {
+            denom: "usei".to_string(),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+/// Covers a loss noticed on bond, its partial cover by an underfunded insurance
+/// fund and the uncovered part of the claim, which is never drawn again.
+#[test]
+fn proper_insurance_fund_partial_draw() {
+    let mut deps = dependencies(&[]);
+    let validator = sample_validator(DEFAULT_VALIDATOR);
+    set_validator_mock(&mut deps.querier);
+
+    let owner = String::from("owner1");
+    let addr1 = String::from("addr1000");
+    let addr2 = String::from("addr2000");
+    let insurance_fund = String::from("insurance_fund");
+    initialize(
+        deps.borrow_mut(),
+        owner.clone(),
+        String::from("reward"),
+        String::from("token"),
+        String::from("stsei_token"),
+    );
+    do_register_validator(&mut deps, validator.clone());
+    do_bond(&mut deps, addr1.clone(), Uint128::from(1000u64));
+    deps.querier.with_token_balances(&[
+        (&String::from("token"), &[(&addr1, &Uint128::from(1000u64))]),
+        (&String::from("stsei_token"), &[]),
+    ]);
+    set_insurance_fund(&mut deps, &owner, &insurance_fund);
+
+    // the bond notices the loss and draws it from the fund
+    set_delegation(&mut deps.querier, validator.clone(), 900, "usei");
+    let bond = ExecuteMsg::Bond {
+        recipient: None,
+        callback: None,
+        min_mint_amount: None,
+    };
+    let info = mock_info(&addr2, &[coin(100, "usei")]);
+    let res = execute(deps.as_mut(), mock_env(), info, bond).unwrap();
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(res.messages[2], insurance_draw_msg(&insurance_fund, 100));
+
+    deps.querier.with_token_balances(&[
+        (
+            &String::from("token"),
+            &[(&addr1, &Uint128::from(1000u64)), (&addr2, &Uint128::from(111u64))],
+        ),
+        (&String::from("stsei_token"), &[]),
+    ]);
+    set_delegation(&mut deps.querier, validator.clone(), 1000, "usei");
+
+    // the fund only holds a part of the claim
+    let info = mock_info(&insurance_fund, &[coin(30, "usei")]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::RestoreBond {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(StakingMsg::Delegate {
+            validator: validator.address.clone(),
+            amount: coin(30, "usei"),
+        })]
+    );
+    set_delegation(&mut deps.querier, validator.clone(), 1030, "usei");
+
+    let state: StateResponse =
+        from_json(query(deps.as_ref(), mock_env(), State {}).unwrap()).unwrap();
+    assert_eq!(state.total_bond_bsei_amount, Uint128::from(1030u64));
+
+    let insurance_draws = QueryMsg::InsuranceDraws {
+        start_after: None,
+        limit: None,
+    };
+    let res: InsuranceDrawsResponse =
+        from_json(query(deps.as_ref(), mock_env(), insurance_draws.clone()).unwrap()).unwrap();
+    assert_eq!(res.uncovered_claim, Uint128::from(70u64));
+    assert_eq!(res.draws.len(), 1);
+    assert_eq!(res.draws[0].amount, Uint128::from(30u64));
+
+    // the uncovered part is not drawn again
+    let res = execute(deps.as_mut(), mock_env(), mock_info(&addr1, &[]), CheckSlashing {})
+        .unwrap();
+    assert!(res.messages.is_empty());
+    let res: InsuranceDrawsResponse =
+        from_json(query(deps.as_ref(), mock_env(), insurance_draws.clone()).unwrap()).unwrap();
+    assert_eq!(res.uncovered_claim, Uint128::from(70u64));
+
+    // a new loss replaces it
+    set_delegation(&mut deps.querier, validator, 1010, "usei");
+    let res = execute(deps.as_mut(), mock_env(), mock_info(&addr1, &[]), CheckSlashing {})
+        .unwrap();
+    assert_eq!(res.messages, vec![insurance_draw_msg(&insurance_fund, 20)]);
+    let res: InsuranceDrawsResponse =
+        from_json(query(deps.as_ref(), mock_env(), insurance_draws).unwrap()).unwrap();
+    assert_eq!(res.uncovered_claim, Uint128::from(20u64));
+}
+
+/// Covers the shortfall of a released unbond batch, which is recorded but not
+/// claimed from the insurance fund.
+#[test]
+fn proper_insurance_fund_skips_unbonding_shortfall() {
+    let mut deps = dependencies(&[]);
+    let validator = sample_validator(DEFAULT_VALIDATOR);
+    set_validator_mock(&mut deps.querier);
+
+    let owner = String::from("owner1");
+    let bob = String::from("bob");
+    let insurance_fund = String::from("insurance_fund");
+    initialize(
+        deps.borrow_mut(),
+        owner.clone(),
+        String::from("reward"),
+        String::from("token"),
+        String::from("stsei_token"),
+    );
+    do_register_validator(&mut deps, validator.clone());
+    do_bond(&mut deps, bob.clone(), Uint128::from(1000u64));
+    set_delegation(&mut deps.querier, validator.clone(), 1000, "usei");
+    deps.querier.with_token_balances(&[
+        (&String::from("token"), &[(&bob, &Uint128::from(1000u64))]),
+        (&String::from("stsei_token"), &[]),
+    ]);
+    set_insurance_fund(&mut deps, &owner, &insurance_fund);
+
+    // the whole bond is undelegated with the first batch
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(31);
+    let res = execute_unbond(deps.as_mut(), env.clone(), Uint128::from(1000u64), bob.clone(), None)
+        .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    set_delegation(&mut deps.querier, validator, 0, "usei");
+    deps.querier.with_token_balances(&[
+        (&String::from("token"), &[]),
+        (&String::from("stsei_token"), &[]),
+    ]);
+
+    // less than the batch comes back
+    env.block.time = env.block.time.plus_seconds(91);
+    deps.querier.with_native_balances(&[(
+        String::from(MOCK_CONTRACT_ADDR),
+        Coin {
+            denom: "usei".to_string(),
+            amount: Uint128::from(900u64),
+        },
+    )]);
+    let withdraw = ExecuteMsg::WithdrawUnbonded {
+        limit: None,
+        batch_ids: None,
+        recipient: None,
+        msg: None,
+        rebond_as: None,
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info(&bob, &[]), withdraw).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert!(matches!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send { .. })));
+
+    let slashing_events = QueryMsg::SlashingEvents {
+        start_after: None,
+        limit: None,
+    };
+    let res: SlashingEventsResponse =
+        from_json(query(deps.as_ref(), mock_env(), slashing_events).unwrap()).unwrap();
+    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events[0].source, SlashingSource::Unbonding);
+
+    // no claim is made, so the next check does not draw the shortfall either
+    let res = execute(deps.as_mut(), env, mock_info(&bob, &[]), CheckSlashing {}).unwrap();
+    assert!(res.messages.is_empty());
+    let res: InsuranceDrawsResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::InsuranceDraws {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.uncovered_claim, Uint128::zero());
+    assert!(res.draws.is_empty());
+}
@@ -0,0 +1,468 @@
+// Copyright 2021 Anchor Protocol. Modified by Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The liquid staking token classes of the hub, each with its own exchange rate,
+//! bonded and requested amounts, reward policy and peg recovery fee.
+//!
+//! bSei and stSei are the built-in classes. Their amounts stay in the fields of
+//! `State` and `CurrentBatch`, which the existing queries read, and `read_classes`
+//! and `store_classes` map them to and from `TokenClass`. The slashing, the
+//! insurance restore, the batch undelegation, the release and the reward split go
+//! through the list of all classes.
+
+use crate::bond::{bonded_coin, delegation_messages, mint_messages};
+use crate::contract::slashing;
+use crate::convert::check_min_mint_amount;
+use crate::math::Peg;
+use crate::state::{
+    read_token_classes, store_unbond_wait_list, CONFIG, CURRENT_BATCH,
+    DEFAULT_MAX_UNBOND_REQUESTS, MAX_TOKEN_CLASSES, PARAMETERS, STATE, TOKEN_CLASSES,
+};
+use crate::unbond::{check_min_unbond_amount, next_batch_time, process_undelegations};
+use basset::hub::{
+    CurrentBatch, RewardPolicy, State, TokenClass, UnbondType, BSEI_CLASS, STSEI_CLASS,
+};
+use cosmwasm_std::{
+    attr, coins, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, QueryRequest, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+    WasmQuery,
+};
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, TokenInfoResponse};
+
+/// Register a new token class.
+/// Only creator/owner is allowed to execute
+pub fn execute_register_token_class(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+    token_contract: String,
+    reward_policy: RewardPolicy,
+    peg_recovery_fee: Decimal,
+    er_threshold: Decimal,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if sender_raw != config.creator {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if name.is_empty() {
+        return Err(StdError::generic_err("the token class name can not be empty"));
+    }
+    if name == BSEI_CLASS || name == STSEI_CLASS {
+        return Err(StdError::generic_err(format!(
+            "{} is a built-in token class",
+            name
+        )));
+    }
+    if TOKEN_CLASSES.may_load(deps.storage, &name)?.is_some() {
+        return Err(StdError::generic_err(format!(
+            "token class {} is already registered",
+            name
+        )));
+    }
+
+    let classes = read_token_classes(deps.storage)?;
+    if classes.len() >= MAX_TOKEN_CLASSES {
+        return Err(StdError::generic_err(format!(
+            "can not register more than {} token classes",
+            MAX_TOKEN_CLASSES
+        )));
+    }
+
+    let token_contract = deps.api.addr_validate(&token_contract)?;
+    let token_raw = Some(deps.api.addr_canonicalize(token_contract.as_str())?);
+    if config.bsei_token_contract == token_raw
+        || config.stsei_token_contract == token_raw
+        || classes.iter().any(|class| class.token_contract == token_contract)
+    {
+        return Err(StdError::generic_err("the token contract is already registered"));
+    }
+
+    match &reward_policy {
+        RewardPolicy::Dispatched => {
+            return Err(StdError::generic_err(
+                "the rewards of a registered token class can not be dispatched",
+            ));
+        }
+        RewardPolicy::RewardBearing { reward_contract } => {
+            deps.api.addr_validate(reward_contract)?;
+        }
+        RewardPolicy::Rebase => {}
+    }
+    if peg_recovery_fee > Decimal::one() {
+        return Err(StdError::generic_err(
+            "peg_recovery_fee can not be greater than 1",
+        ));
+    }
+
+    let class = TokenClass {
+        name,
+        token_contract,
+        reward_policy,
+        peg_recovery_fee,
+        er_threshold: er_threshold.min(Decimal::one()),
+        exchange_rate: Decimal::one(),
+        total_bond_amount: Uint128::zero(),
+        requested_amount: Uint128::zero(),
+    };
+    TOKEN_CLASSES.save(deps.storage, &class.name, &class)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "register_token_class"),
+        attr("name", class.name),
+        attr("token_contract", class.token_contract),
+    ]))
+}
+
+/// Update the fee settings of a token class.
+/// Only creator/owner is allowed to execute
+pub fn execute_update_token_class(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+    peg_recovery_fee: Option<Decimal>,
+    er_threshold: Option<Decimal>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if sender_raw != config.creator {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut class = load_token_class(deps.storage, &name)?;
+    if let Some(peg_recovery_fee) = peg_recovery_fee {
+        if peg_recovery_fee > Decimal::one() {
+            return Err(StdError::generic_err(
+                "peg_recovery_fee can not be greater than 1",
+            ));
+        }
+        class.peg_recovery_fee = peg_recovery_fee;
+    }
+    if let Some(er_threshold) = er_threshold {
+        class.er_threshold = er_threshold.min(Decimal::one());
+    }
+    TOKEN_CLASSES.save(deps.storage, &name, &class)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_token_class"),
+        attr("name", name),
+    ]))
+}
+
+/// Bond the underlying coin and mint the tokens of the class.
+pub fn execute_bond_class(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    recipient: Option<String>,
+    callback: Option<Binary>,
+    min_mint_amount: Option<Uint128>,
+) -> StdResult<Response> {
+    let params = PARAMETERS.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let payment = bonded_coin(&info, &params.underlying_coin_denom)?;
+
+    let sender = info.sender;
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => sender.clone(),
+    };
+    let contract_address = env.contract.address.clone();
+
+    // check slashing, the exchange rate of the class is updated as well
+    let (_, draw_msg) = slashing(&mut deps, env)?;
+    let mut class = load_token_class(deps.storage, &name)?;
+
+    let total_supply = query_total_class_issued(deps.as_ref(), &class)?;
+    let (mint_amount, _peg_fee) = Peg::class(&class).mint_amount(total_supply, payment.amount)?;
+    check_min_mint_amount(mint_amount, min_mint_amount)?;
+
+    class.total_bond_amount += payment.amount;
+    class.update_exchange_rate(total_supply + mint_amount);
+    TOKEN_CLASSES.save(deps.storage, &name, &class)?;
+
+    let mut messages = delegation_messages(&deps, &config, payment.clone())?;
+    messages.append(&mut mint_messages(
+        &class.token_contract,
+        &contract_address,
+        &recipient,
+        callback,
+        mint_amount,
+    )?);
+    messages.extend(draw_msg);
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "mint"),
+        attr("from", sender),
+        attr("recipient", recipient),
+        attr("class", name),
+        attr("bonded", payment.amount),
+        attr("minted", mint_amount),
+    ]))
+}
+
+/// This message must be call by receive_cw20
+/// This message will undelegate coin and burn the tokens of the class
+pub(crate) fn execute_unbond_class(
+    mut deps: DepsMut,
+    env: Env,
+    name: String,
+    amount: Uint128,
+    sender: String,
+    min_unbond_amount: Option<Uint128>,
+) -> StdResult<Response> {
+    let params = PARAMETERS.load(deps.storage)?;
+    let max_unbond_requests = params
+        .max_unbond_requests
+        .unwrap_or(DEFAULT_MAX_UNBOND_REQUESTS);
+
+    let mut current_batch = CURRENT_BATCH.load(deps.storage)?;
+
+    // Check slashing, update state, and calculate the new exchange rates.
+    let (mut state, draw_msg) = slashing(&mut deps, env.clone())?;
+    let mut class = load_token_class(deps.storage, &name)?;
+
+    let total_supply = query_total_class_issued(deps.as_ref(), &class)?;
+
+    // Apply peg recovery fee
+    let (amount_with_fee, _peg_fee) = Peg::class(&class).unbond_amount(total_supply, amount)?;
+    check_min_unbond_amount(amount_with_fee * class.exchange_rate, min_unbond_amount)?;
+    class.requested_amount += amount_with_fee;
+
+    store_unbond_wait_list(
+        deps.storage,
+        current_batch.id,
+        sender.clone(),
+        amount_with_fee,
+        UnbondType::Class(name.clone()),
+        max_unbond_requests,
+    )?;
+
+    class.update_exchange_rate(total_supply.checked_sub(amount)?);
+    // the batch processing below reads the requested amount of the class
+    TOKEN_CLASSES.save(deps.storage, &name, &class)?;
+
+    let current_time = env.block.time.seconds();
+    let next_time = next_batch_time(&deps.querier, &state, &params, current_time)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+
+    // If the epoch period is passed, the undelegate message would be sent.
+    if current_time >= next_time {
+        let mut undelegate_msgs =
+            process_undelegations(&mut deps, env, &mut current_batch, &mut state)?;
+        messages.append(&mut undelegate_msgs);
+    }
+
+    CURRENT_BATCH.save(deps.storage, &current_batch)?;
+    STATE.save(deps.storage, &state)?;
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: class.token_contract.to_string(),
+        msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
+        funds: vec![],
+    }));
+    messages.extend(draw_msg);
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "burn"),
+        attr("from", sender),
+        attr("class", name),
+        attr("burnt_amount", amount),
+        attr("unbonded_amount", amount_with_fee),
+    ]))
+}
+
+/// Split the bonded rewards among the classes that are not paid by the reward
+/// dispatcher, by their bonded amount. The rebase classes bond their share, the
+/// reward bearing ones get it sent to their reward contract. The rewards go to
+/// stSei while none of these classes is bonded.
+/// Returns the amount to delegate and the reward messages.
+pub(crate) fn split_class_rewards(
+    deps: &mut DepsMut,
+    amount: Uint128,
+) -> StdResult<(Uint128, Vec<CosmosMsg>)> {
+    let mut state = STATE.load(deps.storage)?;
+    let mut current_batch = CURRENT_BATCH.load(deps.storage)?;
+    let mut classes: Vec<TokenClass> = read_classes(deps.as_ref(), &state, &current_batch)?
+        .into_iter()
+        .filter(|class| class.reward_policy != RewardPolicy::Dispatched)
+        .collect();
+
+    let coin_denom = PARAMETERS.load(deps.storage)?.underlying_coin_denom;
+    let mut shares = bonded_shares(amount, &classes);
+    if shares.iter().all(|share| share.is_zero()) {
+        if let Some(stsei) = classes.iter().position(|class| class.name == STSEI_CLASS) {
+            shares[stsei] = amount;
+        }
+    }
+
+    let mut bond_amount = amount;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for (class, share) in classes.iter_mut().zip(shares) {
+        if share.is_zero() {
+            continue;
+        }
+        match &class.reward_policy {
+            RewardPolicy::Rebase => {
+                class.total_bond_amount += share;
+                let total_supply = query_total_class_issued(deps.as_ref(), class)?;
+                class.update_exchange_rate(total_supply);
+            }
+            RewardPolicy::RewardBearing { reward_contract } => {
+                bond_amount -= share;
+                messages.push(
+                    BankMsg::Send {
+                        to_address: reward_contract.clone(),
+                        amount: coins(share.u128(), &coin_denom),
+                    }
+                    .into(),
+                );
+            }
+            RewardPolicy::Dispatched => {}
+        }
+    }
+    store_classes(deps.storage, &mut state, &mut current_batch, &classes)?;
+    STATE.save(deps.storage, &state)?;
+    Ok((bond_amount, messages))
+}
+
+/// All the token classes: bSei and stSei once their token contracts are
+/// registered, then the registered classes by name.
+pub(crate) fn read_classes(
+    deps: Deps,
+    state: &State,
+    current_batch: &CurrentBatch,
+) -> StdResult<Vec<TokenClass>> {
+    let config = CONFIG.load(deps.storage)?;
+    let params = PARAMETERS.load(deps.storage)?;
+
+    let mut classes = vec![];
+    if let Some(token_contract) = config.bsei_token_contract {
+        classes.push(TokenClass {
+            name: BSEI_CLASS.to_string(),
+            token_contract: deps.api.addr_humanize(&token_contract)?,
+            reward_policy: RewardPolicy::Dispatched,
+            peg_recovery_fee: params.peg_recovery_fee,
+            er_threshold: params.er_threshold,
+            exchange_rate: state.bsei_exchange_rate,
+            total_bond_amount: state.total_bond_bsei_amount,
+            requested_amount: current_batch.requested_bsei_with_fee,
+        });
+    }
+    if let Some(token_contract) = config.stsei_token_contract {
+        classes.push(TokenClass {
+            name: STSEI_CLASS.to_string(),
+            token_contract: deps.api.addr_humanize(&token_contract)?,
+            reward_policy: RewardPolicy::Rebase,
+            peg_recovery_fee: Decimal::zero(),
+            er_threshold: Decimal::zero(),
+            exchange_rate: state.stsei_exchange_rate,
+            total_bond_amount: state.total_bond_stsei_amount,
+            requested_amount: current_batch.requested_stsei,
+        });
+    }
+    classes.extend(read_token_classes(deps.storage)?);
+    Ok(classes)
+}
+
+/// Store the classes: the built-in ones into `state` and `current_batch`, which
+/// the caller saves, the registered ones into `TOKEN_CLASSES`.
+pub(crate) fn store_classes(
+    storage: &mut dyn Storage,
+    state: &mut State,
+    current_batch: &mut CurrentBatch,
+    classes: &[TokenClass],
+) -> StdResult<()> {
+    for class in classes {
+        if !apply_built_in_class(state, current_batch, class) {
+            TOKEN_CLASSES.save(storage, &class.name, class)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy a built-in class into `state` and `current_batch`.
+/// Returns false for a registered class.
+pub(crate) fn apply_built_in_class(
+    state: &mut State,
+    current_batch: &mut CurrentBatch,
+    class: &TokenClass,
+) -> bool {
+    match class.name.as_str() {
+        BSEI_CLASS => {
+            state.bsei_exchange_rate = class.exchange_rate;
+            state.total_bond_bsei_amount = class.total_bond_amount;
+            current_batch.requested_bsei_with_fee = class.requested_amount;
+            true
+        }
+        STSEI_CLASS => {
+            state.stsei_exchange_rate = class.exchange_rate;
+            state.total_bond_stsei_amount = class.total_bond_amount;
+            current_batch.requested_stsei = class.requested_amount;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Split `amount` among the classes in proportion to their bonded amount.
+/// The last bonded class takes the rounding.
+pub(crate) fn bonded_shares(amount: Uint128, classes: &[TokenClass]) -> Vec<Uint128> {
+    let total_bonded = total_bonded(classes);
+    if total_bonded.is_zero() {
+        return vec![Uint128::zero(); classes.len()];
+    }
+    let mut shares: Vec<Uint128> = classes
+        .iter()
+        .map(|class| amount.multiply_ratio(class.total_bond_amount, total_bonded))
+        .collect();
+    let shared = shares.iter().fold(Uint128::zero(), |total, share| total + *share);
+    if let Some(last) = classes.iter().rposition(|class| !class.total_bond_amount.is_zero()) {
+        shares[last] += amount - shared;
+    }
+    shares
+}
+
+pub(crate) fn total_bonded(classes: &[TokenClass]) -> Uint128 {
+    classes
+        .iter()
+        .fold(Uint128::zero(), |total, class| total + class.total_bond_amount)
+}
+
+pub(crate) fn load_token_class(storage: &dyn Storage, name: &str) -> StdResult<TokenClass> {
+    TOKEN_CLASSES
+        .may_load(storage, name)?
+        .ok_or_else(|| StdError::generic_err(format!("token class {} is not registered", name)))
+}
+
+pub(crate) fn token_class_by_contract(
+    storage: &dyn Storage,
+    token_contract: &Addr,
+) -> StdResult<Option<TokenClass>> {
+    Ok(read_token_classes(storage)?
+        .into_iter()
+        .find(|class| class.token_contract == *token_contract))
+}
+
+pub(crate) fn query_total_class_issued(deps: Deps, class: &TokenClass) -> StdResult<Uint128> {
+    let token_info: TokenInfoResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: class.token_contract.to_string(),
+            msg: to_json_binary(&Cw20QueryMsg::TokenInfo {})?,
+        }))?;
+    Ok(token_info.total_supply)
+}
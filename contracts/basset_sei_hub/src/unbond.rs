@@ -12,22 +12,39 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::bond::execute_bond;
 use crate::contract::{query_total_bsei_issued, slashing};
+use crate::math::Peg;
 use crate::state::{
-    get_finished_amount, read_unbond_history, remove_unbond_wait_list, store_unbond_history,
-    store_unbond_wait_list, CONFIG, CURRENT_BATCH, PARAMETERS, STATE,
+    get_batch_requests, get_finished_amount, read_unbond_history,
+    remove_unbond_wait_list, store_slashing_event, store_unbond_history, store_unbond_wait_list,
+    ReleasingBatches, ReleasingClass, CONFIG, CURRENT_BATCH, DEFAULT_MAX_UNBOND_REQUESTS,
+    PARAMETERS, RELEASING_BATCHES, STATE,
+};
+use crate::token_class::{read_classes, store_classes};
+use basset::hub::{
+    ClassUnbondHistory, CurrentBatch, EpochSource, Parameters, SlashingEvent, SlashingSource,
+    State, TokenType, UnbondHistory, UnbondType, UnbondedReceiveMsg, BSEI_CLASS, STSEI_CLASS,
 };
-use basset::hub::{CurrentBatch, State, UnbondHistory, UnbondType};
 use basset_sei_validators_registry::common::calculate_undelegations;
 use basset_sei_validators_registry::registry::ValidatorResponse;
 use cosmwasm_bignumber::{Decimal256, Uint256};
 use cosmwasm_std::{
-    attr, coin, coins, to_json_binary, BankMsg, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response,
-    StakingMsg, StdError, StdResult, Storage, Uint128, WasmMsg,
+    attr, coin, coins, to_json_binary, BankMsg, Binary, CosmosMsg, Decimal, DepsMut, Env,
+    MessageInfo, QuerierWrapper, Response, StakingMsg, StdError, StdResult, Storage, Uint128,
+    WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
+use sei_cosmwasm::{Epoch, SeiQuerier, SeiQueryWrapper};
 use signed_integer::SignedInt;
 
+// number of batches visited while releasing the matured batches
+const MAX_PROCESS_LIMIT: u32 = 100;
+const DEFAULT_PROCESS_LIMIT: u32 = 30;
+
+// the Sei epoch duration is given in nanoseconds
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
 /// This message must be call by receive_cw20
 /// This message will undelegate coin and burn basset token
 pub(crate) fn execute_unbond(
@@ -35,32 +52,33 @@ pub(crate) fn execute_unbond(
     env: Env,
     amount: Uint128,
     sender: String,
+    min_unbond_amount: Option<Uint128>,
 ) -> StdResult<Response> {
     // Read params
     let params = PARAMETERS.load(deps.storage)?;
-    let epoch_period = params.epoch_period;
-    let threshold = params.er_threshold;
-    let recovery_fee = params.peg_recovery_fee;
+    let max_unbond_requests = params
+        .max_unbond_requests
+        .unwrap_or(DEFAULT_MAX_UNBOND_REQUESTS);
 
     let mut current_batch = CURRENT_BATCH.load(deps.storage)?;
 
     // Check slashing, update state, and calculate the new exchange rate.
-    let mut state = slashing(&mut deps, env.clone())?;
+    let (mut state, draw_msg) = slashing(&mut deps, env.clone())?;
 
     let mut total_supply = query_total_bsei_issued(deps.as_ref())?;
 
     // Collect all the requests within a epoch period
     // Apply peg recovery fee
-    let amount_with_fee: Uint128;
-    if state.bsei_exchange_rate < threshold {
-        let max_peg_fee = amount * recovery_fee;
-        let required_peg_fee = (total_supply + current_batch.requested_bsei_with_fee)
-            .checked_sub(state.total_bond_bsei_amount)?;
-        let peg_fee = Uint128::min(max_peg_fee, required_peg_fee);
-        amount_with_fee = amount.checked_sub(peg_fee)?;
-    } else {
-        amount_with_fee = amount;
-    }
+    let (amount_with_fee, _peg_fee) = calculate_bsei_unbond_amount(
+        &state,
+        &params,
+        current_batch.requested_bsei_with_fee,
+        total_supply,
+        amount,
+    )?;
+
+    // The peg fee and the slashing can both lower the unbonded amount
+    check_min_unbond_amount(amount_with_fee * state.bsei_exchange_rate, min_unbond_amount)?;
     current_batch.requested_bsei_with_fee += amount_with_fee;
 
     store_unbond_wait_list(
@@ -69,6 +87,7 @@ pub(crate) fn execute_unbond(
         sender.clone(),
         amount_with_fee,
         UnbondType::BSei,
+        max_unbond_requests,
     )?;
 
     total_supply -= amount;
@@ -77,12 +96,12 @@ pub(crate) fn execute_unbond(
     state.update_bsei_exchange_rate(total_supply, current_batch.requested_bsei_with_fee);
 
     let current_time = env.block.time.seconds();
-    let passed_time = current_time - state.last_unbonded_time;
+    let next_time = next_batch_time(&deps.querier, &state, &params, current_time)?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
 
     // If the epoch period is passed, the undelegate message would be sent.
-    if passed_time > epoch_period {
+    if current_time >= next_time {
         let mut undelegate_msgs =
             process_undelegations(&mut deps, env, &mut current_batch, &mut state)?;
         messages.append(&mut undelegate_msgs);
@@ -108,6 +127,7 @@ pub(crate) fn execute_unbond(
         msg: to_json_binary(&burn_msg)?,
         funds: vec![],
     }));
+    messages.extend(draw_msg);
 
     let res = Response::new().add_messages(messages).add_attributes(vec![
         attr("action", "burn"),
@@ -118,14 +138,29 @@ pub(crate) fn execute_unbond(
     Ok(res)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn execute_withdraw_unbonded(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    limit: Option<u32>,
+    batch_ids: Option<Vec<u64>>,
+    recipient: Option<String>,
+    msg: Option<Binary>,
+    rebond_as: Option<TokenType>,
 ) -> StdResult<Response> {
     let sender_human = info.sender;
     let contract_address = env.contract.address.clone();
 
+    if msg.is_some() && rebond_as.is_some() {
+        return Err(StdError::generic_err("msg and rebond_as can not be used together"));
+    }
+
+    let recipient = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => sender_human.clone(),
+    };
+
     // read params
     let params = PARAMETERS.load(deps.storage)?;
     let unbonding_period = params.unbonding_period;
@@ -140,10 +175,10 @@ pub fn execute_withdraw_unbonded(
         .amount;
 
     // calculate withdraw rate for user requests
-    process_withdraw_rate(&mut deps, historical_time, hub_balance)?;
+    process_withdraw_rate(&mut deps, &env, historical_time, hub_balance, DEFAULT_PROCESS_LIMIT)?;
 
     let (withdraw_amount, deprecated_batches) =
-        get_finished_amount(deps.storage, sender_human.to_string())?;
+        get_finished_amount(deps.storage, sender_human.to_string(), batch_ids, limit)?;
 
     if withdraw_amount.is_zero() {
         return Err(StdError::generic_err(format!(
@@ -152,44 +187,184 @@ pub fn execute_withdraw_unbonded(
         )));
     }
 
+    let withdrawn_batches = deprecated_batches.len();
+
     // remove the previous batches for the user
     remove_unbond_wait_list(deps.storage, deprecated_batches, sender_human.to_string())?;
 
-    // Update previous balance used for calculation in next sei batch release
-    let prev_balance = hub_balance.checked_sub(withdraw_amount)?;
-    STATE.update(deps.storage, |mut last_state| -> StdResult<_> {
-        last_state.prev_hub_balance = prev_balance;
-        Ok(last_state)
-    })?;
-
-    // Send the money to the user
-    let msgs: Vec<CosmosMsg> = vec![BankMsg::Send {
-        to_address: sender_human.to_string(),
-        amount: coins(withdraw_amount.u128(), &*coin_denom),
-    }
-    .into()];
+    // Update previous balance used for calculation in next sei batch release.
+    update_prev_hub_balance(deps.storage, hub_balance, withdraw_amount)?;
 
-    let res = Response::new().add_messages(msgs).add_attributes(vec![
+    let attrs = vec![
         attr("action", "finish_burn"),
         attr("from", contract_address),
+        attr("recipient", recipient.clone()),
         attr("amount", withdraw_amount),
-    ]);
+        attr("withdrawn_batches", withdrawn_batches.to_string()),
+    ];
+    let funds = coins(withdraw_amount.u128(), &*coin_denom);
+
+    // Bond the money again on behalf of the recipient
+    if let Some(rebond_as) = rebond_as {
+        let bond_info = MessageInfo {
+            sender: sender_human,
+            funds,
+        };
+        let bond_res = execute_bond(
+            deps,
+            env,
+            bond_info,
+            rebond_as.into(),
+            Some(recipient.to_string()),
+            None,
+            None,
+        )?;
+        return Ok(Response::new()
+            .add_attributes(attrs)
+            .add_submessages(bond_res.messages)
+            .add_attributes(bond_res.attributes));
+    }
+
+    // Send the money to the recipient
+    let send_msg: CosmosMsg = match msg {
+        Some(msg) => UnbondedReceiveMsg {
+            sender: sender_human.to_string(),
+            amount: withdraw_amount,
+            msg,
+        }
+        .into_cosmos_msg(recipient, funds)?,
+        None => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: funds,
+        }
+        .into(),
+    };
+
+    let res = Response::new().add_message(send_msg).add_attributes(attrs);
     Ok(res)
 }
 
+/// Keeper-driven withdrawal of a released batch.
+/// Sends every user of the batch their share and pays the caller the keeper fee.
+pub fn execute_distribute_released(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    batch_id: u64,
+    limit: Option<u32>,
+) -> StdResult<Response> {
+    let params = PARAMETERS.load(deps.storage)?;
+    let coin_denom = params.underlying_coin_denom;
+    let keeper_fee = params.keeper_fee.unwrap_or_else(Decimal::zero);
+
+    let historical_time = env.block.time.seconds() - params.unbonding_period;
+
+    let hub_balance = deps
+        .querier
+        .query_balance(&env.contract.address, &*coin_denom)?
+        .amount;
+
+    // calculate withdraw rate for user requests
+    process_withdraw_rate(&mut deps, &env, historical_time, hub_balance, DEFAULT_PROCESS_LIMIT)?;
+
+    let history = read_unbond_history(deps.storage, batch_id)?;
+    if !history.released {
+        return Err(StdError::generic_err(format!(
+            "Batch {} has not been released yet",
+            batch_id
+        )));
+    }
+
+    // distributed users are dropped from the batch index, so always read from the start
+    let requests = get_batch_requests(deps.storage, batch_id, None, limit)?;
+    if requests.is_empty() {
+        return Err(StdError::generic_err(format!(
+            "No requests left to distribute in batch {}",
+            batch_id
+        )));
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut total_amount = Uint128::zero();
+    let mut keeper_tip = Uint128::zero();
+    for (user, _) in requests.iter() {
+        let (amount, batches) =
+            get_finished_amount(deps.storage, user.clone(), Some(vec![batch_id]), None)?;
+        remove_unbond_wait_list(deps.storage, batches, user.clone())?;
+        if amount.is_zero() {
+            continue;
+        }
+
+        let tip = amount * keeper_fee;
+        total_amount += amount;
+        keeper_tip += tip;
+        messages.push(
+            BankMsg::Send {
+                to_address: user.clone(),
+                amount: coins((amount - tip).u128(), &*coin_denom),
+            }
+            .into(),
+        );
+    }
+
+    if !keeper_tip.is_zero() {
+        messages.push(
+            BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(keeper_tip.u128(), &*coin_denom),
+            }
+            .into(),
+        );
+    }
+
+    update_prev_hub_balance(deps.storage, hub_balance, total_amount)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "distribute_released"),
+        attr("batch_id", batch_id.to_string()),
+        attr("distributed_requests", requests.len().to_string()),
+        attr("amount", total_amount),
+        attr("keeper_tip", keeper_tip),
+    ]))
+}
+
+/// While some matured batches are still pending, the funds received for them
+/// must stay out of the previous balance.
+fn update_prev_hub_balance(
+    storage: &mut dyn Storage,
+    hub_balance: Uint128,
+    withdraw_amount: Uint128,
+) -> StdResult<()> {
+    let releasing = RELEASING_BATCHES.may_load(storage)?.is_some();
+    STATE.update(storage, |mut last_state| -> StdResult<_> {
+        let prev_balance = if releasing {
+            last_state.prev_hub_balance
+        } else {
+            hub_balance
+        };
+        last_state.prev_hub_balance = prev_balance.checked_sub(withdraw_amount)?;
+        Ok(last_state)
+    })?;
+    Ok(())
+}
+
+/// The unbonded amounts of the token classes are added to `classes`.
 fn calculate_newly_added_unbonded_amount(
     storage: &mut dyn Storage,
     last_processed_batch: u64,
     historical_time: u64,
-) -> (Uint256, Uint256, u64) {
-    let mut stsei_total_unbonded_amount = Uint256::zero();
-    let mut bsei_total_unbonded_amount = Uint256::zero();
+    limit: u32,
+    classes: &mut Vec<ReleasingClass>,
+) -> (u64, bool) {
     let mut batch_count: u64 = 0;
 
     // Iterate over unbonded histories that have been processed
     // to calculate newly added unbonded amount
     let mut i = last_processed_batch + 1;
     loop {
+        if batch_count >= limit as u64 {
+            return (batch_count, false);
+        }
         let history: UnbondHistory;
         match read_unbond_history(storage, i) {
             Ok(h) => {
@@ -204,25 +379,31 @@ fn calculate_newly_added_unbonded_amount(
             }
             Err(_) => break,
         }
-        let stsei_burnt_amount = Uint256::from(history.stsei_amount);
-        let stsei_historical_rate = Decimal256::from(history.stsei_withdraw_rate);
-        let stsei_unbonded_amount = stsei_burnt_amount * stsei_historical_rate;
-
-        let bsei_burnt_amount = Uint256::from(history.bsei_amount);
-        let bsei_historical_rate = Decimal256::from(history.bsei_withdraw_rate);
-        let bsei_unbonded_amount = bsei_burnt_amount * bsei_historical_rate;
-
-        stsei_total_unbonded_amount += stsei_unbonded_amount;
-        bsei_total_unbonded_amount += bsei_unbonded_amount;
+        for class in history.class_histories() {
+            let unbonded_amount =
+                Uint256::from(class.amount) * Decimal256::from(class.withdraw_rate);
+            match classes.iter_mut().find(|c| c.class == class.class) {
+                Some(c) => c.total_unbonded_amount += unbonded_amount,
+                None => classes.push(ReleasingClass {
+                    class: class.class,
+                    total_unbonded_amount: unbonded_amount,
+                    actual_unbonded_amount: Uint256::zero(),
+                }),
+            }
+        }
         batch_count += 1;
         i += 1;
     }
 
-    (
-        stsei_total_unbonded_amount,
-        bsei_total_unbonded_amount,
-        batch_count,
-    )
+    (batch_count, true)
+}
+
+fn slashed_amount(unbonded_amount: Uint256, actual_unbonded_amount: Uint256) -> Uint128 {
+    if unbonded_amount > actual_unbonded_amount {
+        (unbonded_amount - actual_unbonded_amount).into()
+    } else {
+        Uint128::zero()
+    }
 }
 
 fn calculate_new_withdraw_rate(
@@ -272,92 +453,161 @@ fn calculate_new_withdraw_rate(
 }
 
 /// This is designed for an accurate unbonded amount calculation.
-/// Execute while processing withdraw_unbonded
+/// Execute while processing withdraw_unbonded or process_released_batches.
+/// At most `limit` batches are visited. The matured batches are first collected
+/// into a window; once all of them are collected, the slashed amount is computed
+/// for the whole window and the new withdraw rates are applied batch by batch.
+/// This way the slashing is distributed the same whether the window is processed
+/// in one call or in several ones.
 fn process_withdraw_rate(
     deps: &mut DepsMut,
+    env: &Env,
     historical_time: u64,
     hub_balance: Uint128,
+    limit: u32,
 ) -> StdResult<()> {
     let mut state = STATE.load(deps.storage)?;
 
     let last_processed_batch = state.last_processed_batch;
+    let mut window = RELEASING_BATCHES
+        .may_load(deps.storage)?
+        .unwrap_or(ReleasingBatches {
+            last_batch: last_processed_batch,
+            ..Default::default()
+        });
+    let mut budget = limit as u64;
+
+    if !window.sealed {
+        let mut classes = window.releasing_classes();
+        let (batch_count, complete) = calculate_newly_added_unbonded_amount(
+            deps.storage,
+            window.last_batch,
+            historical_time,
+            limit,
+            &mut classes,
+        );
+        window.last_batch += batch_count;
+        budget -= batch_count;
 
-    let (stsei_total_unbonded_amount, bsei_total_unbonded_amount, batch_count) =
-        calculate_newly_added_unbonded_amount(deps.storage, last_processed_batch, historical_time);
+        if !complete {
+            for class in classes {
+                window.set_releasing_class(class);
+            }
+            RELEASING_BATCHES.save(deps.storage, &window)?;
+            return Ok(());
+        }
 
-    if batch_count < 1 {
-        return Ok(());
-    }
+        if window.last_batch == last_processed_batch {
+            RELEASING_BATCHES.remove(deps.storage);
+            return Ok(());
+        }
 
-    let balance_change = SignedInt::from_subtraction(hub_balance, state.prev_hub_balance);
+        let balance_change = SignedInt::from_subtraction(hub_balance, state.prev_hub_balance);
 
-    // if balance change is negativity, return error
-    if balance_change.1 {
-        return Err(StdError::generic_err("current balance of hub contract can not be lower than prev one."));
-    }
+        // if balance change is negativity, return error
+        if balance_change.1 {
+            return Err(StdError::generic_err("current balance of hub contract can not be lower than prev one."));
+        }
 
-    let actual_unbonded_amount = balance_change.0;
+        // the received amount is shared among the classes by their unbonded amount,
+        // the last unbonded class takes the rounding
+        let received_amount = Uint256::from(balance_change.0);
+        let expected_amount = classes
+            .iter()
+            .fold(Uint256::zero(), |total, class| total + class.total_unbonded_amount);
+        if !expected_amount.is_zero() {
+            let mut remaining = received_amount;
+            for class in classes.iter_mut() {
+                class.actual_unbonded_amount = Uint128::from(received_amount)
+                    .multiply_ratio(
+                        Uint128::from(class.total_unbonded_amount),
+                        Uint128::from(expected_amount),
+                    )
+                    .into();
+                remaining = remaining - class.actual_unbonded_amount;
+            }
+            if let Some(last) = classes
+                .iter_mut()
+                .rev()
+                .find(|class| !class.total_unbonded_amount.is_zero())
+            {
+                last.actual_unbonded_amount += remaining;
+            }
+        }
+        window.sealed = true;
+
+        // record the losses of the released batches, which are not claimed from the
+        // insurance fund (see `insurance`)
+        if received_amount < expected_amount {
+            let (mut bsei_slashed, mut stsei_slashed, mut classes_slashed) =
+                (Uint128::zero(), Uint128::zero(), Uint128::zero());
+            for class in &classes {
+                let slashed =
+                    slashed_amount(class.total_unbonded_amount, class.actual_unbonded_amount);
+                match class.class.as_str() {
+                    BSEI_CLASS => bsei_slashed += slashed,
+                    STSEI_CLASS => stsei_slashed += slashed,
+                    _ => classes_slashed += slashed,
+                }
+            }
+            store_slashing_event(
+                deps.storage,
+                SlashingEvent {
+                    id: 0,
+                    time: env.block.time.seconds(),
+                    source: SlashingSource::Unbonding,
+                    amount_before: expected_amount.into(),
+                    amount_after: received_amount.into(),
+                    bsei_slashed,
+                    stsei_slashed,
+                    classes_slashed,
+                    first_batch: Some(last_processed_batch + 1),
+                    last_batch: Some(window.last_batch),
+                },
+            )?;
+        }
+        for class in classes {
+            window.set_releasing_class(class);
+        }
 
-    let mut bsei_unbond_ratio = Decimal256::zero();
-    if stsei_total_unbonded_amount + bsei_total_unbonded_amount > Uint256::zero() {
-        let stsei_unbond_ratio = Decimal256::from_ratio(
-            stsei_total_unbonded_amount.0,
-            (stsei_total_unbonded_amount + bsei_total_unbonded_amount).0,
-        );
-        bsei_unbond_ratio = Decimal256::one() - stsei_unbond_ratio;
+        // The current balance is now accounted for by the batches of the window.
+        state.prev_hub_balance = hub_balance;
     }
 
-    let bsei_actual_unbonded_amount = Uint256::from(actual_unbonded_amount) * bsei_unbond_ratio;
-    // Use signed integer in case of some rogue transfers.
-    let bsei_slashed_amount =
-        SignedInt::from_subtraction(bsei_total_unbonded_amount, bsei_actual_unbonded_amount);
-    let stsei_slashed_amount = SignedInt::from_subtraction(
-        stsei_total_unbonded_amount,
-        Uint256::from(actual_unbonded_amount) - bsei_actual_unbonded_amount,
-    );
-
     // Iterate again to calculate the withdraw rate for each unprocessed history
-    let mut iterator = last_processed_batch + 1;
-    loop {
-        let history: UnbondHistory;
-        match read_unbond_history(deps.storage, iterator) {
-            Ok(h) => {
-                if h.time > historical_time {
-                    break;
-                }
-                if !h.released {
-                    history = h
-                } else {
-                    break;
-                }
-            }
-            Err(_) => {
-                break;
+    let window_classes = window.releasing_classes();
+    let mut iterator = state.last_processed_batch + 1;
+    while budget > 0 && iterator <= window.last_batch {
+        let mut history = read_unbond_history(deps.storage, iterator)?;
+
+        // Calculate the new withdraw rate of each class.
+        // Use signed integer in case of some rogue transfers.
+        for mut class in history.class_histories() {
+            if let Some(window_class) = window_classes.iter().find(|c| c.class == class.class) {
+                class.withdraw_rate = calculate_new_withdraw_rate(
+                    class.amount,
+                    class.withdraw_rate,
+                    window_class.total_unbonded_amount,
+                    SignedInt::from_subtraction(
+                        window_class.total_unbonded_amount,
+                        window_class.actual_unbonded_amount,
+                    ),
+                );
+                history.set_class_history(class);
             }
         }
-
-        // Calculate the new withdraw rate
-        let stsei_new_withdraw_rate = calculate_new_withdraw_rate(
-            history.stsei_amount,
-            history.stsei_withdraw_rate,
-            stsei_total_unbonded_amount,
-            stsei_slashed_amount,
-        );
-        let bsei_new_withdraw_rate = calculate_new_withdraw_rate(
-            history.bsei_amount,
-            history.bsei_withdraw_rate,
-            bsei_total_unbonded_amount,
-            bsei_slashed_amount,
-        );
-
-        let mut history_for_i = history;
         // store the history and mark it as released
-        history_for_i.bsei_withdraw_rate = bsei_new_withdraw_rate;
-        history_for_i.stsei_withdraw_rate = stsei_new_withdraw_rate;
-        history_for_i.released = true;
-        store_unbond_history(deps.storage, iterator, history_for_i)?;
+        history.released = true;
+        store_unbond_history(deps.storage, iterator, history)?;
         state.last_processed_batch = iterator;
         iterator += 1;
+        budget -= 1;
+    }
+
+    if state.last_processed_batch == window.last_batch {
+        RELEASING_BATCHES.remove(deps.storage);
+    } else {
+        RELEASING_BATCHES.save(deps.storage, &window)?;
     }
 
     STATE.save(deps.storage, &state)?;
@@ -365,6 +615,37 @@ fn process_withdraw_rate(
     Ok(())
 }
 
+/// Release the matured batches in bounded steps, so that the withdrawals stay cheap.
+/// Permissionless
+pub fn execute_process_released_batches(
+    mut deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> StdResult<Response> {
+    let params = PARAMETERS.load(deps.storage)?;
+    let historical_time = env.block.time.seconds() - params.unbonding_period;
+
+    let hub_balance = deps
+        .querier
+        .query_balance(&env.contract.address, &*params.underlying_coin_denom)?
+        .amount;
+
+    let limit = limit.unwrap_or(DEFAULT_PROCESS_LIMIT).min(MAX_PROCESS_LIMIT);
+    process_withdraw_rate(&mut deps, &env, historical_time, hub_balance, limit)?;
+
+    let state = STATE.load(deps.storage)?;
+    let pending_batch = RELEASING_BATCHES
+        .may_load(deps.storage)?
+        .map(|window| window.last_batch)
+        .unwrap_or(state.last_processed_batch);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "process_released_batches"),
+        attr("last_processed_batch", state.last_processed_batch.to_string()),
+        attr("last_pending_batch", pending_batch.to_string()),
+    ]))
+}
+
 fn pick_validator(deps: &DepsMut, claim: Uint128, delegator: String) -> StdResult<Vec<CosmosMsg>> {
     //read params
     let params = PARAMETERS.load(deps.storage)?;
@@ -406,16 +687,21 @@ pub(crate) fn execute_unbond_stsei(
     env: Env,
     amount: Uint128,
     sender: String,
+    min_unbond_amount: Option<Uint128>,
 ) -> StdResult<Response> {
     // Read params
 
     let params = PARAMETERS.load(deps.storage)?;
-    let epoch_period = params.epoch_period;
+    let max_unbond_requests = params
+        .max_unbond_requests
+        .unwrap_or(DEFAULT_MAX_UNBOND_REQUESTS);
 
     let mut current_batch = CURRENT_BATCH.load(deps.storage)?;
 
     // Check slashing, update state, and calculate the new exchange rate.
-    let mut state = slashing(&mut deps, env.clone())?;
+    let (mut state, draw_msg) = slashing(&mut deps, env.clone())?;
+
+    check_min_unbond_amount(amount * state.stsei_exchange_rate, min_unbond_amount)?;
 
     // Collect all the requests within a epoch period
     current_batch.requested_stsei += amount;
@@ -426,15 +712,16 @@ pub(crate) fn execute_unbond_stsei(
         sender.clone(),
         amount,
         UnbondType::StSei,
+        max_unbond_requests,
     )?;
 
     let current_time = env.block.time.seconds();
-    let passed_time = current_time - state.last_unbonded_time;
+    let next_time = next_batch_time(&deps.querier, &state, &params, current_time)?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
 
     // If the epoch period is passed, the undelegate message would be sent.
-    if passed_time > epoch_period {
+    if current_time >= next_time {
         let mut undelegate_msgs =
             process_undelegations(&mut deps, env, &mut current_batch, &mut state)?;
         messages.append(&mut undelegate_msgs);
@@ -461,6 +748,7 @@ pub(crate) fn execute_unbond_stsei(
         msg: to_json_binary(&burn_msg)?,
         funds: vec![],
     }));
+    messages.extend(draw_msg);
 
     let res = Response::new().add_messages(messages).add_attributes(vec![
         attr("action", "burn"),
@@ -471,54 +759,167 @@ pub(crate) fn execute_unbond_stsei(
     Ok(res)
 }
 
-fn process_undelegations(
+pub(crate) fn check_min_unbond_amount(
+    unbonded_amount: Uint128,
+    min_unbond_amount: Option<Uint128>,
+) -> StdResult<()> {
+    if let Some(min_unbond_amount) = min_unbond_amount {
+        if unbonded_amount < min_unbond_amount {
+            return Err(StdError::generic_err(format!(
+                "Unbonded amount {} is less than the minimum {}",
+                unbonded_amount, min_unbond_amount
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the amount of bSei that is unbonded after the peg recovery fee
+/// and the fee itself.
+pub(crate) fn calculate_bsei_unbond_amount(
+    state: &State,
+    params: &Parameters,
+    requested_bsei_with_fee: Uint128,
+    total_supply: Uint128,
+    amount: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    Peg::bsei(state, params, requested_bsei_with_fee).unbond_amount(total_supply, amount)
+}
+
+/// Returns the earliest time the requests of the current batch can be withdrawn.
+/// The batch is undelegated by the first unbond after the epoch period.
+pub(crate) fn estimate_release_time(
+    querier: &QuerierWrapper,
+    state: &State,
+    params: &Parameters,
+    now: u64,
+) -> StdResult<u64> {
+    Ok(next_batch_time(querier, state, params, now)?.max(now) + params.unbonding_period)
+}
+
+/// The current batch is undelegated once more than `epoch_period` has passed.
+/// With the Sei epoch source it waits for the next chain epoch instead, so the
+/// returned time is estimated from the epoch duration.
+pub(crate) fn next_batch_time(
+    querier: &QuerierWrapper,
+    state: &State,
+    params: &Parameters,
+    now: u64,
+) -> StdResult<u64> {
+    match (&params.epoch_source, state.last_unbonded_epoch) {
+        (Some(EpochSource::SeiEpoch), Some(last_unbonded_epoch)) => {
+            let epoch = query_sei_epoch(querier)?;
+            let duration = (epoch.duration / NANOS_PER_SECOND).max(1);
+            let epochs_per_batch = params.epoch_period.div_ceil(duration).max(1);
+            let remaining_epochs =
+                (last_unbonded_epoch + epochs_per_batch).saturating_sub(epoch.current_epoch);
+            Ok(now + remaining_epochs * duration)
+        }
+        _ => Ok(state.last_unbonded_time + params.epoch_period + 1),
+    }
+}
+
+fn query_sei_epoch(querier: &QuerierWrapper) -> StdResult<Epoch> {
+    let sei_querier = QuerierWrapper::<SeiQueryWrapper>::new(&**querier);
+    Ok(SeiQuerier::new(&sei_querier).query_epoch()?.epoch)
+}
+
+/// Undelegate the current batch without waiting for the next unbond request.
+pub fn execute_process_batch(mut deps: DepsMut, env: Env) -> StdResult<Response> {
+    let params = PARAMETERS.load(deps.storage)?;
+    let mut current_batch = CURRENT_BATCH.load(deps.storage)?;
+
+    let state = STATE.load(deps.storage)?;
+    let requested = read_classes(deps.as_ref(), &state, &current_batch)?
+        .iter()
+        .any(|class| !class.requested_amount.is_zero());
+    if !requested {
+        return Err(StdError::generic_err("The current batch has no unbond requests"));
+    }
+
+    // Check slashing and update state
+    let (mut state, draw_msg) = slashing(&mut deps, env.clone())?;
+
+    let now = env.block.time.seconds();
+    let next_time = next_batch_time(&deps.querier, &state, &params, now)?;
+    if now < next_time {
+        return Err(StdError::generic_err(format!(
+            "The current batch can not be processed before {}",
+            next_time
+        )));
+    }
+
+    let batch_id = current_batch.id;
+    let mut messages = process_undelegations(&mut deps, env, &mut current_batch, &mut state)?;
+    messages.extend(draw_msg);
+
+    CURRENT_BATCH.save(deps.storage, &current_batch)?;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "process_batch"),
+        attr("batch_id", batch_id.to_string()),
+    ]))
+}
+
+pub(crate) fn process_undelegations(
     deps: &mut DepsMut,
     env: Env,
     current_batch: &mut CurrentBatch,
     state: &mut State,
 ) -> StdResult<Vec<CosmosMsg>> {
-    // Apply the current exchange rate.
-    let stsei_undelegation_amount = current_batch.requested_stsei * state.stsei_exchange_rate;
-    let bsei_undelegation_amount = current_batch.requested_bsei_with_fee * state.bsei_exchange_rate;
     let delegator = env.contract.address;
-
-    // Send undelegated requests to possibly more than one validators
-    let undelegated_msgs = pick_validator(
-        deps,
-        bsei_undelegation_amount + stsei_undelegation_amount,
-        delegator.to_string(),
-    )?;
-
-    state.total_bond_stsei_amount = state
-        .total_bond_stsei_amount
-        .checked_sub(stsei_undelegation_amount)?;
-    state.total_bond_bsei_amount = state
-        .total_bond_bsei_amount
-        .checked_sub(bsei_undelegation_amount)?;
-
-    // Store history for withdraw unbonded
-    let history = UnbondHistory {
+    let mut history = UnbondHistory {
         batch_id: current_batch.id,
         time: env.block.time.seconds(),
-        stsei_amount: current_batch.requested_stsei,
-        stsei_applied_exchange_rate: state.stsei_exchange_rate,
-        stsei_withdraw_rate: state.stsei_exchange_rate,
-
-        bsei_amount: current_batch.requested_bsei_with_fee,
+        bsei_amount: Uint128::zero(),
         bsei_applied_exchange_rate: state.bsei_exchange_rate,
         bsei_withdraw_rate: state.bsei_exchange_rate,
 
+        stsei_amount: Uint128::zero(),
+        stsei_applied_exchange_rate: state.stsei_exchange_rate,
+        stsei_withdraw_rate: state.stsei_exchange_rate,
+
         released: false,
+        classes: None,
     };
 
+    // Apply the current exchange rate of each class.
+    let mut undelegation_amount = Uint128::zero();
+    let mut classes = read_classes(deps.as_ref(), state, current_batch)?;
+    for class in classes.iter_mut() {
+        if class.requested_amount.is_zero() {
+            continue;
+        }
+        let class_undelegation_amount = class.requested_amount * class.exchange_rate;
+        class.total_bond_amount = class
+            .total_bond_amount
+            .checked_sub(class_undelegation_amount)?;
+        undelegation_amount += class_undelegation_amount;
+        history.set_class_history(ClassUnbondHistory {
+            class: class.name.clone(),
+            amount: class.requested_amount,
+            applied_exchange_rate: class.exchange_rate,
+            withdraw_rate: class.exchange_rate,
+        });
+        class.requested_amount = Uint128::zero();
+    }
+    store_classes(deps.storage, state, current_batch, &classes)?;
+
+    // Send undelegated requests to possibly more than one validators
+    let undelegated_msgs = pick_validator(deps, undelegation_amount, delegator.to_string())?;
+
+    // Store history for withdraw unbonded
     store_unbond_history(deps.storage, current_batch.id, history)?;
     // batch info must be updated to new batch
     current_batch.id += 1;
-    current_batch.requested_stsei = Uint128::zero();
-    current_batch.requested_bsei_with_fee = Uint128::zero();
 
     // state.last_unbonded_time must be updated to the current block time
     state.last_unbonded_time = env.block.time.seconds();
+    let params = PARAMETERS.load(deps.storage)?;
+    if params.epoch_source == Some(EpochSource::SeiEpoch) {
+        state.last_unbonded_epoch = Some(query_sei_epoch(&deps.querier)?.current_epoch);
+    }
 
     Ok(undelegated_msgs)
 }
@@ -0,0 +1,134 @@
+// Copyright 2021 Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! The insurance fund holds the underlying coins set aside to cover slashing,
+//! usually funded by a reward route of the rewards dispatcher. When the hub
+//! notices a slashing, it draws from the fund and rebonds the coins, so that
+//! the exchange rates are restored instead of the holders taking the loss.
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use basset::hub::ExecuteMsg as HubExecuteMsg;
+use cosmwasm_std::{
+    attr, coins, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+
+use crate::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{Config, CONFIG};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let owner = msg.owner.unwrap_or_else(|| info.sender.to_string());
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_canonicalize(&owner)?,
+            hub_contract: deps.api.addr_canonicalize(&msg.hub_contract)?,
+        },
+    )?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            owner,
+            hub_contract,
+        } => execute_update_config(deps, info, owner, hub_contract),
+        ExecuteMsg::Draw { denom, amount } => execute_draw(deps, env, info, denom, amount),
+    }
+}
+
+/// Only creator/owner is allowed to execute
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    hub_contract: Option<String>,
+) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_canonicalize(&owner)?;
+    }
+    if let Some(hub_contract) = hub_contract {
+        config.hub_contract = deps.api.addr_canonicalize(&hub_contract)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "update_config")]))
+}
+
+/// Send what the fund holds of the requested amount to the hub to be rebonded.
+/// Only the hub is allowed to execute
+pub fn execute_draw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.hub_contract {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &denom)?
+        .amount;
+    let drawn = amount.min(balance);
+
+    let mut response = Response::new();
+    if !drawn.is_zero() {
+        response = response.add_message(WasmMsg::Execute {
+            contract_addr: info.sender.to_string(),
+            msg: to_json_binary(&HubExecuteMsg::RestoreBond {})?,
+            funds: coins(drawn.u128(), &denom),
+        });
+    }
+    Ok(response.add_attributes(vec![
+        attr("action", "draw"),
+        attr("requested", amount),
+        attr("drawn", drawn),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: deps.api.addr_humanize(&config.owner)?.to_string(),
+        hub_contract: deps.api.addr_humanize(&config.hub_contract)?.to_string(),
+    })
+}
@@ -0,0 +1,125 @@
+// Copyright 2021 Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use crate::contract::{execute, instantiate, query};
+use crate::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use basset::hub::ExecuteMsg as HubExecuteMsg;
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    attr, coins, from_json, to_json_binary, OwnedDeps, StdError, SubMsg, Uint128, WasmMsg,
+};
+
+fn init() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg {
+        hub_contract: "hub".to_string(),
+        owner: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    deps
+}
+
+fn draw(amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Draw {
+        denom: "usei".to_string(),
+        amount: Uint128::new(amount),
+    }
+}
+
+#[test]
+fn proper_update_config() {
+    let mut deps = init();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some("new_owner".to_string()),
+        hub_contract: Some("new_hub".to_string()),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("hub", &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: "new_owner".to_string(),
+            hub_contract: "new_hub".to_string(),
+        }
+    );
+}
+
+fn restore_bond(amount: u128) -> SubMsg {
+    SubMsg::new(WasmMsg::Execute {
+        contract_addr: "hub".to_string(),
+        msg: to_json_binary(&HubExecuteMsg::RestoreBond {}).unwrap(),
+        funds: coins(amount, "usei"),
+    })
+}
+
+#[test]
+fn proper_draw_only_by_hub() {
+    let mut deps = init();
+    deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins(100, "usei"));
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), draw(100)).unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    // the previous hub can not draw once it is replaced
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        hub_contract: Some("new_hub".to_string()),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+    let err = execute(deps.as_mut(), mock_env(), mock_info("hub", &[]), draw(100)).unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    execute(deps.as_mut(), mock_env(), mock_info("new_hub", &[]), draw(100)).unwrap();
+}
+
+#[test]
+fn proper_draw_from_empty_fund() {
+    let mut deps = init();
+
+    // an empty fund has nothing to send
+    let res = execute(deps.as_mut(), mock_env(), mock_info("hub", &[]), draw(100)).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "draw"), attr("requested", "100"), attr("drawn", "0")]
+    );
+
+    // the coins of another denom are not drawn
+    deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins(100, "uatom"));
+    let res = execute(deps.as_mut(), mock_env(), mock_info("hub", &[]), draw(100)).unwrap();
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+fn proper_draw_limited_to_balance() {
+    let mut deps = init();
+    deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins(60, "usei"));
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("hub", &[]), draw(100)).unwrap();
+    assert_eq!(res.messages, vec![restore_bond(60)]);
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "draw"), attr("requested", "100"), attr("drawn", "60")]
+    );
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("hub", &[]), draw(40)).unwrap();
+    assert_eq!(res.messages, vec![restore_bond(40)]);
+}
@@ -26,7 +26,9 @@ use cosmwasm_std::{
     to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
 };
 
-use crate::handler::{udpate_config, update_swap_denom, set_new_owner, accept_ownership};
+use crate::handler::{
+    accept_ownership, set_new_owner, udpate_config, update_swap_denom, update_token_contract,
+};
 use basset::reward::{
     ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, StateResponse, NewOwnerResponse,
 };
@@ -107,6 +109,10 @@ pub fn execute(
         ExecuteMsg::UpdateSwapDenom { swap_denom, is_add } => {
             update_swap_denom(deps, info, swap_denom, is_add)
         }
+        ExecuteMsg::UpdateTokenContract { token_contract } => {
+            let api = deps.api;
+            update_token_contract(deps, info, api.addr_validate(&token_contract)?)
+        }
     }
 }
 
@@ -1,4 +1,5 @@
-use crate::{state::{read_config, store_config, read_new_owner, store_new_owner}, error::ContractError};
+use crate::error::ContractError;
+use crate::state::{read_config, read_new_owner, store_config, store_new_owner, TOKEN_CONTRACT};
 use cosmwasm_std::{DepsMut, MessageInfo, Response, StdError, Addr};
 
 
@@ -24,6 +25,21 @@ pub fn update_swap_denom(
         .add_attribute("owner", info.sender))
 }
 
+pub fn update_token_contract(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_contract: Addr,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+    if config.hub_contract != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Std(StdError::generic_err("unauthorized")));
+    }
+    TOKEN_CONTRACT.save(deps.storage, &deps.api.addr_canonicalize(token_contract.as_str())?)?;
+    Ok(Response::new()
+        .add_attribute("action", "update_token_contract")
+        .add_attribute("token_contract", token_contract))
+}
+
 pub fn udpate_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -42,7 +58,9 @@ pub fn udpate_config(
     }
 
     if let Some(hub_contract) = hub_contract {
-        config.hub_contract = deps.api.addr_canonicalize(hub_contract.as_str())?
+        config.hub_contract = deps.api.addr_canonicalize(hub_contract.as_str())?;
+        // the token is resolved again from the new hub
+        TOKEN_CONTRACT.remove(deps.storage);
     }
 
     if let Some(reward_denom) = reward_denom {
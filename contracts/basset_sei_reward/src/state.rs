@@ -24,6 +24,9 @@ pub const STATE: Item<State> = Item::new("\u{0}\u{5}state");
 pub const CONFIG: Item<Config> = Item::new("\u{0}\u{6}config");
 pub const HOLDERS: Map<&[u8], Holder> = Map::new("holders");
 pub const NEWOWNER: Item<NewOwnerAddr> = Item::new("\u{0}\u{8}newowner");
+/// The bSei token allowed to change the balances.
+/// Resolved from the hub on the first balance change, then pushed by the hub
+pub const TOKEN_CONTRACT: Item<CanonicalAddr> = Item::new("token_contract");
 
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::Cell;
 use std::collections::HashMap;
 
 use basset::common::{QueryTaxWrapper, TaxRateResponse, QueryTaxMsg, TaxCapResponse};
@@ -52,6 +53,7 @@ pub struct WasmMockQuerier {
     token_querier: TokenQuerier,
     oracle_price_querier: OraclePriceQuerier,
     collateral_querier: CollateralQuerier,
+    wasm_queries: Cell<u64>,
 }
 
 
@@ -216,6 +218,9 @@ impl Querier for WasmMockQuerier {
 
 impl WasmMockQuerier {
     pub fn handle_query(&self, request: &QueryRequest<QueryTaxWrapper>) -> QuerierResult {
+        if let QueryRequest::Wasm(_) = request {
+            self.wasm_queries.set(self.wasm_queries.get() + 1);
+        }
         match &request {
             QueryRequest::Custom(QueryTaxWrapper { query_data }) => {
                 match query_data {
@@ -256,6 +261,8 @@ impl WasmMockQuerier {
                         bsei_token_contract: Some(String::from(MOCK_TOKEN_CONTRACT_ADDR)),
                         airdrop_registry_contract: Some(String::from("airdrop")),
                         stsei_token_contract: Some(String::from(MOCK_STSEI_TOKEN_CONTRACT_ADDR)),
+                        insurance_fund_contract: None,
+                        rewards_contract: Some(String::from(MOCK_CONTRACT_ADDR)),
 
                         token_contract: Some(String::from(MOCK_TOKEN_CONTRACT_ADDR)),
                     };
@@ -324,9 +331,15 @@ impl WasmMockQuerier {
             token_querier: TokenQuerier::default(),
             oracle_price_querier: OraclePriceQuerier::default(),
             collateral_querier:CollateralQuerier::default(),
+            wasm_queries: Cell::new(0),
          }
     }
 
+    /// The number of contract queries handled so far
+    pub fn wasm_queries(&self) -> u64 {
+        self.wasm_queries.get()
+    }
+
     // configure the mint whitelist mock basset
     pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
         self.token_querier = TokenQuerier::new(balances);
@@ -290,6 +290,49 @@ fn increase_balance() {
     );
 }
 
+#[test]
+fn cached_token_contract() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), default_init()).unwrap();
+
+    let msg = ExecuteMsg::IncreaseBalance {
+        address: String::from("addr0000"),
+        amount: Uint128::from(100u128),
+    };
+
+    // the token contract is queried from the hub on the first balance change only
+    let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(deps.querier.wasm_queries(), 1);
+    execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    let decrease = ExecuteMsg::DecreaseBalance {
+        address: String::from("addr0000"),
+        amount: Uint128::from(50u128),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), decrease).unwrap();
+    assert_eq!(deps.querier.wasm_queries(), 1);
+
+    // only the hub pushes a new token contract
+    let update = ExecuteMsg::UpdateTokenContract {
+        token_contract: String::from("new_token"),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner1", &[]), update.clone());
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::Std(StdError::generic_err("unauthorized"))
+    );
+    let hub_info = mock_info(MOCK_HUB_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), hub_info, update).unwrap();
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::Std(StdError::generic_err("unauthorized"))
+    );
+    execute(deps.as_mut(), mock_env(), mock_info("new_token", &[]), msg).unwrap();
+    assert_eq!(deps.querier.wasm_queries(), 1);
+}
+
 #[test]
 fn increase_balance_with_decimals() {
     let mut deps = mock_dependencies(&[Coin {
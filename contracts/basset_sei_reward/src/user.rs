@@ -16,13 +16,13 @@ use crate::error::ContractError;
 use crate::querier::query_token_contract_address;
 use crate::state::{
     read_config, read_holder, read_holders, read_state, store_holder, store_state, Config, Holder,
-    State,
+    State, TOKEN_CONTRACT,
 };
 use basset::reward::{AccruedRewardsResponse, HolderResponse, HoldersResponse};
 
 use cosmwasm_std::{
-    attr, BankMsg, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, Uint128,
+    attr, BankMsg, CanonicalAddr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128,
 };
 
 use crate::math::{
@@ -90,23 +90,16 @@ pub fn execute_claim_rewards(
 }
 
 pub fn execute_increase_balance(
-    deps: DepsMut,
+    mut deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     address: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let config = read_config(deps.storage)?;
-    let owner_human = deps.api.addr_humanize(&config.hub_contract)?;
     let address_raw = deps.api.addr_canonicalize(&address)?;
-    let sender = info.sender;
-
-    let token_address = deps
-        .api
-        .addr_humanize(&query_token_contract_address(deps.as_ref(), owner_human)?)?;
 
     // Check sender is token contract
-    if sender != token_address {
+    if token_contract(&mut deps)? != deps.api.addr_canonicalize(info.sender.as_str())? {
         return Err(ContractError::Std(StdError::generic_err("unauthorized")));
     }
 
@@ -135,20 +128,16 @@ pub fn execute_increase_balance(
 }
 
 pub fn execute_decrease_balance(
-    deps: DepsMut,
+    mut deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     address: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let config = read_config(deps.storage)?;
-    let hub_contract = deps.api.addr_humanize(&config.hub_contract)?;
     let address_raw = deps.api.addr_canonicalize(&address)?;
 
     // Check sender is token contract
-    if query_token_contract_address(deps.as_ref(), hub_contract)?
-        != deps.api.addr_canonicalize(info.sender.as_str())?
-    {
+    if token_contract(&mut deps)? != deps.api.addr_canonicalize(info.sender.as_str())? {
         return Err(ContractError::Std(StdError::generic_err("unauthorized")));
     }
 
@@ -222,6 +211,17 @@ pub fn query_holders(
     Ok(HoldersResponse { holders })
 }
 
+// the bSei token, which is only queried from the hub until it has been cached.
+fn token_contract(deps: &mut DepsMut) -> StdResult<CanonicalAddr> {
+    if let Some(token_contract) = TOKEN_CONTRACT.may_load(deps.storage)? {
+        return Ok(token_contract);
+    }
+    let hub_contract = deps.api.addr_humanize(&read_config(deps.storage)?.hub_contract)?;
+    let token_contract = query_token_contract_address(deps.as_ref(), hub_contract)?;
+    TOKEN_CONTRACT.save(deps.storage, &token_contract)?;
+    Ok(token_contract)
+}
+
 // calculate the reward based on the sender's index and the global index.
 fn calculate_decimal_rewards(
     global_index: Decimal,
@@ -17,7 +17,7 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use basset::dispatcher::{ConfigResponse, NewOwnerResponse};
+use basset::dispatcher::{ConfigResponse, NewOwnerResponse, RewardRoutesResponse};
 use basset_sei_rewards_dispatcher::msg::{
     ExecuteMsg, GetBufferedRewardsResponse, InstantiateMsg, QueryMsg,
 };
@@ -34,4 +34,5 @@ fn main() {
     export_schema(&schema_for!(GetBufferedRewardsResponse), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
     export_schema(&schema_for!(NewOwnerResponse), &out_dir);
+    export_schema(&schema_for!(RewardRoutesResponse), &out_dir);
 }
@@ -15,18 +15,25 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
-use crate::handler::{update_oracle_contract, update_swap_contract, update_swap_denom};
+use crate::handler::{
+    query_reward_routes, update_exchange_rate_source, update_oracle_config, update_oracle_contract,
+    update_reward_routes, update_swap_contract, update_swap_denom,
+};
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::state::{
     read_config, read_new_owner, store_config, store_new_owner, Config, NewOwnerAddr, CONFIG,
+    REWARD_ROUTES,
+};
+use basset::dispatcher::{
+    ConfigResponse, ExchangeRateSource, NewOwnerResponse, RewardAction, RewardRoute,
 };
-use basset::dispatcher::{ConfigResponse, NewOwnerResponse};
 use basset::hub::ExecuteMsg::{BondRewards, UpdateGlobalIndex};
-use basset::oracle_pyth::QueryMsg as PythOracleQueryMsg;
+use basset::oracle::{query_exchange_rate, OracleConfig, OracleSource};
 use basset::swap_ext::{Asset, AssetInfo, SimulationResponse, SwapExecteMsg, SwapQueryMsg};
 use cosmwasm_std::{
     attr, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, Fraction,
-    MessageInfo, QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+    MessageInfo, Order, QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg,
+    WasmQuery,
 };
 use std::ops::Mul;
 
@@ -48,6 +55,8 @@ pub fn instantiate(
         swap_contract: deps.api.addr_canonicalize(&msg.swap_contract)?,
         swap_denoms: msg.swap_denoms,
         oracle_contract: deps.api.addr_canonicalize(&msg.oracle_contract)?,
+        exchange_rate_source: None,
+        oracle: None,
     };
 
     if msg.krp_keeper_rate > Decimal::one() {
@@ -114,6 +123,13 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ExecuteMsg::UpdateOracleContract { oracle_contract } => {
             update_oracle_contract(deps, info, oracle_contract)
         }
+        ExecuteMsg::UpdateExchangeRateSource {
+            exchange_rate_source,
+        } => update_exchange_rate_source(deps, info, exchange_rate_source),
+        ExecuteMsg::UpdateOracleConfig { oracle } => update_oracle_config(deps, info, oracle),
+        ExecuteMsg::UpdateRewardRoutes { denom, routes } => {
+            update_reward_routes(deps, info, denom, routes)
+        }
     }
 }
 
@@ -235,7 +251,6 @@ pub fn execute_swap(
     let config = read_config(deps.storage)?;
     let hub_addr = deps.api.addr_humanize(&config.hub_contract)?;
     let swap_addr = deps.api.addr_humanize(&config.swap_contract)?;
-    let oracle_addr = deps.api.addr_humanize(&config.oracle_contract)?;
 
     if info.sender != hub_addr {
         return Err(StdError::generic_err("unauthorized"));
@@ -254,12 +269,24 @@ pub fn execute_swap(
             None,
         )?;
 
-    let (sei_2_ust_rewards_xchg_rate, ust_2_sei_rewards_xchg_rate) = get_exchange_rates(
+    let exchange_rates = get_exchange_rates(
         &deps,
-        &oracle_addr,
+        env.block.time.seconds(),
+        &config,
         config.stsei_reward_denom.as_str(),
         config.bsei_reward_denom.as_str(),
-    )?;
+    );
+    // a bad price must not be used, the rewards are left as they are until the next swap
+    let (sei_2_ust_rewards_xchg_rate, ust_2_sei_rewards_xchg_rate) = match exchange_rates {
+        Ok(rates) => rates,
+        Err(err) => {
+            return Ok(Response::new().add_messages(msgs).add_attributes(vec![
+                attr("action", "swap"),
+                attr("initial_balance", format!("{:?}", balance)),
+                attr("swap_skipped", err.to_string()),
+            ]));
+        }
+    };
 
     let (offer_coin, ask_denom) = get_swap_info(
         config,
@@ -336,6 +363,11 @@ pub(crate) fn convert_to_target_denoms(
             continue;
         }
 
+        // the other routed denoms are dispatched as they are
+        if REWARD_ROUTES.has(deps.storage, &coin.denom) {
+            continue;
+        }
+
         if !coin.amount.is_zero() {
             let simulation_response = query_swap_simulation(
                 &deps,
@@ -411,18 +443,24 @@ pub(crate) fn create_swap_msg(
 
 pub(crate) fn get_exchange_rates(
     deps: &DepsMut,
-    oracle_addr: &Addr,
+    now: u64,
+    config: &Config,
     denom_a: &str,
     denom_b: &str,
 ) -> StdResult<(Decimal, Decimal)> {
-    let querier = &deps.querier;
-    let a_2_b_xchg_rate: Decimal = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: oracle_addr.to_string(),
-        msg: to_json_binary(&PythOracleQueryMsg::QueryExchangeRateByAssetLabel {
-            base_label: denom_a.to_string(),
-            quote_label: denom_b.to_string(),
-        })?,
-    }))?;
+    let oracle = match &config.oracle {
+        Some(oracle) => oracle.clone(),
+        None => {
+            let source = match config.exchange_rate_source.clone().unwrap_or_default() {
+                ExchangeRateSource::Pyth => OracleSource::PythAdapter {
+                    contract: deps.api.addr_humanize(&config.oracle_contract)?.to_string(),
+                },
+                ExchangeRateSource::SeiOracle => OracleSource::SeiNative {},
+            };
+            OracleConfig::single(source)
+        }
+    };
+    let a_2_b_xchg_rate = query_exchange_rate(&deps.querier, &oracle, denom_a, denom_b, now)?;
 
     Ok((
         a_2_b_xchg_rate.clone(),
@@ -485,73 +523,58 @@ pub fn execute_dispatch_rewards(deps: DepsMut, env: Env, info: MessageInfo) -> S
     }
 
     let contr_addr = env.contract.address;
-    let stsei_rewards = deps
-        .querier
-        .query_balance(contr_addr.clone(), config.stsei_reward_denom.as_str())?;
-
     let bsei_reward_addr = deps.api.addr_humanize(&config.bsei_reward_contract)?;
-    let bsei_rewards = deps
-        .querier
-        .query_balance(contr_addr, config.bsei_reward_denom.as_str())?;
-
-    let mut messages: Vec<CosmosMsg> = vec![];
-    if !bsei_rewards.amount.is_zero() {
-        let keeper_rewards = bsei_rewards.amount * config.krp_keeper_rate;
-
-        messages.push(
-            BankMsg::Send {
-                to_address: deps
-                    .api
-                    .addr_humanize(&config.krp_keeper_address)?
-                    .to_string(),
-                amount: vec![Coin {
-                    denom: config.bsei_reward_denom.clone(),
-                    amount: keeper_rewards,
-                }],
-            }
-            .into(),
-        );
+    let swap_addr = deps.api.addr_humanize(&config.swap_contract)?;
+    let krp_keeper_addr = deps.api.addr_humanize(&config.krp_keeper_address)?;
 
-        messages.push(
-            BankMsg::Send {
-                to_address: bsei_reward_addr.to_string(),
-                amount: vec![Coin {
-                    denom: config.bsei_reward_denom.clone(),
-                    amount: bsei_rewards.amount - keeper_rewards,
-                }],
-            }
-            .into(),
-        );
+    // the reward denoms first, then the other routed denoms
+    let mut denoms = vec![
+        config.bsei_reward_denom.clone(),
+        config.stsei_reward_denom.clone(),
+    ];
+    for denom in REWARD_ROUTES.keys(deps.storage, None, None, Order::Ascending) {
+        let denom = denom?;
+        if !denoms.contains(&denom) {
+            denoms.push(denom);
+        }
     }
 
-    if !stsei_rewards.amount.is_zero() {
-        let keeper_rewards = stsei_rewards.amount * config.krp_keeper_rate;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut bsei_rewards = Coin::new(0, config.bsei_reward_denom.as_str());
+    for denom in denoms {
+        let rewards = deps.querier.query_balance(contr_addr.clone(), denom.as_str())?;
+        if denom == config.bsei_reward_denom {
+            bsei_rewards = rewards.clone();
+        }
+        if rewards.amount.is_zero() {
+            continue;
+        }
 
+        let keeper_rewards = rewards.amount * config.krp_keeper_rate;
         messages.push(
             BankMsg::Send {
-                to_address: deps
-                    .api
-                    .addr_humanize(&config.krp_keeper_address)?
-                    .to_string(),
+                to_address: krp_keeper_addr.to_string(),
                 amount: vec![Coin {
-                    denom: config.stsei_reward_denom.clone(),
+                    denom: denom.clone(),
                     amount: keeper_rewards,
                 }],
             }
             .into(),
         );
 
-        let rebond_rewards = stsei_rewards.amount.checked_sub(keeper_rewards)?;
-        if !rebond_rewards.is_zero() {
-            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: hub_addr.to_string(),
-                msg: to_json_binary(&BondRewards {}).unwrap(),
-                funds: vec![Coin {
-                    denom: config.stsei_reward_denom.clone(),
-                    amount: rebond_rewards,
-                }],
-            }));
-        }
+        let routes = match REWARD_ROUTES.may_load(deps.storage, &denom)? {
+            Some(routes) => routes,
+            None => default_reward_routes(&config, &bsei_reward_addr, &denom),
+        };
+        messages.extend(reward_route_messages(
+            &hub_addr,
+            &swap_addr,
+            Coin {
+                denom,
+                amount: rewards.amount.checked_sub(keeper_rewards)?,
+            },
+            &routes,
+        )?);
     }
 
     messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
@@ -570,6 +593,75 @@ pub fn execute_dispatch_rewards(deps: DepsMut, env: Env, info: MessageInfo) -> S
     ]))
 }
 
+/// The bSei reward denom goes to the bSei reward contract, the stSei reward denom is rebonded.
+fn default_reward_routes(
+    config: &Config,
+    bsei_reward_addr: &Addr,
+    denom: &str,
+) -> Vec<RewardRoute> {
+    let action = if denom == config.bsei_reward_denom {
+        RewardAction::Send {
+            recipient: bsei_reward_addr.to_string(),
+        }
+    } else {
+        RewardAction::Rebond {}
+    };
+    vec![RewardRoute {
+        share: Decimal::one(),
+        action,
+    }]
+}
+
+/// Split the rewards between the routes, the last one taking the rounding leftover.
+fn reward_route_messages(
+    hub_addr: &Addr,
+    swap_addr: &Addr,
+    rewards: Coin,
+    routes: &[RewardRoute],
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut left = rewards.amount;
+    for (i, route) in routes.iter().enumerate() {
+        let amount = if i + 1 == routes.len() {
+            left
+        } else {
+            rewards.amount * route.share
+        };
+        left = left.checked_sub(amount)?;
+        if amount.is_zero() {
+            continue;
+        }
+
+        let coin = Coin {
+            denom: rewards.denom.clone(),
+            amount,
+        };
+        messages.push(match &route.action {
+            RewardAction::Send { recipient } => BankMsg::Send {
+                to_address: recipient.clone(),
+                amount: vec![coin],
+            }
+            .into(),
+            RewardAction::Rebond {} => CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: hub_addr.to_string(),
+                msg: to_json_binary(&BondRewards {})?,
+                funds: vec![coin],
+            }),
+            RewardAction::SwapThenSend {
+                ask_denom,
+                recipient,
+            } => create_swap_msg(
+                coin,
+                ask_denom.clone(),
+                swap_addr.to_string(),
+                Some(recipient.clone()),
+            )?,
+            RewardAction::Burn {} => BankMsg::Burn { amount: vec![coin] }.into(),
+        });
+    }
+    Ok(messages)
+}
+
 fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = read_config(deps.storage)?;
     Ok(ConfigResponse {
@@ -589,6 +681,8 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         swap_contract: deps.api.addr_humanize(&config.swap_contract)?.to_string(),
         swap_denoms: config.swap_denoms,
         oracle_contract: deps.api.addr_humanize(&config.oracle_contract)?.to_string(),
+        exchange_rate_source: Some(config.exchange_rate_source.unwrap_or_default()),
+        oracle: config.oracle,
     })
 }
 
@@ -597,6 +691,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
         QueryMsg::NewOwner {} => to_json_binary(&query_new_owner(deps)?),
+        QueryMsg::RewardRoutes {} => to_json_binary(&query_reward_routes(deps)?),
         // QueryMsg::GetBufferedRewards {} => unimplemented!(),
     }
 }
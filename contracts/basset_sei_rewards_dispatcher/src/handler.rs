@@ -1,5 +1,9 @@
-use cosmwasm_std::{DepsMut, MessageInfo, Response, StdError, StdResult};
-use crate::state::{read_config, store_config};
+use basset::dispatcher::{
+    DenomRewardRoutes, ExchangeRateSource, RewardAction, RewardRoute, RewardRoutesResponse,
+};
+use basset::oracle::OracleConfig;
+use cosmwasm_std::{Decimal, Deps, DepsMut, MessageInfo, Order, Response, StdError, StdResult};
+use crate::state::{read_config, store_config, Config, REWARD_ROUTES};
 
 pub fn update_swap_contract(
     deps: DepsMut,
@@ -55,4 +59,124 @@ pub fn update_oracle_contract(
         .add_attribute("action", "update_oracle_contract")
         .add_attribute("oracle_contract", oracle_contract)
         .add_attribute("owner", info.sender))
-}
\ No newline at end of file
+}
+pub fn update_exchange_rate_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    exchange_rate_source: ExchangeRateSource,
+) -> StdResult<Response> {
+    let mut config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+    let attr_source = format!("{:?}", exchange_rate_source);
+    config.exchange_rate_source = Some(exchange_rate_source);
+    store_config(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("action", "update_exchange_rate_source")
+        .add_attribute("exchange_rate_source", attr_source)
+        .add_attribute("owner", info.sender))
+}
+
+pub fn update_oracle_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    oracle: Option<OracleConfig>,
+) -> StdResult<Response> {
+    let mut config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+    if let Some(oracle) = &oracle {
+        oracle.validate(deps.api)?;
+    }
+    let attr_oracle = format!("{:?}", oracle);
+    config.oracle = oracle;
+    store_config(deps.storage, &config)?;
+    Ok(Response::new()
+        .add_attribute("action", "update_oracle_config")
+        .add_attribute("oracle", attr_oracle)
+        .add_attribute("owner", info.sender))
+}
+
+pub fn update_reward_routes(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    routes: Option<Vec<RewardRoute>>,
+) -> StdResult<Response> {
+    let config = read_config(deps.storage)?;
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+    let attr_routes = format!("{:?}", routes);
+    match routes {
+        Some(routes) => {
+            validate_reward_routes(deps.as_ref(), &config, &denom, &routes)?;
+            REWARD_ROUTES.save(deps.storage, &denom, &routes)?;
+        }
+        None => REWARD_ROUTES.remove(deps.storage, &denom),
+    }
+    Ok(Response::new()
+        .add_attribute("action", "update_reward_routes")
+        .add_attribute("denom", denom)
+        .add_attribute("routes", attr_routes)
+        .add_attribute("owner", info.sender))
+}
+
+pub fn query_reward_routes(deps: Deps) -> StdResult<RewardRoutesResponse> {
+    let routes = REWARD_ROUTES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (denom, routes) = item?;
+            Ok(DenomRewardRoutes { denom, routes })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(RewardRoutesResponse { routes })
+}
+
+fn validate_reward_routes(
+    deps: Deps,
+    config: &Config,
+    denom: &str,
+    routes: &[RewardRoute],
+) -> StdResult<()> {
+    if routes.is_empty() {
+        return Err(StdError::generic_err("At least one reward route is required"));
+    }
+    let mut total_share = Decimal::zero();
+    for route in routes {
+        if route.share.is_zero() {
+            return Err(StdError::generic_err("The share of a reward route cannot be zero"));
+        }
+        total_share += route.share;
+        match &route.action {
+            RewardAction::Send { recipient } => {
+                deps.api.addr_validate(recipient)?;
+            }
+            RewardAction::Rebond {} => {
+                if denom != config.stsei_reward_denom {
+                    return Err(StdError::generic_err(
+                        "Only the stSei reward denom can be rebonded",
+                    ));
+                }
+            }
+            RewardAction::SwapThenSend {
+                ask_denom,
+                recipient,
+            } => {
+                if ask_denom == denom {
+                    return Err(StdError::generic_err(
+                        "A reward route cannot swap to the same denom",
+                    ));
+                }
+                deps.api.addr_validate(recipient)?;
+            }
+            RewardAction::Burn {} => {}
+        }
+    }
+    if total_share != Decimal::one() {
+        return Err(StdError::generic_err("The shares of the reward routes must add up to 1"));
+    }
+    Ok(())
+}
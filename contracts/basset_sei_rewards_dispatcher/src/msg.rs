@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use basset::dispatcher::{ExchangeRateSource, RewardRoute};
+use basset::oracle::OracleConfig;
 use cosmwasm_std::{Decimal, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -59,7 +61,20 @@ pub enum ExecuteMsg {
     },
     UpdateOracleContract{
         oracle_contract: String,
-    }
+    },
+    UpdateExchangeRateSource {
+        exchange_rate_source: ExchangeRateSource,
+    },
+    /// Sets the oracle sources and price checks, `None` goes back to the legacy oracle
+    UpdateOracleConfig {
+        oracle: Option<OracleConfig>,
+    },
+    /// Sets how the rewards of `denom` are dispatched, with shares adding up to 1.
+    /// `None` goes back to the default destination of the denom
+    UpdateRewardRoutes {
+        denom: String,
+        routes: Option<Vec<RewardRoute>>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -70,6 +85,8 @@ pub enum QueryMsg {
     // Config returns config
     Config {},
     NewOwner {},
+    /// Return type: RewardRoutesResponse.
+    RewardRoutes {},
 }
 
 // We define a custom struct for each query response
@@ -15,12 +15,17 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use basset::dispatcher::{ExchangeRateSource, RewardRoute};
+use basset::oracle::OracleConfig;
 use cosmwasm_std::{CanonicalAddr, Decimal, StdResult, Storage};
 
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
 
 pub static CONFIG: Item<Config> = Item::new("config");
 pub static NEWOWNERADDR: Item<NewOwnerAddr> = Item::new("newowneraddr");
+/// How the rewards of each routed denom are dispatched. The bSei reward denom goes to the bSei
+/// reward contract and the stSei reward denom is rebonded unless they are routed.
+pub static REWARD_ROUTES: Map<&str, Vec<RewardRoute>> = Map::new("reward_routes");
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -34,6 +39,9 @@ pub struct Config {
     pub swap_contract: CanonicalAddr,
     pub swap_denoms: Vec<String>,
     pub oracle_contract: CanonicalAddr,
+    pub exchange_rate_source: Option<ExchangeRateSource>,
+    /// Overrides `oracle_contract` and `exchange_rate_source` when set
+    pub oracle: Option<OracleConfig>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -12,12 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use basset::oracle_pyth::QueryMsg as PythOracleQueryMsg;
+use basset::oracle_pyth::{
+    PriceFeed, PriceFeedQueryMsg, PriceFeedResponse, PythPrice, QueryMsg as PythOracleQueryMsg,
+};
 use basset::swap_ext::{AssetInfo, SimulationResponse, SwapQueryMsg};
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary,from_json, to_json_binary, Coin, ContractResult, Decimal, Empty, OwnedDeps, Querier,
-    QuerierResult, QueryRequest, SystemError, Uint128, WasmQuery,
+    from_binary,from_json, to_json_binary, Coin, ContractResult, Decimal, OwnedDeps, Querier,
+    Int64, QuerierResult, QueryRequest, SystemError, Uint128, Uint64, WasmQuery,
+};
+use sei_cosmwasm::{
+    DenomOracleExchangeRatePair, ExchangeRatesResponse as SeiExchangeRatesResponse,
+    OracleExchangeRate, SeiQuery, SeiQueryWrapper,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -27,6 +33,9 @@ pub const MOCK_BSEI_REWARD_CONTRACT_ADDR: &str = "rewards";
 pub const MOCK_KRP_KEEPER_CONTRACT_ADDR: &str = "krp_keeper";
 pub const MOCK_SWAP_CONTRACT_ADDR: &str = "swap";
 pub const MOCK_ORACLE_CONTRACT_ADDR: &str = "oracle";
+pub const MOCK_PYTH_CONTRACT_ADDR: &str = "pyth";
+/// The publish time of the fresh pyth prices, the block time of `mock_env`
+pub const MOCK_PYTH_PUBLISH_TIME: i64 = 1_571_797_419;
 pub const BTOKEN_REWARD_DENOM: &str = "kusd";
 pub const STTOKEN_REWARD_DENOM: &str = "usei";
 
@@ -45,13 +54,13 @@ pub fn mock_dependencies(
 }
 
 pub struct WasmMockQuerier {
-    base: MockQuerier<Empty>,
+    base: MockQuerier<SeiQueryWrapper>,
 }
 
 impl Querier for WasmMockQuerier {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
         // MockQuerier doesn't support Custom, so we ignore it completely here
-        let request: QueryRequest<Empty> = match from_json(bin_request) {
+        let request: QueryRequest<SeiQueryWrapper> = match from_json(bin_request) {
             Ok(v) => v,
             Err(e) => {
                 return QuerierResult::Err(SystemError::InvalidRequest {
@@ -65,9 +74,30 @@ impl Querier for WasmMockQuerier {
 }
 
 impl WasmMockQuerier {
-    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+    pub fn handle_query(&self, request: &QueryRequest<SeiQueryWrapper>) -> QuerierResult {
         let (sei_denom, usd_denom) = ("usei", "kusd");
         match &request {
+            QueryRequest::Custom(SeiQueryWrapper { query_data, .. }) => match query_data {
+                SeiQuery::ExchangeRates {} => {
+                    // 1usei = 32kusd, both quoted in usd
+                    let pair = |denom: &str, rate: u64| DenomOracleExchangeRatePair {
+                        denom: denom.to_string(),
+                        oracle_exchange_rate: OracleExchangeRate {
+                            exchange_rate: Decimal::from_ratio(rate, 1000u64),
+                            last_update: Uint64::new(1),
+                            last_update_timestamp: 1,
+                        },
+                    };
+                    let res = SeiExchangeRatesResponse {
+                        denom_oracle_exchange_rate_pairs: vec![
+                            pair(sei_denom, 32000),
+                            pair(usd_denom, 1000),
+                        ],
+                    };
+                    QuerierResult::Ok(ContractResult::from(to_json_binary(&res)))
+                }
+                _ => unimplemented!(),
+            },
             QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
                 if *contract_addr == MOCK_SWAP_CONTRACT_ADDR {
                     match from_binary(msg).unwrap() {
@@ -147,6 +177,31 @@ impl WasmMockQuerier {
                             }
                         }
                     }
+                } else if *contract_addr == MOCK_PYTH_CONTRACT_ADDR {
+                    let PriceFeedQueryMsg::PriceFeed { id } = from_json(msg).unwrap();
+                    // usei = 32usd and kusd = 1usd with a 0.1% confidence interval,
+                    // "stale_usei" was published long ago and "wide_usei" is unsure
+                    let price = |price: i64, conf: u64, publish_time: i64| PythPrice {
+                        price: Int64::new(price),
+                        conf: Uint64::new(conf),
+                        expo: -6,
+                        publish_time,
+                    };
+                    let price = match id.as_str() {
+                        "usei" => price(32_000_000, 32_000, MOCK_PYTH_PUBLISH_TIME),
+                        "kusd" => price(1_000_000, 1_000, MOCK_PYTH_PUBLISH_TIME),
+                        "stale_usei" => price(32_000_000, 32_000, 0),
+                        "wide_usei" => price(32_000_000, 3_200_000, MOCK_PYTH_PUBLISH_TIME),
+                        _ => panic!("UNSUPPORTED PRICE FEED: {}", id),
+                    };
+                    let res = PriceFeedResponse {
+                        price_feed: PriceFeed {
+                            id,
+                            ema_price: price.clone(),
+                            price,
+                        },
+                    };
+                    QuerierResult::Ok(ContractResult::from(to_json_binary(&res)))
                 } else {
                     unimplemented!()
                 }
@@ -161,7 +216,7 @@ impl WasmMockQuerier {
 }
 
 impl WasmMockQuerier {
-    pub fn new(base: MockQuerier<Empty>) -> Self {
+    pub fn new(base: MockQuerier<SeiQueryWrapper>) -> Self {
         WasmMockQuerier { base }
     }
 }
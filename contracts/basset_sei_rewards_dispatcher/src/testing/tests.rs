@@ -32,15 +32,26 @@
 //! 4. Anywhere you see query(&deps, ...) you must replace it with query(deps.as_mut(), ...)
 
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{coins, Api, Coin, Decimal, StdError, Uint128};
+use basset::dispatcher::{
+    ConfigResponse, DenomRewardRoutes, ExchangeRateSource, RewardAction, RewardRoute,
+    RewardRoutesResponse,
+};
+use basset::hub::ExecuteMsg::{BondRewards, UpdateGlobalIndex};
+use basset::swap_ext::SwapExecteMsg;
+use basset::oracle::{OracleConfig, OracleSource};
+use cosmwasm_std::{
+    attr, coins, from_json, to_json_binary, Api, BankMsg, Coin, CosmosMsg, Decimal, StdError,
+    SubMsg, Uint128, WasmMsg,
+};
 
-use crate::contract::{accept_ownership, execute, get_swap_info, instantiate};
-use crate::msg::{ExecuteMsg, InstantiateMsg};
+use crate::contract::{accept_ownership, execute, get_swap_info, instantiate, query};
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
 use crate::msg::ExecuteMsg::SetOwner;
 use crate::state::{CONFIG, read_new_owner};
 use crate::testing::mock_querier::{
     mock_dependencies, BTOKEN_REWARD_DENOM, MOCK_BSEI_REWARD_CONTRACT_ADDR, MOCK_HUB_CONTRACT_ADDR,
-    MOCK_KRP_KEEPER_CONTRACT_ADDR, MOCK_ORACLE_CONTRACT_ADDR, MOCK_SWAP_CONTRACT_ADDR,
+    MOCK_KRP_KEEPER_CONTRACT_ADDR, MOCK_ORACLE_CONTRACT_ADDR, MOCK_PYTH_CONTRACT_ADDR,
+    MOCK_SWAP_CONTRACT_ADDR,
     STTOKEN_REWARD_DENOM,
 };
 
@@ -185,6 +196,128 @@ fn test_swap_to_reward_denom() {
     }
 }
 
+#[test]
+fn test_swap_with_sei_oracle_rates() {
+    let mut deps = mock_dependencies(&[
+        Coin::new(200, "usei"),
+        Coin::new(300, "kusd"),
+        Coin::new(500, "usdr"),
+    ]);
+    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), default_init()).unwrap();
+
+    let msg = ExecuteMsg::UpdateExchangeRateSource {
+        exchange_rate_source: ExchangeRateSource::SeiOracle,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("invalid_owner", &[]), msg.clone());
+    assert_eq!(res.unwrap_err(), StdError::generic_err("Unauthorized"));
+    execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.exchange_rate_source, Some(ExchangeRateSource::SeiOracle));
+
+    // the oracle module gives the same rate as the pyth mock: 1usei = 32kusd
+    let msg = ExecuteMsg::SwapToRewardDenom {
+        stsei_total_bonded: Uint128::from(1u128),
+        bsei_total_bonded: Uint128::from(2u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(MOCK_HUB_CONTRACT_ADDR, &[]), msg)
+        .unwrap();
+    assert!(res.attributes.contains(&attr("sei_2_ust_rewards_xchg_rate", "32")));
+    assert!(res.attributes.contains(&attr("offer_coin_denom", "usei")));
+    assert!(res.attributes.contains(&attr("offer_coin_amount", "120")));
+}
+
+#[test]
+fn test_swap_with_oracle_checks() {
+    let mut deps = mock_dependencies(&[
+        Coin::new(200, "usei"),
+        Coin::new(300, "kusd"),
+        Coin::new(500, "usdr"),
+    ]);
+    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), default_init()).unwrap();
+
+    let pyth = |base: &str| OracleSource::Pyth {
+        contract: MOCK_PYTH_CONTRACT_ADDR.to_string(),
+        base_price_id: base.to_string(),
+        quote_price_id: "kusd".to_string(),
+    };
+    let oracle = |primary: OracleSource, secondary: Option<OracleSource>| OracleConfig {
+        primary,
+        secondary,
+        max_age: Some(60),
+        max_confidence: Some(Decimal::percent(1)),
+        max_deviation: Some(Decimal::percent(10)),
+    };
+    let mut swap = |oracle: Option<OracleConfig>| {
+        let msg = ExecuteMsg::UpdateOracleConfig { oracle };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        let msg = ExecuteMsg::SwapToRewardDenom {
+            stsei_total_bonded: Uint128::from(1u128),
+            bsei_total_bonded: Uint128::from(2u128),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(MOCK_HUB_CONTRACT_ADDR, &[]), msg).unwrap()
+    };
+
+    // a fresh and tight pyth price is used for the swap
+    let res = swap(Some(oracle(pyth("usei"), None)));
+    assert_eq!(res.messages.len(), 2);
+    assert!(res.attributes.contains(&attr("sei_2_ust_rewards_xchg_rate", "32")));
+    assert!(res.attributes.contains(&attr("offer_coin_amount", "120")));
+
+    // a stale price or a wide confidence interval skips the swap,
+    // only the conversion of usdr is sent
+    let res = swap(Some(oracle(pyth("stale_usei"), None)));
+    assert_eq!(res.messages.len(), 1);
+    assert!(res.attributes.contains(&attr(
+        "swap_skipped",
+        "Generic error: oracle price is stale: published at 0"
+    )));
+    let res = swap(Some(oracle(pyth("wide_usei"), None)));
+    assert_eq!(res.messages.len(), 1);
+    assert!(res.attributes.contains(&attr(
+        "swap_skipped",
+        "Generic error: oracle confidence interval 0.101 is too wide"
+    )));
+
+    // the sei oracle mock was updated at the epoch, the secondary source is used
+    let res = swap(Some(oracle(
+        OracleSource::SeiNative {},
+        Some(OracleSource::Fixed {
+            rate: Decimal::from_ratio(32u128, 1u128),
+        }),
+    )));
+    assert_eq!(res.messages.len(), 2);
+    assert!(res.attributes.contains(&attr("offer_coin_amount", "120")));
+
+    // both sources are fine but too far apart
+    let res = swap(Some(oracle(
+        OracleSource::Fixed {
+            rate: Decimal::from_ratio(40u128, 1u128),
+        },
+        Some(pyth("usei")),
+    )));
+    assert_eq!(res.messages.len(), 1);
+    assert!(res
+        .attributes
+        .contains(&attr("swap_skipped", "Generic error: oracle prices deviate by 0.25")));
+
+    // without a config the legacy pyth adapter is used again
+    let res = swap(None);
+    assert_eq!(res.messages.len(), 2);
+    assert!(res.attributes.contains(&attr("sei_2_ust_rewards_xchg_rate", "32")));
+
+    let msg = ExecuteMsg::UpdateOracleConfig {
+        oracle: Some(OracleConfig::single(OracleSource::Fixed {
+            rate: Decimal::zero(),
+        })),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg.clone());
+    assert_eq!(res.unwrap_err(), StdError::generic_err("fixed oracle rate must not be zero"));
+    let res = execute(deps.as_mut(), mock_env(), mock_info("invalid_owner", &[]), msg);
+    assert_eq!(res.unwrap_err(), StdError::generic_err("Unauthorized"));
+}
+
 #[test]
 fn test_dispatch_rewards() {
     let mut deps = mock_dependencies(&[
@@ -263,6 +396,228 @@ fn test_dispatch_rewards_zero_krp_keeper_rate() {
     }
 }
 
+#[test]
+fn test_dispatch_reward_routes() {
+    let mut deps = mock_dependencies(&[
+        Coin::new(200, "usei"),
+        Coin::new(300, "kusd"),
+        Coin::new(20, "usdr"),
+    ]);
+
+    let msg = InstantiateMsg {
+        krp_keeper_rate: Decimal::zero(),
+        ..default_init()
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+    let insurance_fund = "insurance_fund".to_string();
+    let reserve = "reserve".to_string();
+    let stsei_routes = vec![
+        RewardRoute {
+            share: Decimal::percent(90),
+            action: RewardAction::Rebond {},
+        },
+        RewardRoute {
+            share: Decimal::percent(10),
+            action: RewardAction::Send {
+                recipient: insurance_fund.clone(),
+            },
+        },
+    ];
+    let update = ExecuteMsg::UpdateRewardRoutes {
+        denom: STTOKEN_REWARD_DENOM.to_string(),
+        routes: Some(stsei_routes.clone()),
+    };
+
+    // only the owner routes the rewards
+    let res = execute(deps.as_mut(), mock_env(), mock_info("someone", &[]), update.clone());
+    assert_eq!(res.unwrap_err(), StdError::generic_err("Unauthorized"));
+
+    // the shares must add up to 1
+    let mut routes = stsei_routes.clone();
+    routes[1].share = Decimal::percent(20);
+    let msg = ExecuteMsg::UpdateRewardRoutes {
+        denom: STTOKEN_REWARD_DENOM.to_string(),
+        routes: Some(routes),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg);
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("The shares of the reward routes must add up to 1")
+    );
+
+    // only the stSei reward denom is rebonded
+    let msg = ExecuteMsg::UpdateRewardRoutes {
+        denom: BTOKEN_REWARD_DENOM.to_string(),
+        routes: Some(stsei_routes.clone()),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg);
+    assert_eq!(
+        res.unwrap_err(),
+        StdError::generic_err("Only the stSei reward denom can be rebonded")
+    );
+
+    execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), update).unwrap();
+    let usdr_routes = vec![
+        RewardRoute {
+            share: Decimal::percent(50),
+            action: RewardAction::Burn {},
+        },
+        RewardRoute {
+            share: Decimal::percent(50),
+            action: RewardAction::SwapThenSend {
+                ask_denom: STTOKEN_REWARD_DENOM.to_string(),
+                recipient: reserve.clone(),
+            },
+        },
+    ];
+    let msg = ExecuteMsg::UpdateRewardRoutes {
+        denom: "usdr".to_string(),
+        routes: Some(usdr_routes.clone()),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+    let res: RewardRoutesResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::RewardRoutes {}).unwrap()).unwrap();
+    assert_eq!(
+        res.routes,
+        vec![
+            DenomRewardRoutes {
+                denom: "usdr".to_string(),
+                routes: usdr_routes,
+            },
+            DenomRewardRoutes {
+                denom: STTOKEN_REWARD_DENOM.to_string(),
+                routes: stsei_routes,
+            },
+        ]
+    );
+
+    let info = mock_info(MOCK_HUB_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::DispatchRewards {}).unwrap();
+    let keeper_send = |denom: &str| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: MOCK_KRP_KEEPER_CONTRACT_ADDR.to_string(),
+            amount: vec![Coin::new(0, denom)],
+        }
+        .into()
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(keeper_send(BTOKEN_REWARD_DENOM)),
+            SubMsg::new(BankMsg::Send {
+                to_address: MOCK_BSEI_REWARD_CONTRACT_ADDR.to_string(),
+                amount: vec![Coin::new(300, BTOKEN_REWARD_DENOM)],
+            }),
+            SubMsg::new(keeper_send(STTOKEN_REWARD_DENOM)),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: MOCK_HUB_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&BondRewards {}).unwrap(),
+                funds: vec![Coin::new(180, STTOKEN_REWARD_DENOM)],
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: insurance_fund,
+                amount: vec![Coin::new(20, STTOKEN_REWARD_DENOM)],
+            }),
+            SubMsg::new(keeper_send("usdr")),
+            SubMsg::new(BankMsg::Burn {
+                amount: vec![Coin::new(10, "usdr")],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: MOCK_SWAP_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&SwapExecteMsg::SwapDenom {
+                    from_coin: Coin::new(10, "usdr"),
+                    target_denom: STTOKEN_REWARD_DENOM.to_string(),
+                    to_address: Some(reserve),
+                })
+                .unwrap(),
+                funds: vec![Coin::new(10, "usdr")],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: MOCK_BSEI_REWARD_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&UpdateGlobalIndex {
+                    airdrop_hooks: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // without its routes, the stSei reward denom is rebonded again
+    let msg = ExecuteMsg::UpdateRewardRoutes {
+        denom: STTOKEN_REWARD_DENOM.to_string(),
+        routes: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+    let info = mock_info(MOCK_HUB_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::DispatchRewards {}).unwrap();
+    assert_eq!(
+        res.messages[3],
+        SubMsg::new(WasmMsg::Execute {
+            contract_addr: MOCK_HUB_CONTRACT_ADDR.to_string(),
+            msg: to_json_binary(&BondRewards {}).unwrap(),
+            funds: vec![Coin::new(200, STTOKEN_REWARD_DENOM)],
+        })
+    );
+}
+
+#[test]
+fn test_dispatch_insurance_fund_share() {
+    let mut deps = mock_dependencies(&[Coin::new(1001, STTOKEN_REWARD_DENOM)]);
+    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), default_init()).unwrap();
+
+    // the insurance fund is paid in the underlying coin, out of the stSei rewards
+    let insurance_fund = "insurance_fund".to_string();
+    let msg = ExecuteMsg::UpdateRewardRoutes {
+        denom: STTOKEN_REWARD_DENOM.to_string(),
+        routes: Some(vec![
+            RewardRoute {
+                share: Decimal::percent(7),
+                action: RewardAction::Send {
+                    recipient: insurance_fund.clone(),
+                },
+            },
+            RewardRoute {
+                share: Decimal::percent(93),
+                action: RewardAction::Rebond {},
+            },
+        ]),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+    // the keeper fee is taken first, the rebond takes the rounding leftover
+    let info = mock_info(MOCK_HUB_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::DispatchRewards {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(BankMsg::Send {
+                to_address: MOCK_KRP_KEEPER_CONTRACT_ADDR.to_string(),
+                amount: vec![Coin::new(50, STTOKEN_REWARD_DENOM)],
+            }),
+            SubMsg::new(BankMsg::Send {
+                to_address: insurance_fund,
+                amount: vec![Coin::new(66, STTOKEN_REWARD_DENOM)],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: MOCK_HUB_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&BondRewards {}).unwrap(),
+                funds: vec![Coin::new(885, STTOKEN_REWARD_DENOM)],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: MOCK_BSEI_REWARD_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&UpdateGlobalIndex {
+                    airdrop_hooks: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+}
+
 #[test]
 fn test_get_swap_info() {
     let mut deps = mock_dependencies(&[]);
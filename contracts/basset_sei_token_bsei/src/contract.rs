@@ -15,15 +15,19 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+};
 
 use cw20_legacy::allowances::{execute_decrease_allowance, execute_increase_allowance};
 use cw20_legacy::contract::instantiate as cw20_init;
 use cw20_legacy::contract::query as cw20_query;
-use cw20_legacy::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw20_legacy::contract::{execute_update_marketing, execute_upload_logo, instantiate_marketing};
+use cw20_legacy::msg::InstantiateMsg;
+use cw20_legacy::state::MARKETING_INFO;
 
 use crate::handler::*;
-use crate::msg::{MigrateMsg, TokenInitMsg};
+use crate::msg::{ExecuteMsg, MigrateMsg, QueryMsg, TokenInitMsg};
 use crate::state::store_hub_contract;
 use cw20::MinterResponse;
 use cw20_legacy::ContractError;
@@ -53,6 +57,7 @@ pub fn instantiate(
                 minter: msg.hub_contract,
                 cap: None,
             }),
+            marketing: msg.marketing,
         },
     )?;
 
@@ -99,15 +104,47 @@ pub fn execute(
             amount,
             msg,
         } => execute_send_from(deps, env, info, owner, contract, amount, msg),
+        ExecuteMsg::UpdateMarketing {
+            project,
+            description,
+            marketing,
+        } => execute_update_marketing(deps, env, info, project, description, marketing),
+        ExecuteMsg::UploadLogo(logo) => execute_upload_logo(deps, env, info, logo),
+        ExecuteMsg::AddBalanceHook { address } => execute_add_balance_hook(deps, info, address),
+        ExecuteMsg::RemoveBalanceHook { address } => {
+            execute_remove_balance_hook(deps, info, address)
+        }
+        ExecuteMsg::Permit(permit) => execute_permit(deps, env, permit),
+        ExecuteMsg::UpdateRewardContract { reward_contract } => {
+            execute_update_reward_contract(deps, info, reward_contract)
+        }
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+    reply_balance_hook(msg)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    cw20_query(deps, _env, msg)
+    match msg.into_cw20() {
+        Ok(msg) => cw20_query(deps, _env, msg),
+        Err(QueryMsg::PermitNonce { owner }) => to_json_binary(&query_permit_nonce(deps, owner)?),
+        Err(QueryMsg::BalanceHooks {}) => to_json_binary(&query_balance_hooks(deps)?),
+        Err(query) => Err(StdError::generic_err(format!("unsupported query: {:?}", query))),
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    if let Some(marketing) = msg.marketing {
+        if MARKETING_INFO.may_load(deps.storage)?.is_some() {
+            return Err(StdError::generic_err(
+                "marketing info is already set, use update_marketing",
+            ));
+        }
+        instantiate_marketing(deps, marketing)?;
+    }
     Ok(Response::default())
 }
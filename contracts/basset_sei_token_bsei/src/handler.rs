@@ -12,23 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use cosmwasm_std::{
-    to_json_binary, Binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, SubMsg, Uint128, WasmMsg,
-};
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128};
 
-use crate::querier::query_reward_contract;
 use crate::state::read_hub_contract;
-use basset::hub::ExecuteMsg::CheckSlashing;
-use basset::reward::ExecuteMsg::{DecreaseBalance, IncreaseBalance};
+use basset::token::Permit;
 use cw20_legacy::allowances::{
-    execute_burn_from as cw20_burn_from, execute_send_from as cw20_send_from,
-    execute_transfer_from as cw20_transfer_from,
+    execute_burn_from as cw20_burn_from, execute_increase_allowance as cw20_increase_allowance,
+    execute_send_from as cw20_send_from, execute_transfer_from as cw20_transfer_from,
 };
 use cw20_legacy::contract::{
     execute_burn as cw20_burn, execute_mint as cw20_mint, execute_send as cw20_send,
     execute_transfer as cw20_transfer,
 };
 use cw20_legacy::ContractError;
+use lst_token::handler as lst;
+use lst_token::token::{LstToken, TokenKind};
+
+pub use lst_token::handler::{query_balance_hooks, query_permit_nonce, reply_balance_hook};
 
 pub fn execute_transfer(
     deps: DepsMut,
@@ -37,33 +37,11 @@ pub fn execute_transfer(
     recipient: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
+    let token = bsei_token(deps.as_ref())?;
     let sender = info.sender.clone();
-    let reward_contract = query_reward_contract(&deps)?;
-
-    let rcpt_addr = deps.api.addr_validate(&recipient)?;
-
-    let res: Response = cw20_transfer(deps, env, info, recipient, amount)?;
-    let messages = vec![
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: reward_contract.to_string(),
-            msg: to_json_binary(&DecreaseBalance {
-                address: sender.to_string(),
-                amount,
-            })?,
-            funds: vec![],
-        })),
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: reward_contract.to_string(),
-            msg: to_json_binary(&IncreaseBalance {
-                address: rcpt_addr.to_string(),
-                amount,
-            })?,
-            funds: vec![],
-        })),
-    ];
-    Ok(Response::new()
-        .add_submessages(messages)
-        .add_attributes(res.attributes))
+    lst::execute_transfer(deps, &token, &sender, &recipient, amount, |deps| {
+        cw20_transfer(deps, env, info, recipient.clone(), amount)
+    })
 }
 
 pub fn execute_burn(
@@ -72,26 +50,11 @@ pub fn execute_burn(
     info: MessageInfo,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
+    let token = bsei_token(deps.as_ref())?;
     let sender = info.sender.clone();
-    let reward_contract = query_reward_contract(&deps)?;
-    let hub_contract = deps.api.addr_humanize(&read_hub_contract(deps.storage)?)?;
-
-    if sender != hub_contract {
-        return Err(ContractError::Unauthorized {});
-    }
-    let res: Response = cw20_burn(deps, env, info, amount)?;
-    let messages = vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: reward_contract.to_string(),
-        msg: to_json_binary(&DecreaseBalance {
-            address: sender.to_string(),
-            amount,
-        })?,
-        funds: vec![],
-    }))];
- 
-    Ok(Response::new()
-        .add_submessages(messages)
-        .add_attributes(res.attributes))
+    lst::execute_burn(deps, &token, &sender, amount, |deps| {
+        cw20_burn(deps, env, info, amount)
+    })
 }
 
 pub fn execute_mint(
@@ -101,19 +64,11 @@ pub fn execute_mint(
     recipient: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let reward_contract = query_reward_contract(&deps)?;
-
-    let res: Response = cw20_mint(deps, env, info, recipient.clone(), amount)?;
-    Ok(Response::new()
-        .add_submessages(vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: reward_contract.to_string(),
-            msg: to_json_binary(&IncreaseBalance {
-                address: recipient,
-                amount,
-            })?,
-            funds: vec![],
-        }))])
-        .add_attributes(res.attributes))
+    let token = bsei_token(deps.as_ref())?;
+    let sender = info.sender.clone();
+    lst::execute_mint(deps, &token, &sender, &recipient, amount, |deps| {
+        cw20_mint(deps, env, info, recipient.clone(), amount)
+    })
 }
 
 pub fn execute_send(
@@ -124,36 +79,11 @@ pub fn execute_send(
     amount: Uint128,
     msg: Binary,
 ) -> Result<Response, ContractError> {
+    let token = bsei_token(deps.as_ref())?;
     let sender = info.sender.clone();
-    let reward_contract = query_reward_contract(&deps)?;
-
-    let res: Response = cw20_send(deps, env, info, contract.clone(), amount, msg)?;
-    let messages = vec![
-        vec![
-            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: reward_contract.to_string(),
-                msg: to_json_binary(&DecreaseBalance {
-                    address: sender.to_string(),
-                    amount,
-                })?,
-                funds: vec![],
-            })),
-            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: reward_contract.to_string(),
-                msg: to_json_binary(&IncreaseBalance {
-                    address: contract,
-                    amount,
-                })?,
-                funds: vec![],
-            })),
-        ],
-        res.messages,
-    ]
-    .concat();
-
-    Ok(Response::new()
-        .add_submessages(messages)
-        .add_attributes(res.attributes))
+    lst::execute_transfer(deps, &token, &sender, &contract, amount, |deps| {
+        cw20_send(deps, env, info, contract.clone(), amount, msg)
+    })
 }
 
 pub fn execute_transfer_from(
@@ -164,32 +94,11 @@ pub fn execute_transfer_from(
     recipient: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let reward_contract = query_reward_contract(&deps)?;
-
-    let valid_owner = deps.api.addr_validate(owner.as_str())?;
-
-    let res: Response = cw20_transfer_from(deps, env, info, owner, recipient.clone(), amount)?;
-    let messages = vec![
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: reward_contract.to_string(),
-            msg: to_json_binary(&DecreaseBalance {
-                address: valid_owner.to_string(),
-                amount,
-            })?,
-            funds: vec![],
-        })),
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: reward_contract.to_string(),
-            msg: to_json_binary(&IncreaseBalance {
-                address: recipient,
-                amount,
-            })?,
-            funds: vec![],
-        })),
-    ];
-    Ok(Response::new()
-        .add_submessages(messages)
-        .add_attributes(res.attributes))
+    let token = bsei_token(deps.as_ref())?;
+    let valid_owner = deps.api.addr_validate(&owner)?;
+    lst::execute_transfer(deps, &token, &valid_owner, &recipient, amount, |deps| {
+        cw20_transfer_from(deps, env, info, owner, recipient.clone(), amount)
+    })
 }
 
 pub fn execute_burn_from(
@@ -199,30 +108,11 @@ pub fn execute_burn_from(
     owner: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let reward_contract = query_reward_contract(&deps)?;
-    let hub_contract = deps.api.addr_humanize(&read_hub_contract(deps.storage)?)?;
-
-    let valid_owner = deps.api.addr_validate(owner.as_str())?;
-
-    let res: Response = cw20_burn_from(deps, env, info, owner, amount)?;
-    let messages = vec![
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: reward_contract.to_string(),
-            msg: to_json_binary(&DecreaseBalance {
-                address: valid_owner.to_string(),
-                amount,
-            })?,
-            funds: vec![],
-        })),
-        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: hub_contract.to_string(),
-            msg: to_json_binary(&CheckSlashing {})?,
-            funds: vec![],
-        })),
-    ];
-    Ok(Response::new()
-        .add_submessages(messages)
-        .add_attributes(res.attributes))
+    let token = bsei_token(deps.as_ref())?;
+    let valid_owner = deps.api.addr_validate(&owner)?;
+    lst::execute_burn_from(deps, &token, &valid_owner, amount, |deps| {
+        cw20_burn_from(deps, env, info, owner, amount)
+    })
 }
 
 pub fn execute_send_from(
@@ -234,35 +124,48 @@ pub fn execute_send_from(
     amount: Uint128,
     msg: Binary,
 ) -> Result<Response, ContractError> {
-    let reward_contract = query_reward_contract(&deps)?;
+    let token = bsei_token(deps.as_ref())?;
+    let valid_owner = deps.api.addr_validate(&owner)?;
+    lst::execute_transfer(deps, &token, &valid_owner, &contract, amount, |deps| {
+        cw20_send_from(deps, env, info, owner, contract.clone(), amount, msg)
+    })
+}
 
-    let valid_owner = deps.api.addr_validate(owner.as_str())?;
+pub fn execute_add_balance_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let token = bsei_token(deps.as_ref())?;
+    lst::execute_add_balance_hook(deps, info, &token, address)
+}
+
+pub fn execute_remove_balance_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let token = bsei_token(deps.as_ref())?;
+    lst::execute_remove_balance_hook(deps, info, &token, address)
+}
 
-    let res: Response = cw20_send_from(deps, env, info, owner, contract.clone(), amount, msg)?;
-    let messages = vec![
-        vec![
-            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: reward_contract.to_string(),
-                msg: to_json_binary(&DecreaseBalance {
-                    address: valid_owner.to_string(),
-                    amount,
-                })?,
-                funds: vec![],
-            })),
-            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: reward_contract.to_string(),
-                msg: to_json_binary(&IncreaseBalance {
-                    address: contract,
-                    amount,
-                })?,
-                funds: vec![],
-            })),
-        ],
-        res.messages,
-    ]
-    .concat();
+pub fn execute_update_reward_contract(
+    deps: DepsMut,
+    info: MessageInfo,
+    reward_contract: String,
+) -> Result<Response, ContractError> {
+    let token = bsei_token(deps.as_ref())?;
+    lst::execute_update_reward_contract(deps, info, &token, reward_contract)
+}
+
+pub fn execute_permit(deps: DepsMut, env: Env, permit: Permit) -> Result<Response, ContractError> {
+    lst::execute_permit(deps, env, permit, cw20_increase_allowance)
+}
 
-    Ok(Response::new()
-        .add_submessages(messages)
-        .add_attributes(res.attributes))
+/// bSei pays its holders through the reward contract.
+fn bsei_token(deps: Deps) -> StdResult<LstToken> {
+    Ok(LstToken {
+        kind: TokenKind::RewardBearing,
+        hub_contract: deps.api.addr_humanize(&read_hub_contract(deps.storage)?)?,
+    })
 }
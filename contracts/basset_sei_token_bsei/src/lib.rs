@@ -17,7 +17,6 @@ pub mod msg;
 pub mod state;
 
 mod handler;
-mod querier;
 
 #[cfg(test)]
 mod testing;
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use cw20::Cw20Coin;
+use basset::token::Permit;
+use cosmwasm_std::{Binary, Uint128};
+use cw20::{Cw20Coin, Expiration, Logo};
+use cw20_legacy::msg::{InstantiateMarketingInfo, QueryMsg as Cw20QueryMsg};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -23,7 +26,147 @@ pub struct TokenInitMsg {
     pub decimals: u8,
     pub initial_balances: Vec<Cw20Coin>,
     pub hub_contract: String,
+    pub marketing: Option<InstantiateMarketingInfo>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// The first marketing info of a token instantiated without it.
+    /// Once set, it is changed with `UpdateMarketing` and `UploadLogo`.
+    pub marketing: Option<InstantiateMarketingInfo>,
+}
+
+/// The cw20-legacy messages, along with the management of the balance hooks
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+    },
+    Burn {
+        amount: Uint128,
+    },
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    Mint {
+        recipient: String,
+        amount: Uint128,
+    },
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    BurnFrom {
+        owner: String,
+        amount: Uint128,
+    },
+    UpdateMarketing {
+        project: Option<String>,
+        description: Option<String>,
+        marketing: Option<String>,
+    },
+    UploadLogo(Logo),
+
+    /// Notify `address` of every balance change.
+    /// Only the hub or its owner is allowed to execute
+    AddBalanceHook {
+        address: String,
+    },
+    /// Only the hub or its owner is allowed to execute
+    RemoveBalanceHook {
+        address: String,
+    },
+    /// Grant an allowance signed off-chain by the owner.
+    /// Anyone is allowed to execute
+    Permit(Permit),
+    /// Replace the bSei reward contract among the balance hooks.
+    /// Only the hub is allowed to execute
+    UpdateRewardContract {
+        reward_contract: String,
+    },
+}
+
+/// The cw20-legacy queries, along with the balance hooks
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Balance {
+        address: String,
+    },
+    TokenInfo {},
+    Minter {},
+    Allowance {
+        owner: String,
+        spender: String,
+    },
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    MarketingInfo {},
+    DownloadLogo {},
+    /// Return type: BalanceResponse.
+    BalanceAt { address: String, height: u64 },
+    /// Return type: TotalSupplyResponse.
+    TotalSupplyAt { height: u64 },
+    /// Return type: BalanceHooksResponse.
+    BalanceHooks {},
+    /// The nonce of the next permit of `owner`.
+    /// Return type: PermitNonceResponse.
+    PermitNonce { owner: String },
+}
+
+impl QueryMsg {
+    /// The cw20-legacy query, or the query itself if it is one of the extensions
+    pub fn into_cw20(self) -> Result<Cw20QueryMsg, QueryMsg> {
+        Ok(match self {
+            QueryMsg::Balance { address } => Cw20QueryMsg::Balance { address },
+            QueryMsg::TokenInfo {} => Cw20QueryMsg::TokenInfo {},
+            QueryMsg::Minter {} => Cw20QueryMsg::Minter {},
+            QueryMsg::Allowance { owner, spender } => Cw20QueryMsg::Allowance { owner, spender },
+            QueryMsg::AllAllowances {
+                owner,
+                start_after,
+                limit,
+            } => Cw20QueryMsg::AllAllowances {
+                owner,
+                start_after,
+                limit,
+            },
+            QueryMsg::AllAccounts { start_after, limit } => {
+                Cw20QueryMsg::AllAccounts { start_after, limit }
+            }
+            QueryMsg::MarketingInfo {} => Cw20QueryMsg::MarketingInfo {},
+            QueryMsg::DownloadLogo {} => Cw20QueryMsg::DownloadLogo {},
+            QueryMsg::BalanceAt { address, height } => Cw20QueryMsg::BalanceAt { address, height },
+            QueryMsg::TotalSupplyAt { height } => Cw20QueryMsg::TotalSupplyAt { height },
+            query @ (QueryMsg::BalanceHooks {} | QueryMsg::PermitNonce { .. }) => return Err(query),
+        })
+    }
+}
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cell::Cell;
+
 use basset::dispatcher::ConfigResponse as RewardsDispatcherConfig;
 use basset::hub::ConfigResponse;
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
@@ -47,6 +49,7 @@ pub fn mock_dependencies(
 
 pub struct WasmMockQuerier {
     base: MockQuerier<Empty>,
+    wasm_queries: Cell<u64>,
 }
 
 impl Querier for WasmMockQuerier {
@@ -67,6 +70,9 @@ impl Querier for WasmMockQuerier {
 
 impl WasmMockQuerier {
     pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        if let QueryRequest::Wasm(_) = request {
+            self.wasm_queries.set(self.wasm_queries.get() + 1);
+        }
         match &request {
             QueryRequest::Wasm(WasmQuery::Smart {
                 contract_addr,
@@ -85,6 +91,8 @@ impl WasmMockQuerier {
                         bsei_token_contract: Some(String::from(MOCK_TOKEN_CONTRACT_ADDR)),
                         airdrop_registry_contract: Some(String::from("airdrop")),
                         stsei_token_contract: Some(String::from(MOCK_STSEI_TOKEN_CONTRACT_ADDR)),
+                        insurance_fund_contract: None,
+                        rewards_contract: Some(String::from(MOCK_REWARDS_CONTRACT_ADDR)),
 
                         token_contract: Some(String::from(MOCK_TOKEN_CONTRACT_ADDR)),
                     };
@@ -101,6 +109,8 @@ impl WasmMockQuerier {
                         swap_contract: String::from(MOCK_SWAP_CONTRACT_ADDR),
                         swap_denoms: vec![],
                         oracle_contract: String::from(MOCK_ORACLE_CONTRACT_ADDR),
+                        exchange_rate_source: None,
+                        oracle: None,
                     };
                     SystemResult::Ok(ContractResult::from(to_json_binary(&config)))
                 } else {
@@ -114,6 +124,14 @@ impl WasmMockQuerier {
 
 impl WasmMockQuerier {
     pub fn new(base: MockQuerier<Empty>) -> Self {
-        WasmMockQuerier { base }
+        WasmMockQuerier {
+            base,
+            wasm_queries: Cell::new(0),
+        }
+    }
+
+    /// The number of contract queries handled so far
+    pub fn wasm_queries(&self) -> u64 {
+        self.wasm_queries.get()
     }
 }
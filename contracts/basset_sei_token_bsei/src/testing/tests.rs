@@ -14,22 +14,32 @@
 
 use basset::hub::ExecuteMsg::CheckSlashing;
 use basset::reward::ExecuteMsg::{DecreaseBalance, IncreaseBalance};
+use basset::token::{
+    permit_hash, pubkey_address, BalanceHookMsg, BalanceHooksResponse, Permit, PermitNonceResponse,
+    BALANCE_HOOK_GAS_LIMIT, BALANCE_HOOK_REPLY_ID,
+};
 use cosmwasm_std::testing::{mock_env, mock_info};
 use cosmwasm_std::{
-    coins, to_json_binary, Api, CosmosMsg, DepsMut, OwnedDeps, Querier, Storage, SubMsg,
-    Uint128, WasmMsg,
+    coins, from_json, to_json_binary, Addr, Api, Binary, CosmosMsg, DepsMut, Order, OwnedDeps,
+    Querier, Reply, StdError, Storage, SubMsg, SubMsgResult, Uint128, WasmMsg,
+};
+use cw20::{
+    AllowanceResponse, BalanceResponse, Cw20Coin, Cw20ReceiveMsg, DownloadLogoResponse,
+    EmbeddedLogo, Logo, LogoInfo, MarketingInfoResponse, MinterResponse, TokenInfoResponse,
 };
-use cw20::{Cw20ReceiveMsg, MinterResponse, TokenInfoResponse};
-use cw20_legacy::contract::{query_minter, query_token_info};
-use cw20_legacy::msg::ExecuteMsg;
+use cw20_legacy::contract::{query_balance, query_minter, query_token_info};
+use cw20_legacy::msg::{InstantiateMarketingInfo, TotalSupplyResponse};
+use cw20_legacy::state::BALANCES;
 
-use crate::contract::{execute, instantiate};
-use crate::msg::TokenInitMsg;
+use crate::contract::{execute, instantiate, migrate, query, reply};
+use crate::msg::{ExecuteMsg, MigrateMsg, QueryMsg, TokenInitMsg};
 use crate::state::read_hub_contract;
 use crate::testing::mock_querier::{
     mock_dependencies, MOCK_HUB_CONTRACT_ADDR, MOCK_REWARDS_CONTRACT_ADDR,
 };
 use cw20_legacy::ContractError;
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use k256::ecdsa::{Signature, SigningKey};
 use std::borrow::BorrowMut;
 
 // this will set up the init for other tests
@@ -59,6 +69,7 @@ fn _do_init<S: Storage, A: Api, Q: Querier>(
         decimals: 6,
         initial_balances: vec![],
         hub_contract,
+        marketing: None,
     };
 
     let info = mock_info(&String::from(MOCK_HUB_CONTRACT_ADDR), &[]);
@@ -102,6 +113,7 @@ fn proper_initialization() {
         decimals: 6,
         initial_balances: vec![],
         hub_contract: hub_contract.clone(),
+        marketing: None,
     };
     let info = mock_info(&hub_contract, &[]);
     let res = instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
@@ -128,6 +140,125 @@ fn proper_initialization() {
     assert_eq!(read_hub_contract(&deps.storage).unwrap(), hub_contract_raw);
 }
 
+#[test]
+fn proper_marketing() {
+    let mut deps = mock_dependencies(&[]);
+    let hub_contract = String::from(MOCK_HUB_CONTRACT_ADDR);
+
+    let init_msg = TokenInitMsg {
+        name: "bsei".to_string(),
+        symbol: "BSEI".to_string(),
+        decimals: 6,
+        initial_balances: vec![],
+        hub_contract: hub_contract.clone(),
+        marketing: Some(InstantiateMarketingInfo {
+            project: Some("https://bsei.finance".to_string()),
+            description: Some("Bonded SEI".to_string()),
+            marketing: Some("marketing".to_string()),
+            logo: Some(Logo::Url("https://bsei.finance/logo.svg".to_string())),
+        }),
+    };
+    let info = mock_info(&hub_contract, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
+
+    let res: MarketingInfoResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::MarketingInfo {}).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        MarketingInfoResponse {
+            project: Some("https://bsei.finance".to_string()),
+            description: Some("Bonded SEI".to_string()),
+            marketing: Some(Addr::unchecked("marketing")),
+            logo: Some(LogoInfo::Url("https://bsei.finance/logo.svg".to_string())),
+        }
+    );
+
+    // only the marketing address can update the metadata
+    let msg = ExecuteMsg::UpdateMarketing {
+        project: None,
+        description: Some("".to_string()),
+        marketing: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(&hub_contract, &[]), msg.clone());
+    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+    execute(deps.as_mut(), mock_env(), mock_info("marketing", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::UploadLogo(Logo::Embedded(EmbeddedLogo::Png(Binary::from(b"logo"))));
+    let res = execute(deps.as_mut(), mock_env(), mock_info("marketing", &[]), msg);
+    assert_eq!(res.unwrap_err(), ContractError::InvalidPngHeader {});
+
+    let png = Binary::from([0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0x00]);
+    let msg = ExecuteMsg::UploadLogo(Logo::Embedded(EmbeddedLogo::Png(png.clone())));
+    execute(deps.as_mut(), mock_env(), mock_info("marketing", &[]), msg).unwrap();
+
+    let res: MarketingInfoResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::MarketingInfo {}).unwrap()).unwrap();
+    assert_eq!(res.description, None);
+    assert_eq!(res.logo, Some(LogoInfo::Embedded));
+    let res: DownloadLogoResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::DownloadLogo {}).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        DownloadLogoResponse {
+            mime_type: "image/png".to_string(),
+            data: png,
+        }
+    );
+
+    // the balances keep their legacy layout, keyed by the canonical address
+    let addr = String::from("addr0000");
+    do_mint(deps.as_mut(), addr.clone(), Uint128::new(1000));
+    let addr_raw = deps.api.addr_canonicalize(&addr).unwrap();
+    assert_eq!(
+        BALANCES.load(&deps.storage, addr_raw.as_slice()).unwrap(),
+        Uint128::new(1000)
+    );
+    assert_eq!(
+        query_balance(deps.as_ref(), addr).unwrap().balance,
+        Uint128::new(1000)
+    );
+}
+
+#[test]
+fn migrate_marketing() {
+    let mut deps = mock_dependencies(&[]);
+    do_init_with_minter(&mut deps, MOCK_HUB_CONTRACT_ADDR, None);
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::MarketingInfo {}).unwrap();
+    let res: MarketingInfoResponse = from_json(res).unwrap();
+    assert_eq!(res.marketing, None);
+
+    // a token deployed without marketing info gets its first one when migrated
+    let msg = MigrateMsg {
+        marketing: Some(InstantiateMarketingInfo {
+            project: Some("https://bsei.finance".to_string()),
+            description: Some("Bonded SEI".to_string()),
+            marketing: Some("marketing".to_string()),
+            logo: Some(Logo::Url("https://bsei.finance/logo.svg".to_string())),
+        }),
+    };
+    migrate(deps.as_mut(), mock_env(), msg.clone()).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::MarketingInfo {}).unwrap();
+    let res: MarketingInfoResponse = from_json(res).unwrap();
+    assert_eq!(res.marketing, Some(Addr::unchecked("marketing")));
+    assert_eq!(res.logo, Some(LogoInfo::Url("https://bsei.finance/logo.svg".to_string())));
+
+    // the marketing address can update it from then on
+    let update = ExecuteMsg::UpdateMarketing {
+        project: None,
+        description: Some("Bonded Sei".to_string()),
+        marketing: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("marketing", &[]), update).unwrap();
+
+    let err = migrate(deps.as_mut(), mock_env(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("marketing info is already set, use update_marketing")
+    );
+    migrate(deps.as_mut(), mock_env(), MigrateMsg { marketing: None }).unwrap();
+}
+
 #[test]
 fn transfer() {
     let mut deps = mock_dependencies(&coins(2, "token"));
@@ -174,6 +305,383 @@ fn transfer() {
     );
 }
 
+#[test]
+fn balance_hooks() {
+    let mut deps = mock_dependencies(&coins(2, "token"));
+    let addr1 = String::from("addr0001");
+    let addr2 = String::from("addr0002");
+    let gauge = String::from("gauge");
+
+    do_init_with_minter(
+        deps.borrow_mut(),
+        &String::from(MOCK_HUB_CONTRACT_ADDR),
+        None,
+    );
+    do_mint(deps.as_mut(), addr1.clone(), Uint128::new(100));
+
+    // the reward contract is resolved once and kept as the first hook
+    let res: BalanceHooksResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::BalanceHooks {}).unwrap()).unwrap();
+    assert_eq!(res.hooks, vec![MOCK_REWARDS_CONTRACT_ADDR.to_string()]);
+
+    let msg = ExecuteMsg::AddBalanceHook {
+        address: gauge.clone(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(&addr1, &[]), msg.clone());
+    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+    // the owner of the hub manages the hooks too
+    execute(deps.as_mut(), mock_env(), mock_info("owner1", &[]), msg.clone()).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), mock_info(MOCK_HUB_CONTRACT_ADDR, &[]), msg);
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "Generic error: Balance hook gauge is already registered"
+    );
+
+    let msg = ExecuteMsg::Transfer {
+        recipient: addr2.clone(),
+        amount: Uint128::new(10),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(&addr1, &[]), msg).unwrap();
+    let hook_msg = |contract: &str, msg: BalanceHookMsg| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_json_binary(&msg).unwrap(),
+            funds: vec![],
+        }))
+    };
+    let decrease = BalanceHookMsg::DecreaseBalance {
+        address: addr1.clone(),
+        amount: Uint128::new(10),
+    };
+    let increase = BalanceHookMsg::IncreaseBalance {
+        address: addr2.clone(),
+        amount: Uint128::new(10),
+    };
+    // only the reward contract may revert the transfer
+    let optional_msg = |contract: &str, msg: BalanceHookMsg| {
+        SubMsg::reply_on_error(hook_msg(contract, msg).msg, BALANCE_HOOK_REPLY_ID)
+            .with_gas_limit(BALANCE_HOOK_GAS_LIMIT)
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            hook_msg(MOCK_REWARDS_CONTRACT_ADDR, decrease.clone()),
+            hook_msg(MOCK_REWARDS_CONTRACT_ADDR, increase.clone()),
+            optional_msg(&gauge, decrease),
+            optional_msg(&gauge, increase),
+        ]
+    );
+
+    // a failing gauge is ignored
+    let failure = Reply {
+        id: BALANCE_HOOK_REPLY_ID,
+        result: SubMsgResult::Err("out of gas".to_string()),
+    };
+    let res = reply(deps.as_mut(), mock_env(), failure).unwrap();
+    assert!(res.messages.is_empty());
+
+    let msg = ExecuteMsg::RemoveBalanceHook {
+        address: MOCK_REWARDS_CONTRACT_ADDR.to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info(MOCK_HUB_CONTRACT_ADDR, &[]), msg);
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "Generic error: The reward contract cannot be removed"
+    );
+
+    let msg = ExecuteMsg::RemoveBalanceHook { address: gauge };
+    execute(deps.as_mut(), mock_env(), mock_info(MOCK_HUB_CONTRACT_ADDR, &[]), msg).unwrap();
+    let res: BalanceHooksResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::BalanceHooks {}).unwrap()).unwrap();
+    assert_eq!(res.hooks, vec![MOCK_REWARDS_CONTRACT_ADDR.to_string()]);
+}
+
+#[test]
+fn cached_reward_contract() {
+    let mut deps = mock_dependencies(&coins(2, "token"));
+    let addr1 = String::from("addr0001");
+    let addr2 = String::from("addr0002");
+    let new_reward = String::from("new_reward");
+
+    do_init_with_minter(
+        deps.borrow_mut(),
+        &String::from(MOCK_HUB_CONTRACT_ADDR),
+        None,
+    );
+
+    // the first balance change resolves the reward contract registered in the hub
+    let queries = deps.querier.wasm_queries();
+    do_mint(deps.as_mut(), addr1.clone(), Uint128::new(100));
+    assert_eq!(deps.querier.wasm_queries() - queries, 1);
+
+    // the following transfers do not query anything
+    let msg = ExecuteMsg::Transfer {
+        recipient: addr2.clone(),
+        amount: Uint128::new(10),
+    };
+    let queries = deps.querier.wasm_queries();
+    execute(deps.as_mut(), mock_env(), mock_info(&addr1, &[]), msg.clone()).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info(&addr1, &[]), msg.clone()).unwrap();
+    assert_eq!(deps.querier.wasm_queries() - queries, 0);
+
+    // only the hub pushes a new reward contract
+    let update = ExecuteMsg::UpdateRewardContract {
+        reward_contract: new_reward.clone(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("owner1", &[]), update.clone());
+    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+    execute(deps.as_mut(), mock_env(), mock_info(MOCK_HUB_CONTRACT_ADDR, &[]), update).unwrap();
+
+    let res: BalanceHooksResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::BalanceHooks {}).unwrap()).unwrap();
+    assert_eq!(res.hooks, vec![new_reward.clone()]);
+
+    let queries = deps.querier.wasm_queries();
+    let res = execute(deps.as_mut(), mock_env(), mock_info(&addr1, &[]), msg).unwrap();
+    assert_eq!(deps.querier.wasm_queries() - queries, 0);
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: new_reward.clone(),
+                msg: to_json_binary(&DecreaseBalance {
+                    address: addr1,
+                    amount: Uint128::new(10),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: new_reward,
+                msg: to_json_binary(&IncreaseBalance {
+                    address: addr2,
+                    amount: Uint128::new(10),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+}
+
+#[test]
+fn balance_snapshots() {
+    let mut deps = mock_dependencies(&coins(2, "token"));
+    let addr1 = String::from("addr0001");
+    let addr2 = String::from("addr0002");
+
+    do_init_with_minter(
+        deps.borrow_mut(),
+        &String::from(MOCK_HUB_CONTRACT_ADDR),
+        None,
+    );
+
+    let mut env = mock_env();
+    let start = env.block.height;
+    let info = mock_info(MOCK_HUB_CONTRACT_ADDR, &[]);
+    let mint = ExecuteMsg::Mint {
+        recipient: addr1.clone(),
+        amount: Uint128::new(100),
+    };
+    execute(deps.as_mut(), env.clone(), info, mint).unwrap();
+
+    env.block.height += 1;
+    let transfer = ExecuteMsg::Transfer {
+        recipient: addr2.clone(),
+        amount: Uint128::new(30),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(&addr1, &[]), transfer).unwrap();
+
+    // the hub burns what it is sent, within the same block
+    env.block.height += 1;
+    let transfer = ExecuteMsg::Transfer {
+        recipient: MOCK_HUB_CONTRACT_ADDR.to_string(),
+        amount: Uint128::new(20),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(&addr1, &[]), transfer).unwrap();
+    let burn = ExecuteMsg::Burn {
+        amount: Uint128::new(20),
+    };
+    let info = mock_info(MOCK_HUB_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), env.clone(), info, burn).unwrap();
+
+    let balance_at = |address: &String, height: u64| {
+        let msg = QueryMsg::BalanceAt {
+            address: address.clone(),
+            height,
+        };
+        let res: BalanceResponse =
+            from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        res.balance.u128()
+    };
+    assert_eq!(balance_at(&addr1, start), 0);
+    assert_eq!(balance_at(&addr1, start + 1), 100);
+    assert_eq!(balance_at(&addr1, start + 2), 70);
+    assert_eq!(balance_at(&addr1, start + 3), 50);
+    assert_eq!(balance_at(&addr2, start + 1), 0);
+    assert_eq!(balance_at(&addr2, start + 2), 30);
+    assert_eq!(balance_at(&MOCK_HUB_CONTRACT_ADDR.to_string(), start + 3), 0);
+
+    let total_supply_at = |height: u64| {
+        let msg = QueryMsg::TotalSupplyAt { height };
+        let res: TotalSupplyResponse =
+            from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        res.total_supply.u128()
+    };
+    assert_eq!(total_supply_at(start), 0);
+    assert_eq!(total_supply_at(start + 1), 100);
+    assert_eq!(total_supply_at(start + 2), 100);
+    assert_eq!(total_supply_at(start + 3), 80);
+}
+
+#[test]
+fn balance_snapshots_storage() {
+    let mut deps = mock_dependencies(&coins(2, "token"));
+    let addr1 = String::from("addr0001");
+    let addr2 = String::from("addr0002");
+
+    do_init_with_minter(
+        deps.borrow_mut(),
+        &String::from(MOCK_HUB_CONTRACT_ADDR),
+        None,
+    );
+    do_mint(deps.as_mut(), addr1.clone(), Uint128::new(100));
+    let entries = |storage: &dyn Storage| storage.range(None, None, Order::Ascending).count();
+
+    let transfer = ExecuteMsg::Transfer {
+        recipient: addr2,
+        amount: Uint128::new(10),
+    };
+    let info = mock_info(&addr1, &[]);
+    let mut env = mock_env();
+    env.block.height += 1;
+
+    // a transfer records each account once per block, besides the new balance of addr2
+    let before = entries(&deps.storage);
+    execute(deps.as_mut(), env.clone(), info.clone(), transfer.clone()).unwrap();
+    assert_eq!(entries(&deps.storage) - before, 3);
+
+    let before = entries(&deps.storage);
+    execute(deps.as_mut(), env.clone(), info.clone(), transfer.clone()).unwrap();
+    assert_eq!(entries(&deps.storage) - before, 0);
+
+    env.block.height += 1;
+    let before = entries(&deps.storage);
+    execute(deps.as_mut(), env, info, transfer).unwrap();
+    assert_eq!(entries(&deps.storage) - before, 2);
+}
+
+fn sign_permit(key: &SigningKey, permit: &mut Permit) {
+    let hash = permit_hash(&permit.payload(&mock_env())).unwrap();
+    let signature: Signature = key.sign_prehash(&hash).unwrap();
+    permit.signature = Binary::from(&signature.to_bytes()[..]);
+}
+
+#[test]
+fn permit() {
+    let mut deps = mock_dependencies(&coins(2, "token"));
+    do_init_with_minter(
+        deps.borrow_mut(),
+        &String::from(MOCK_HUB_CONTRACT_ADDR),
+        None,
+    );
+    // the owner is the account of its key
+    let key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+    let pubkey = Binary::from(key.verifying_key().to_encoded_point(true).as_bytes());
+    let owner = pubkey_address("sei", &pubkey).unwrap();
+    let spender = String::from("aggregator");
+    let relayer = mock_info("relayer", &[]);
+
+    let mut permit = Permit {
+        owner: owner.clone(),
+        spender: spender.clone(),
+        amount: Uint128::new(100),
+        expires: None,
+        nonce: 0,
+        deadline: mock_env().block.time.seconds() + 60,
+        pubkey: pubkey.clone(),
+        signature: Binary::default(),
+    };
+    sign_permit(&key, &mut permit);
+
+    // anyone submits the permit of the owner
+    let msg = ExecuteMsg::Permit(permit.clone());
+    execute(deps.as_mut(), mock_env(), relayer.clone(), msg.clone()).unwrap();
+    let allowance: AllowanceResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Allowance {
+                owner: owner.clone(),
+                spender: spender.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(allowance.allowance, Uint128::new(100));
+
+    // a permit is only used once
+    let res = execute(deps.as_mut(), mock_env(), relayer.clone(), msg);
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "Generic error: Invalid permit nonce, expected 1"
+    );
+    let nonce: PermitNonceResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PermitNonce {
+                owner: owner.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(nonce.nonce, 1);
+
+    // the signature covers the whole grant
+    permit.nonce = 1;
+    sign_permit(&key, &mut permit);
+    let mut tampered = permit.clone();
+    tampered.amount = Uint128::new(1000);
+    let res = execute(deps.as_mut(), mock_env(), relayer.clone(), ExecuteMsg::Permit(tampered));
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "Generic error: Invalid permit signature"
+    );
+
+    let mut expired = permit.clone();
+    expired.deadline = mock_env().block.time.seconds() - 1;
+    sign_permit(&key, &mut expired);
+    let res = execute(deps.as_mut(), mock_env(), relayer.clone(), ExecuteMsg::Permit(expired));
+    assert_eq!(res.unwrap_err().to_string(), "Generic error: The permit has expired");
+
+    // the key of another account cannot sign for the owner
+    let other_key = SigningKey::from_bytes(&[8u8; 32].into()).unwrap();
+    let mut forged = permit.clone();
+    forged.pubkey = Binary::from(other_key.verifying_key().to_encoded_point(true).as_bytes());
+    sign_permit(&other_key, &mut forged);
+    let res = execute(deps.as_mut(), mock_env(), relayer.clone(), ExecuteMsg::Permit(forged));
+    assert_eq!(
+        res.unwrap_err().to_string(),
+        "Generic error: The public key does not match the owner"
+    );
+
+    execute(deps.as_mut(), mock_env(), relayer, ExecuteMsg::Permit(permit)).unwrap();
+    let allowance: AllowanceResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Allowance { owner, spender },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(allowance.allowance, Uint128::new(200));
+}
+
 #[test]
 fn transfer_from() {
     let mut deps = mock_dependencies(&coins(2, "token"));
@@ -517,3 +1025,27 @@ fn send_from() {
         .unwrap()
     );
 }
+
+fn init_msg(initial_balances: Vec<Cw20Coin>) -> TokenInitMsg {
+    TokenInitMsg {
+        name: "bsei".to_string(),
+        symbol: "BSEI".to_string(),
+        decimals: 6,
+        initial_balances,
+        hub_contract: MOCK_HUB_CONTRACT_ADDR.to_string(),
+        marketing: None,
+    }
+}
+
+lst_token::contract_tests! {
+    instantiate: crate::contract::instantiate,
+    execute: crate::contract::execute,
+    query: crate::contract::query,
+    reply: crate::contract::reply,
+    execute_msg: crate::msg::ExecuteMsg,
+    query_msg: crate::msg::QueryMsg,
+    init_msg: init_msg,
+    deps: mock_dependencies(&[]),
+    hub: MOCK_HUB_CONTRACT_ADDR,
+    reward_contract: Some(MOCK_REWARDS_CONTRACT_ADDR),
+}
@@ -20,9 +20,8 @@ use cw20::{
     AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, AllSpenderAllowancesResponse,
     BalanceResponse, DownloadLogoResponse, MarketingInfoResponse, MinterResponse, TokenInfoResponse,
 };
-use cw20_base::msg::{ExecuteMsg, QueryMsg};
-
-use basset_sei_token_stsei::msg::TokenInitMsg;
+use basset::token::BalanceHooksResponse;
+use basset_sei_token_stsei::msg::{ExecuteMsg, QueryMsg, TokenInitMsg};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -42,4 +41,5 @@ fn main() {
     export_schema(&schema_for!(AllSpenderAllowancesResponse), &out_dir);
     export_schema(&schema_for!(MarketingInfoResponse), &out_dir);
     export_schema(&schema_for!(DownloadLogoResponse), &out_dir);
+    export_schema(&schema_for!(BalanceHooksResponse), &out_dir);
 }
@@ -15,15 +15,17 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
-use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+};
 
 use cw20_base::allowances::{execute_decrease_allowance, execute_increase_allowance};
 use cw20_base::contract::instantiate as cw20_init;
 use cw20_base::contract::query as cw20_query;
-use cw20_base::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw20_base::msg::InstantiateMsg;
 
 use crate::handler::*;
-use crate::msg::TokenInitMsg;
+use crate::msg::{ExecuteMsg, QueryMsg, TokenInitMsg};
 use crate::state::HUB_CONTRACT;
 use cw20::MinterResponse;
 use cw20_base::ContractError;
@@ -124,10 +126,31 @@ pub fn execute(
         ExecuteMsg::UpdateMinter { new_minter } => {
             execute_update_minter(deps, env, info, new_minter)
         }
+        ExecuteMsg::AddBalanceHook { address } => execute_add_balance_hook(deps, info, address),
+        ExecuteMsg::RemoveBalanceHook { address } => {
+            execute_remove_balance_hook(deps, info, address)
+        }
+        ExecuteMsg::Permit(permit) => execute_permit(deps, env, permit),
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+    reply_balance_hook(msg)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    cw20_query(deps, env, msg)
+    match msg.into_cw20() {
+        Ok(msg) => cw20_query(deps, env, msg),
+        Err(QueryMsg::BalanceAt { address, height }) => {
+            to_json_binary(&query_balance_at(deps, address, height)?)
+        }
+        Err(QueryMsg::TotalSupplyAt { height }) => {
+            to_json_binary(&query_total_supply_at(deps, height)?)
+        }
+        Err(QueryMsg::PermitNonce { owner }) => to_json_binary(&query_permit_nonce(deps, owner)?),
+        Err(QueryMsg::BalanceHooks {}) => to_json_binary(&query_balance_hooks(deps)?),
+        Err(query) => Err(StdError::generic_err(format!("unsupported query: {:?}", query))),
+    }
 }
@@ -12,23 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use basset::hub::ExecuteMsg::CheckSlashing;
+use basset::token::{Permit, TotalSupplyResponse};
 use cosmwasm_std::{
-    to_json_binary, Binary, CosmosMsg, DepsMut, Env, MessageInfo, Response, SubMsg, Uint128, WasmMsg,
+    Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128,
 };
-use cw20::Logo;
+use cw20::{BalanceResponse, Logo};
 use cw20_base::allowances::{
-    execute_burn_from as cw20_burn_from, execute_send_from as cw20_send_from,
-    execute_transfer_from as cw20_transfer_from,
+    execute_burn_from as cw20_burn_from, execute_increase_allowance as cw20_increase_allowance,
+    execute_send_from as cw20_send_from, execute_transfer_from as cw20_transfer_from,
 };
 use cw20_base::contract::{
     execute_burn as cw20_burn, execute_mint as cw20_mint, execute_send as cw20_send,
     execute_transfer as cw20_transfer, execute_update_marketing as cw20_update_marketing,
     execute_update_minter as cw20_update_minter, execute_upload_logo as cw20_upload_logo,
 };
+use cw20_base::state::{BALANCES, TOKEN_INFO};
 use cw20_base::ContractError;
+use cw_storage_plus::Bound;
+use lst_token::handler as lst;
+use lst_token::token::{LstToken, TokenKind};
 
-use crate::state::HUB_CONTRACT;
+use crate::state::{BALANCE_CHANGELOG, HUB_CONTRACT, TOTAL_SUPPLY_CHANGELOG};
+
+pub use lst_token::handler::{query_balance_hooks, query_permit_nonce, reply_balance_hook};
 
 pub fn execute_transfer(
     deps: DepsMut,
@@ -37,7 +43,15 @@ pub fn execute_transfer(
     recipient: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    cw20_transfer(deps, env, info, recipient, amount)
+    let token = stsei_token(deps.as_ref())?;
+    let sender = info.sender.clone();
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    let height = env.block.height;
+    lst::execute_transfer(deps, &token, &sender, &recipient, amount, |deps| {
+        with_snapshots(deps, height, &[&sender, &rcpt_addr], |deps| {
+            cw20_transfer(deps, env, info, recipient.clone(), amount)
+        })
+    })
 }
 
 pub fn execute_burn(
@@ -46,28 +60,14 @@ pub fn execute_burn(
     info: MessageInfo,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let hub_contract = deps.api.addr_humanize(&HUB_CONTRACT.load(deps.storage)?)?;
-
-    if info.sender != hub_contract {
-        return Err(ContractError::Unauthorized {});
-    }
-
-    let messages = vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: hub_contract.to_string(),
-        msg: to_json_binary(&CheckSlashing {})?,
-        funds: vec![],
-    }))];
-    
-    let res = cw20_burn(deps, env, info, amount)?;
-    /* send message example
-     use cosmwasm_std::{coins, BankMsg};
-     let msg = BankMsg::Send { to_address: String::from("you"), amount: coins(1015, "earth") };
-     let sub_msg: SubMsg = SubMsg::reply_always(msg, 1234).with_gas_limit(60_000);
-     messages.push(sub_msg);
-    */
-    Ok(Response::new()
-        .add_submessages(messages)
-        .add_attributes(res.attributes))
+    let token = stsei_token(deps.as_ref())?;
+    let sender = info.sender.clone();
+    let height = env.block.height;
+    lst::execute_burn(deps, &token, &sender, amount, |deps| {
+        with_snapshots(deps, height, &[&sender], |deps| {
+            cw20_burn(deps, env, info, amount)
+        })
+    })
 }
 
 pub fn execute_mint(
@@ -77,7 +77,15 @@ pub fn execute_mint(
     recipient: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    cw20_mint(deps, env, info, recipient, amount)
+    let token = stsei_token(deps.as_ref())?;
+    let sender = info.sender.clone();
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    let height = env.block.height;
+    lst::execute_mint(deps, &token, &sender, &recipient, amount, |deps| {
+        with_snapshots(deps, height, &[&rcpt_addr], |deps| {
+            cw20_mint(deps, env, info, recipient.clone(), amount)
+        })
+    })
 }
 
 pub fn execute_send(
@@ -88,7 +96,15 @@ pub fn execute_send(
     amount: Uint128,
     msg: Binary,
 ) -> Result<Response, ContractError> {
-    cw20_send(deps, env, info, contract, amount, msg)
+    let token = stsei_token(deps.as_ref())?;
+    let sender = info.sender.clone();
+    let rcpt_addr = deps.api.addr_validate(&contract)?;
+    let height = env.block.height;
+    lst::execute_transfer(deps, &token, &sender, &contract, amount, |deps| {
+        with_snapshots(deps, height, &[&sender, &rcpt_addr], |deps| {
+            cw20_send(deps, env, info, contract.clone(), amount, msg)
+        })
+    })
 }
 
 pub fn execute_transfer_from(
@@ -99,7 +115,15 @@ pub fn execute_transfer_from(
     recipient: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    cw20_transfer_from(deps, env, info, owner, recipient, amount)
+    let token = stsei_token(deps.as_ref())?;
+    let valid_owner = deps.api.addr_validate(&owner)?;
+    let rcpt_addr = deps.api.addr_validate(&recipient)?;
+    let height = env.block.height;
+    lst::execute_transfer(deps, &token, &valid_owner, &recipient, amount, |deps| {
+        with_snapshots(deps, height, &[&valid_owner, &rcpt_addr], |deps| {
+            cw20_transfer_from(deps, env, info, owner, recipient.clone(), amount)
+        })
+    })
 }
 
 pub fn execute_burn_from(
@@ -109,17 +133,14 @@ pub fn execute_burn_from(
     owner: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let hub_contract = deps.api.addr_humanize(&HUB_CONTRACT.load(deps.storage)?)?;
-
-    let res = cw20_burn_from(deps, env, info, owner, amount)?;
-    let messages = vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: hub_contract.to_string(),
-        msg: to_json_binary(&CheckSlashing {})?,
-        funds: vec![],
-    }))];
-    Ok(Response::new()
-        .add_submessages(messages)
-        .add_attributes(res.attributes))
+    let token = stsei_token(deps.as_ref())?;
+    let valid_owner = deps.api.addr_validate(&owner)?;
+    let height = env.block.height;
+    lst::execute_burn_from(deps, &token, &valid_owner, amount, |deps| {
+        with_snapshots(deps, height, &[&valid_owner], |deps| {
+            cw20_burn_from(deps, env, info, owner, amount)
+        })
+    })
 }
 
 pub fn execute_send_from(
@@ -131,7 +152,15 @@ pub fn execute_send_from(
     amount: Uint128,
     msg: Binary,
 ) -> Result<Response, ContractError> {
-    cw20_send_from(deps, env, info, owner, contract, amount, msg)
+    let token = stsei_token(deps.as_ref())?;
+    let valid_owner = deps.api.addr_validate(&owner)?;
+    let rcpt_addr = deps.api.addr_validate(&contract)?;
+    let height = env.block.height;
+    lst::execute_transfer(deps, &token, &valid_owner, &contract, amount, |deps| {
+        with_snapshots(deps, height, &[&valid_owner, &rcpt_addr], |deps| {
+            cw20_send_from(deps, env, info, owner, contract.clone(), amount, msg)
+        })
+    })
 }
 
 pub fn execute_update_marketing(
@@ -162,3 +191,89 @@ pub fn execute_update_minter(
 ) -> Result<Response, ContractError> {
     cw20_update_minter(deps, env, info, minter)
 }
+
+pub fn execute_add_balance_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let token = stsei_token(deps.as_ref())?;
+    lst::execute_add_balance_hook(deps, info, &token, address)
+}
+
+pub fn execute_remove_balance_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let token = stsei_token(deps.as_ref())?;
+    lst::execute_remove_balance_hook(deps, info, &token, address)
+}
+
+pub fn execute_permit(deps: DepsMut, env: Env, permit: Permit) -> Result<Response, ContractError> {
+    lst::execute_permit(deps, env, permit, cw20_increase_allowance)
+}
+
+/// The balance at the start of the block `height`.
+pub fn query_balance_at(deps: Deps, address: String, height: u64) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    // the first change from `height` on recorded the balance, else it has not changed since
+    let balance = match BALANCE_CHANGELOG
+        .prefix(&address)
+        .range(deps.storage, Some(Bound::inclusive(height)), None, Order::Ascending)
+        .next()
+    {
+        Some(item) => item?.1,
+        None => BALANCES.may_load(deps.storage, &address)?.unwrap_or_default(),
+    };
+    Ok(BalanceResponse { balance })
+}
+
+/// The total supply at the start of the block `height`.
+pub fn query_total_supply_at(deps: Deps, height: u64) -> StdResult<TotalSupplyResponse> {
+    let total_supply = match TOTAL_SUPPLY_CHANGELOG
+        .range(deps.storage, Some(Bound::inclusive(height)), None, Order::Ascending)
+        .next()
+    {
+        Some(item) => item?.1,
+        None => TOKEN_INFO.load(deps.storage)?.total_supply,
+    };
+    Ok(TotalSupplyResponse { total_supply })
+}
+
+/// stSei accrues the rewards to its exchange rate.
+fn stsei_token(deps: Deps) -> StdResult<LstToken> {
+    Ok(LstToken {
+        kind: TokenKind::ValueAccruing,
+        hub_contract: deps.api.addr_humanize(&HUB_CONTRACT.load(deps.storage)?)?,
+    })
+}
+
+/// Run a balance operation of cw20-base, and record the balances of `accounts` and the
+/// total supply as they were before their first change at `height`.
+fn with_snapshots<F>(
+    mut deps: DepsMut,
+    height: u64,
+    accounts: &[&Addr],
+    operation: F,
+) -> Result<Response, ContractError>
+where
+    F: FnOnce(DepsMut) -> Result<Response, ContractError>,
+{
+    for account in accounts {
+        if !BALANCE_CHANGELOG.has(deps.storage, (account, height)) {
+            let balance = BALANCES.may_load(deps.storage, account)?.unwrap_or_default();
+            BALANCE_CHANGELOG.save(deps.storage, (account, height), &balance)?;
+        }
+    }
+    let total_supply = TOKEN_INFO.load(deps.storage)?.total_supply;
+
+    let res = operation(deps.branch())?;
+
+    if TOKEN_INFO.load(deps.storage)?.total_supply != total_supply
+        && !TOTAL_SUPPLY_CHANGELOG.has(deps.storage, height)
+    {
+        TOTAL_SUPPLY_CHANGELOG.save(deps.storage, height, &total_supply)?;
+    }
+    Ok(res)
+}
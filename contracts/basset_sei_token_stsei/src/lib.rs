@@ -17,3 +17,6 @@ pub mod msg;
 pub mod state;
 
 mod handler;
+
+#[cfg(test)]
+mod testing;
@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use cw20::Cw20Coin;
-use cw20_base::msg::InstantiateMarketingInfo;
+use basset::token::Permit;
+use cosmwasm_std::{Binary, Uint128};
+use cw20::{Cw20Coin, Expiration, Logo};
+use cw20_base::msg::{InstantiateMarketingInfo, QueryMsg as Cw20QueryMsg};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -26,3 +28,153 @@ pub struct TokenInitMsg {
     pub hub_contract: String,
     pub marketing: Option<InstantiateMarketingInfo>,
 }
+
+/// The cw20-base messages, along with the management of the balance hooks
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Transfer {
+        recipient: String,
+        amount: Uint128,
+    },
+    Burn {
+        amount: Uint128,
+    },
+    Send {
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    IncreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    DecreaseAllowance {
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    BurnFrom {
+        owner: String,
+        amount: Uint128,
+    },
+    Mint {
+        recipient: String,
+        amount: Uint128,
+    },
+    UpdateMinter {
+        new_minter: Option<String>,
+    },
+    UpdateMarketing {
+        project: Option<String>,
+        description: Option<String>,
+        marketing: Option<String>,
+    },
+    UploadLogo(Logo),
+
+    /// Notify `address` of every balance change.
+    /// Only the hub or its owner is allowed to execute
+    AddBalanceHook {
+        address: String,
+    },
+    /// Only the hub or its owner is allowed to execute
+    RemoveBalanceHook {
+        address: String,
+    },
+    /// Grant an allowance signed off-chain by the owner.
+    /// Anyone is allowed to execute
+    Permit(Permit),
+}
+
+/// The cw20-base queries, along with the balance hooks
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Balance {
+        address: String,
+    },
+    TokenInfo {},
+    Minter {},
+    Allowance {
+        owner: String,
+        spender: String,
+    },
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    AllSpenderAllowances {
+        spender: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    MarketingInfo {},
+    DownloadLogo {},
+    /// The balance of `address` at the start of the block `height`.
+    /// Return type: BalanceResponse.
+    BalanceAt { address: String, height: u64 },
+    /// The total supply at the start of the block `height`.
+    /// Return type: TotalSupplyResponse.
+    TotalSupplyAt { height: u64 },
+    /// Return type: BalanceHooksResponse.
+    BalanceHooks {},
+    /// The nonce of the next permit of `owner`.
+    /// Return type: PermitNonceResponse.
+    PermitNonce { owner: String },
+}
+
+impl QueryMsg {
+    /// The cw20-base query, or the query itself if it is one of the extensions
+    pub fn into_cw20(self) -> Result<Cw20QueryMsg, QueryMsg> {
+        Ok(match self {
+            QueryMsg::Balance { address } => Cw20QueryMsg::Balance { address },
+            QueryMsg::TokenInfo {} => Cw20QueryMsg::TokenInfo {},
+            QueryMsg::Minter {} => Cw20QueryMsg::Minter {},
+            QueryMsg::Allowance { owner, spender } => Cw20QueryMsg::Allowance { owner, spender },
+            QueryMsg::AllAllowances {
+                owner,
+                start_after,
+                limit,
+            } => Cw20QueryMsg::AllAllowances {
+                owner,
+                start_after,
+                limit,
+            },
+            QueryMsg::AllSpenderAllowances {
+                spender,
+                start_after,
+                limit,
+            } => Cw20QueryMsg::AllSpenderAllowances {
+                spender,
+                start_after,
+                limit,
+            },
+            QueryMsg::AllAccounts { start_after, limit } => {
+                Cw20QueryMsg::AllAccounts { start_after, limit }
+            }
+            QueryMsg::MarketingInfo {} => Cw20QueryMsg::MarketingInfo {},
+            QueryMsg::DownloadLogo {} => Cw20QueryMsg::DownloadLogo {},
+            query @ (QueryMsg::BalanceAt { .. }
+            | QueryMsg::TotalSupplyAt { .. }
+            | QueryMsg::BalanceHooks {}
+            | QueryMsg::PermitNonce { .. }) => return Err(query),
+        })
+    }
+}
@@ -12,7 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use cosmwasm_std::CanonicalAddr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, CanonicalAddr, Uint128};
+use cw_storage_plus::{Item, Map};
 
 pub const HUB_CONTRACT: Item<CanonicalAddr> = Item::new("hub_contract");
+
+/// The cw20-base balance of an account before its first change at a height.
+/// Only the changed accounts are recorded, once per block; the later values are
+/// read from the next record or the current balance.
+pub const BALANCE_CHANGELOG: Map<(&Addr, u64), Uint128> = Map::new("balance_changelog");
+
+/// The cw20-base total supply before its first change at a height
+pub const TOTAL_SUPPLY_CHANGELOG: Map<u64, Uint128> = Map::new("total_supply_changelog");
@@ -0,0 +1,212 @@
+// Copyright 2021 Lido
+//
+// Licensedicensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use basset::hub::ConfigResponse;
+use basset::token::TotalSupplyResponse;
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    from_json, to_json_binary, Binary, ContractResult, Env, Order, OwnedDeps, Storage,
+    SystemResult, Uint128,
+};
+use cw20::{BalanceResponse, Cw20Coin};
+use cw20_base::msg::InstantiateMarketingInfo;
+
+use crate::contract::{execute, instantiate, query};
+use crate::msg::{ExecuteMsg, QueryMsg, TokenInitMsg};
+
+const HUB: &str = "hub";
+const OLD_HOLDER: &str = "addr0000";
+
+fn init_msg(initial_balances: Vec<Cw20Coin>) -> TokenInitMsg {
+    TokenInitMsg {
+        name: "stsei".to_string(),
+        symbol: "STSEI".to_string(),
+        decimals: 6,
+        initial_balances,
+        hub_contract: HUB.to_string(),
+        marketing: Some(InstantiateMarketingInfo {
+            project: None,
+            description: None,
+            marketing: Some("marketing".to_string()),
+            logo: None,
+        }),
+    }
+}
+
+// the hub answers its config, for the owner managing the balance hooks
+fn dependencies() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    deps.querier.update_wasm(|_| {
+        let config = ConfigResponse {
+            owner: "owner1".to_string(),
+            update_reward_index_addr: "update_reward_index_addr".to_string(),
+            reward_dispatcher_contract: None,
+            validators_registry_contract: None,
+            bsei_token_contract: None,
+            stsei_token_contract: Some(MOCK_CONTRACT_ADDR.to_string()),
+            airdrop_registry_contract: None,
+            insurance_fund_contract: None,
+            rewards_contract: None,
+            token_contract: None,
+        };
+        SystemResult::Ok(ContractResult::from(to_json_binary(&config)))
+    });
+    deps
+}
+
+fn init() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = dependencies();
+    let msg = init_msg(vec![Cw20Coin {
+        address: OLD_HOLDER.to_string(),
+        amount: Uint128::new(1000),
+    }]);
+    instantiate(deps.as_mut(), mock_env(), mock_info(HUB, &[]), msg).unwrap();
+    deps
+}
+
+fn balance_at(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    address: &str,
+    height: u64,
+) -> u128 {
+    let msg = QueryMsg::BalanceAt {
+        address: address.to_string(),
+        height,
+    };
+    let res: BalanceResponse = from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    res.balance.u128()
+}
+
+fn total_supply_at(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, height: u64) -> u128 {
+    let msg = QueryMsg::TotalSupplyAt { height };
+    let res: TotalSupplyResponse =
+        from_json(query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    res.total_supply.u128()
+}
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+#[test]
+fn balance_snapshots() {
+    let mut deps = init();
+    let addr1 = String::from("addr0001");
+    let addr2 = String::from("addr0002");
+    let spender = String::from("spender");
+    let start = mock_env().block.height;
+
+    // nothing is recorded before the first change
+    assert_eq!(total_supply_at(&deps, start), 1000);
+    assert_eq!(balance_at(&deps, OLD_HOLDER, start), 1000);
+
+    let mint = ExecuteMsg::Mint {
+        recipient: addr1.clone(),
+        amount: Uint128::new(100),
+    };
+    execute(deps.as_mut(), env_at(start + 1), mock_info(HUB, &[]), mint).unwrap();
+
+    let transfer = ExecuteMsg::Transfer {
+        recipient: addr2.clone(),
+        amount: Uint128::new(30),
+    };
+    execute(deps.as_mut(), env_at(start + 2), mock_info(&addr1, &[]), transfer).unwrap();
+    // the balance of the holder predates its first change
+    let send = ExecuteMsg::Send {
+        contract: HUB.to_string(),
+        amount: Uint128::new(200),
+        msg: Binary::from(br#"{"unbond":{}}"#.as_slice()),
+    };
+    execute(deps.as_mut(), env_at(start + 2), mock_info(OLD_HOLDER, &[]), send).unwrap();
+
+    let burn = ExecuteMsg::Burn {
+        amount: Uint128::new(200),
+    };
+    execute(deps.as_mut(), env_at(start + 3), mock_info(HUB, &[]), burn).unwrap();
+    let allowance = ExecuteMsg::IncreaseAllowance {
+        spender: spender.clone(),
+        amount: Uint128::new(20),
+        expires: None,
+    };
+    execute(deps.as_mut(), env_at(start + 3), mock_info(&addr1, &[]), allowance).unwrap();
+    let burn_from = ExecuteMsg::BurnFrom {
+        owner: addr1.clone(),
+        amount: Uint128::new(20),
+    };
+    execute(deps.as_mut(), env_at(start + 3), mock_info(&spender, &[]), burn_from).unwrap();
+
+    assert_eq!(balance_at(&deps, &addr1, start), 0);
+    assert_eq!(balance_at(&deps, &addr1, start + 1), 0);
+    assert_eq!(balance_at(&deps, &addr1, start + 2), 100);
+    assert_eq!(balance_at(&deps, &addr1, start + 3), 70);
+    assert_eq!(balance_at(&deps, &addr1, start + 4), 50);
+    assert_eq!(balance_at(&deps, &addr2, start + 2), 0);
+    assert_eq!(balance_at(&deps, &addr2, start + 3), 30);
+    assert_eq!(balance_at(&deps, OLD_HOLDER, start), 1000);
+    assert_eq!(balance_at(&deps, OLD_HOLDER, start + 2), 1000);
+    assert_eq!(balance_at(&deps, OLD_HOLDER, start + 3), 800);
+    assert_eq!(balance_at(&deps, HUB, start + 2), 0);
+    assert_eq!(balance_at(&deps, HUB, start + 3), 200);
+    assert_eq!(balance_at(&deps, HUB, start + 4), 0);
+
+    assert_eq!(total_supply_at(&deps, start), 1000);
+    assert_eq!(total_supply_at(&deps, start + 1), 1000);
+    assert_eq!(total_supply_at(&deps, start + 2), 1100);
+    assert_eq!(total_supply_at(&deps, start + 3), 1100);
+    assert_eq!(total_supply_at(&deps, start + 4), 880);
+}
+
+#[test]
+fn balance_snapshots_storage() {
+    let mut deps = init();
+    let addr1 = String::from("addr0001");
+    let start = mock_env().block.height;
+    let entries = |storage: &dyn Storage| storage.range(None, None, Order::Ascending).count();
+
+    let transfer = ExecuteMsg::Transfer {
+        recipient: addr1.clone(),
+        amount: Uint128::new(10),
+    };
+    let info = mock_info(OLD_HOLDER, &[]);
+
+    // a transfer records each account once per block, besides the new balance of addr1
+    let before = entries(&deps.storage);
+    execute(deps.as_mut(), env_at(start + 1), info.clone(), transfer.clone()).unwrap();
+    assert_eq!(entries(&deps.storage) - before, 3);
+
+    let before = entries(&deps.storage);
+    execute(deps.as_mut(), env_at(start + 1), info.clone(), transfer.clone()).unwrap();
+    assert_eq!(entries(&deps.storage) - before, 0);
+
+    let before = entries(&deps.storage);
+    execute(deps.as_mut(), env_at(start + 2), info, transfer).unwrap();
+    assert_eq!(entries(&deps.storage) - before, 2);
+}
+
+lst_token::contract_tests! {
+    instantiate: crate::contract::instantiate,
+    execute: crate::contract::execute,
+    query: crate::contract::query,
+    reply: crate::contract::reply,
+    execute_msg: crate::msg::ExecuteMsg,
+    query_msg: crate::msg::QueryMsg,
+    init_msg: init_msg,
+    deps: dependencies(),
+    hub: HUB,
+    reward_contract: None,
+}
@@ -10,14 +10,10 @@ pub struct InstantiateMsg {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-#[allow(clippy::upper_case_acronyms)]
 pub enum ExecuteMsg {
-    FabricateMIRClaim {
-        stage: u8,
-        amount: Uint128,
-        proof: Vec<String>,
-    },
-    FabricateANCClaim {
+    /// Claim the `amount` of the stage of the `airdrop_token` airdrop through the hub
+    FabricateClaim {
+        airdrop_token: String,
         stage: u8,
         amount: Uint128,
         proof: Vec<String>,
@@ -51,21 +47,10 @@ pub enum QueryMsg {
     },
 }
 
+/// The claim message of a merkle airdrop contract
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-#[allow(clippy::upper_case_acronyms)]
-pub enum MIRAirdropHandleMsg {
-    Claim {
-        stage: u8,
-        amount: Uint128,
-        proof: Vec<String>,
-    },
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-#[allow(clippy::upper_case_acronyms)]
-pub enum ANCAirdropHandleMsg {
+pub enum AirdropHandleMsg {
     Claim {
         stage: u8,
         amount: Uint128,
@@ -1,5 +1,7 @@
 use cosmwasm_std::Decimal;
 
+use crate::oracle::OracleConfig;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -16,9 +18,54 @@ pub struct ConfigResponse {
     pub swap_contract: String,
     pub swap_denoms: Vec<String>,
     pub oracle_contract: String,
+    pub exchange_rate_source: Option<ExchangeRateSource>,
+    pub oracle: Option<OracleConfig>,
+}
+
+/// Where the exchange rate between the two reward denoms is read from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExchangeRateSource {
+    /// `QueryExchangeRateByAssetLabel` of the Pyth oracle contract
+    #[default]
+    Pyth,
+    /// The exchange rates of the Sei oracle module
+    SeiOracle,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct NewOwnerResponse {
     pub new_owner: String,
 }
+
+/// What the rewards dispatcher does with a share of the rewards of a denom.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RewardAction {
+    /// Send them to `recipient`
+    Send { recipient: String },
+    /// Bond them to the hub, for the stSei holders
+    Rebond {},
+    /// Swap them to `ask_denom` through the swap contract, which pays `recipient`
+    SwapThenSend { ask_denom: String, recipient: String },
+    /// Burn them
+    Burn {},
+}
+
+/// A share of the rewards of a denom left after the keeper fee, and what is done with it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardRoute {
+    pub share: Decimal,
+    pub action: RewardAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DenomRewardRoutes {
+    pub denom: String,
+    pub routes: Vec<RewardRoute>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardRoutesResponse {
+    pub routes: Vec<DenomRewardRoutes>,
+}
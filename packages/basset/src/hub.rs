@@ -1,4 +1,7 @@
-use cosmwasm_std::{Binary, CanonicalAddr, Coin, Decimal, Uint128};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CanonicalAddr, Coin, CosmosMsg, Decimal, StdResult, Uint128,
+    VoteOption, WasmMsg, WeightedVoteOption,
+};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -10,6 +13,133 @@ pub enum BondType {
     BondRewards,
 }
 
+/// The name of bSei as a token class built into the hub.
+pub const BSEI_CLASS: &str = "bsei";
+/// The name of stSei as a token class built into the hub.
+pub const STSEI_CLASS: &str = "stsei";
+
+/// How the staking rewards of a token class are paid out.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RewardPolicy {
+    /// The rewards are paid to the holders by the reward dispatcher, like bSei.
+    /// Only the built-in bSei class has this policy.
+    Dispatched,
+    /// The rewards are bonded again and raise the exchange rate, like stSei.
+    Rebase,
+    /// The rewards are sent to `reward_contract`, which pays them to the holders.
+    RewardBearing { reward_contract: String },
+}
+
+/// A liquid staking token issued by the hub. bSei and stSei are built-in classes,
+/// whose amounts are kept in `State` and `CurrentBatch`; the others are registered.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenClass {
+    pub name: String,
+    pub token_contract: Addr,
+    pub reward_policy: RewardPolicy,
+    /// Taken on bond and unbond while the exchange rate is below `er_threshold`
+    pub peg_recovery_fee: Decimal,
+    pub er_threshold: Decimal,
+    pub exchange_rate: Decimal,
+    pub total_bond_amount: Uint128,
+    /// The amount requested in the current batch, after the peg recovery fee
+    pub requested_amount: Uint128,
+}
+
+impl TokenClass {
+    pub fn update_exchange_rate(&mut self, total_issued: Uint128) {
+        let actual_supply = total_issued + self.requested_amount;
+        if self.total_bond_amount.is_zero() || actual_supply.is_zero() {
+            self.exchange_rate = Decimal::one()
+        } else {
+            self.exchange_rate = Decimal::from_ratio(self.total_bond_amount, actual_supply);
+        }
+    }
+}
+
+/// The amount of a token class in an unbond request.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClassUnbondAmount {
+    pub class: String,
+    pub amount: Uint128,
+}
+
+/// The part of an unbond batch that belongs to a token class.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClassUnbondHistory {
+    pub class: String,
+    pub amount: Uint128,
+    pub applied_exchange_rate: Decimal,
+    pub withdraw_rate: Decimal,
+}
+
+/// One of the two tokens issued by the hub.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum TokenType {
+    #[serde(rename = "bsei")]
+    BSei,
+    #[serde(rename = "stsei")]
+    StSei,
+}
+
+/// The direction of a conversion between the two tokens.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ConvertDirection {
+    #[serde(rename = "bsei_to_stsei")]
+    BSeiToStSei,
+    #[serde(rename = "stsei_to_bsei")]
+    StSeiToBSei,
+}
+
+impl From<TokenType> for BondType {
+    fn from(token_type: TokenType) -> Self {
+        match token_type {
+            TokenType::BSei => BondType::BSei,
+            TokenType::StSei => BondType::StSei,
+        }
+    }
+}
+
+/// UnbondedReceiveMsg should be de/serialized under `ReceiveUnbonded()` variant in a ExecuteMsg.
+/// The withdrawn coins are attached to the message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondedReceiveMsg {
+    pub sender: String,
+    pub amount: Uint128,
+    pub msg: Binary,
+}
+
+impl UnbondedReceiveMsg {
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        let msg = UnbondedReceiverExecuteMsg::ReceiveUnbonded(self);
+        to_json_binary(&msg)
+    }
+
+    /// creates a cosmos_msg sending this struct along with the coins to the named contract
+    pub fn into_cosmos_msg<T: Into<String>>(
+        self,
+        contract_addr: T,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        let msg = self.into_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds,
+        };
+        Ok(execute.into())
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum UnbondedReceiverExecuteMsg {
+    ReceiveUnbonded(UnbondedReceiveMsg),
+}
+
 pub type UnbondRequest = Vec<(u64, Uint128, Uint128)>;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -33,6 +163,8 @@ pub struct State {
     pub prev_hub_balance: Uint128,
     pub last_unbonded_time: u64,
     pub last_processed_batch: u64,
+    /// The Sei chain epoch of the last undelegation, kept with `EpochSource::SeiEpoch`.
+    pub last_unbonded_epoch: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
@@ -56,6 +188,7 @@ pub struct Config {
     pub stsei_token_contract: Option<CanonicalAddr>,
     pub airdrop_registry_contract: Option<CanonicalAddr>,
     pub rewards_contract: Option<CanonicalAddr>,
+    pub insurance_fund_contract: Option<CanonicalAddr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -108,6 +241,7 @@ pub enum ExecuteMsg {
         airdrop_registry_contract: Option<String>,
         rewards_contract: Option<String>,
         update_reward_index_addr: Option<String>,
+        insurance_fund_contract: Option<String>,
     },
 
     /// update the parameters that is needed for the contract
@@ -118,6 +252,10 @@ pub enum ExecuteMsg {
         er_threshold: Option<Decimal>,
         paused: Option<bool>,
         reward_denom: Option<String>,
+        max_unbond_requests: Option<u32>,
+        keeper_fee: Option<Decimal>,
+        epoch_source: Option<EpochSource>,
+        insurance_draw_cap: Option<Uint128>,
     },
 
     SetOwner {
@@ -127,6 +265,23 @@ pub enum ExecuteMsg {
     AcceptOwnership {
     },
 
+    /// Register a liquid staking token class besides bSei and stSei.
+    /// The hub must be the minter of `token_contract`.
+    RegisterTokenClass {
+        name: String,
+        token_contract: String,
+        reward_policy: RewardPolicy,
+        peg_recovery_fee: Decimal,
+        er_threshold: Decimal,
+    },
+
+    /// Update the fee settings of a registered token class.
+    UpdateTokenClass {
+        name: String,
+        peg_recovery_fee: Option<Decimal>,
+        er_threshold: Option<Decimal>,
+    },
+
     ////////////////////
     /// User's operations
     ////////////////////
@@ -134,9 +289,30 @@ pub enum ExecuteMsg {
     /// Receives `amount` in underlying coin denom from sender.
     /// Delegate `amount` equally between validators from the registry.
     /// Issue `amount` / exchange_rate for the user.
-    Bond {},
+    /// The tokens are minted to `recipient` (the sender by default). If `callback`
+    /// is set, the tokens are sent to the recipient contract with `Cw20ExecuteMsg::Send`
+    /// and `callback` as the hook message, so the contract learns the minted amount.
+    /// Fails if less than `min_mint_amount` tokens would be minted.
+    Bond {
+        recipient: Option<String>,
+        callback: Option<Binary>,
+        min_mint_amount: Option<Uint128>,
+    },
+
+    /// Same as `Bond`, but issues stSei.
+    BondForStSei {
+        recipient: Option<String>,
+        callback: Option<Binary>,
+        min_mint_amount: Option<Uint128>,
+    },
 
-    BondForStSei {},
+    /// Same as `Bond`, but issues the tokens of the registered `class`.
+    BondForClass {
+        class: String,
+        recipient: Option<String>,
+        callback: Option<Binary>,
+        min_mint_amount: Option<Uint128>,
+    },
 
     BondRewards {},
 
@@ -145,12 +321,71 @@ pub enum ExecuteMsg {
         airdrop_hooks: Option<Vec<Binary>>,
     },
 
-    /// Send back unbonded coin to the user
-    WithdrawUnbonded {},
+    /// Send back unbonded coin to the user.
+    /// At most `limit` released batches are withdrawn per call; `batch_ids`
+    /// restricts the withdrawal to the given batches.
+    /// The coins go to `recipient` (the sender by default). If `msg` is set, the
+    /// recipient contract is called with `ReceiveUnbonded` and the coins attached.
+    /// If `rebond_as` is set, the coins are bonded again and the minted tokens go
+    /// to the recipient.
+    WithdrawUnbonded {
+        limit: Option<u32>,
+        batch_ids: Option<Vec<u64>>,
+        recipient: Option<String>,
+        msg: Option<Binary>,
+        rebond_as: Option<TokenType>,
+    },
 
-    /// Check whether the slashing has happened or not
+    /// Release the matured unbond batches, visiting at most `limit` of them.
+    /// Can be called by anyone; a long backlog is processed over several calls.
+    ProcessReleasedBatches {
+        limit: Option<u32>,
+    },
+
+    /// Send the released funds of a batch to its users, at most `limit` of them.
+    /// Can be called by anyone; the caller receives the keeper fee as a tip.
+    DistributeReleased {
+        batch_id: u64,
+        limit: Option<u32>,
+    },
+
+    /// Undelegate the current batch once the epoch period has passed.
+    /// Can be called by anyone, so the requests do not wait for the next unbond.
+    ProcessBatch {},
+
+    /// Check whether the slashing has happened or not.
+    /// A new slashing loss is drawn from the insurance fund, if any.
     CheckSlashing {},
 
+    /// Rebond the coins drawn from the insurance fund to restore the exchange rates.
+    /// Only the insurance fund can call it.
+    RestoreBond {},
+
+    ////////////////////
+    /// governance
+    ///////////////////
+
+    /// Open the holders' voting on a chain governance proposal until `voting_end`.
+    /// The voting power is snapshotted at the registration block.
+    /// Only the owner can register proposals.
+    RegisterProposal {
+        proposal_id: u64,
+        voting_end: u64,
+    },
+
+    /// Vote on a registered proposal with the bonded value of the sender's bSei
+    /// and stSei at the snapshot. Voting again replaces the previous vote.
+    VoteProposal {
+        proposal_id: u64,
+        options: Vec<WeightedVoteOption>,
+    },
+
+    /// Cast the aggregated vote of the holders once the voting has ended.
+    /// Can be called by anyone.
+    CastProposalVote {
+        proposal_id: u64,
+    },
+
     ////////////////////
     /// bAsset's operations
     ///////////////////
@@ -165,13 +400,13 @@ pub enum ExecuteMsg {
     ///////////////////
     ClaimAirdrop {
         airdrop_token_contract: String,
-        // Contract address of MIR Cw20 Token
+        // Contract address of the airdrop Cw20 Token
         airdrop_contract: String,
-        // Contract address of MIR Airdrop
+        // Contract address of the Airdrop
         airdrop_swap_contract: String,
-        // E.g. Contract address of MIR <> UST Terraswap Pair
+        // E.g. Contract address of the airdrop token <> SEI Pair
         claim_msg: Binary,
-        // Base64-encoded JSON of MIRAirdropHandleMsg::Claim
+        // Base64-encoded JSON of AirdropHandleMsg::Claim
         swap_msg: Binary, // Base64-encoded string of JSON of PairHandleMsg::Swap
     },
 
@@ -195,13 +430,22 @@ pub enum ExecuteMsg {
     MigrateUnbondWaitList {
         limit: Option<u32>,
     },
+
+    // IndexUnbondWaitLists adds a limited amount of the existing waitlist entries
+    // to the batch and user indexes, starting after the `start_after` raw key.
+    IndexUnbondWaitLists {
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
-    Unbond {},
-    Convert {},
+    /// Fails if the request is worth less than `min_unbond_amount` of the underlying coin.
+    Unbond { min_unbond_amount: Option<Uint128> },
+    /// Fails if less than `min_mint_amount` tokens of the other kind would be minted.
+    Convert { min_mint_amount: Option<Uint128> },
     // UpdateGlobalIndex {
     //     airdrop_hooks: Option<Vec<Binary>>,
     // },
@@ -216,6 +460,24 @@ pub struct Parameters {
     pub er_threshold: Decimal,
     pub reward_denom: String,
     pub paused: Option<bool>,
+    /// Maximum number of open unbond requests per address.
+    pub max_unbond_requests: Option<u32>,
+    /// Share of the distributed funds paid to the caller of `DistributeReleased`.
+    pub keeper_fee: Option<Decimal>,
+    /// How the end of an unbond batch is measured; defaults to `EpochSource::BlockTime`.
+    pub epoch_source: Option<EpochSource>,
+    /// The most drawn from the insurance fund for a slashing; the whole loss by default.
+    pub insurance_draw_cap: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EpochSource {
+    /// A batch is closed once `epoch_period` seconds of block time have passed.
+    BlockTime,
+    /// A batch is closed on the first Sei chain epoch that starts `epoch_period`
+    /// seconds after the last undelegation, read from the epoch module.
+    SeiEpoch,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -244,6 +506,66 @@ pub struct UnbondHistory {
     pub stsei_withdraw_rate: Decimal,
 
     pub released: bool,
+
+    /// The registered token classes that were unbonded in the batch
+    pub classes: Option<Vec<ClassUnbondHistory>>,
+}
+
+impl UnbondHistory {
+    /// The amount of the underlying coin released for a request of this batch.
+    pub fn withdraw_amount(&self, request: &UnbondWaitEntity) -> Uint128 {
+        let histories = self.class_histories();
+        let mut amount = Uint128::zero();
+        for requested in request.class_amounts() {
+            if let Some(class) = histories.iter().find(|c| c.class == requested.class) {
+                amount += requested.amount * class.withdraw_rate;
+            }
+        }
+        amount
+    }
+
+    /// The part of the batch of each token class, bSei and stSei first.
+    pub fn class_histories(&self) -> Vec<ClassUnbondHistory> {
+        let mut histories = vec![
+            ClassUnbondHistory {
+                class: BSEI_CLASS.to_string(),
+                amount: self.bsei_amount,
+                applied_exchange_rate: self.bsei_applied_exchange_rate,
+                withdraw_rate: self.bsei_withdraw_rate,
+            },
+            ClassUnbondHistory {
+                class: STSEI_CLASS.to_string(),
+                amount: self.stsei_amount,
+                applied_exchange_rate: self.stsei_applied_exchange_rate,
+                withdraw_rate: self.stsei_withdraw_rate,
+            },
+        ];
+        histories.extend(self.classes.iter().flatten().cloned());
+        histories
+    }
+
+    /// Set the part of the batch of a token class.
+    pub fn set_class_history(&mut self, history: ClassUnbondHistory) {
+        match history.class.as_str() {
+            BSEI_CLASS => {
+                self.bsei_amount = history.amount;
+                self.bsei_applied_exchange_rate = history.applied_exchange_rate;
+                self.bsei_withdraw_rate = history.withdraw_rate;
+            }
+            STSEI_CLASS => {
+                self.stsei_amount = history.amount;
+                self.stsei_applied_exchange_rate = history.applied_exchange_rate;
+                self.stsei_withdraw_rate = history.withdraw_rate;
+            }
+            _ => {
+                let classes = self.classes.get_or_insert_with(Vec::new);
+                match classes.iter_mut().find(|c| c.class == history.class) {
+                    Some(class) => *class = history,
+                    None => classes.push(history),
+                }
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -259,6 +581,7 @@ pub struct UnbondHistoryResponse {
     pub stsei_withdraw_rate: Decimal,
 
     pub released: bool,
+    pub classes: Option<Vec<ClassUnbondHistory>>,
 
     // #[deprecated]
     pub amount: Uint128,
@@ -272,11 +595,32 @@ pub struct UnbondHistoryResponse {
 pub struct UnbondWaitEntity {
     pub bsei_amount: Uint128,
     pub stsei_amount: Uint128,
+    pub classes: Option<Vec<ClassUnbondAmount>>,
+}
+
+impl UnbondWaitEntity {
+    /// The requested amount of each token class, bSei and stSei first.
+    pub fn class_amounts(&self) -> Vec<ClassUnbondAmount> {
+        let mut amounts = vec![
+            ClassUnbondAmount {
+                class: BSEI_CLASS.to_string(),
+                amount: self.bsei_amount,
+            },
+            ClassUnbondAmount {
+                class: STSEI_CLASS.to_string(),
+                amount: self.stsei_amount,
+            },
+        ];
+        amounts.extend(self.classes.iter().flatten().cloned());
+        amounts
+    }
 }
 
 pub enum UnbondType {
     BSei,
     StSei,
+    /// A registered token class, by name
+    Class(String),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -305,6 +649,8 @@ pub struct ConfigResponse {
     pub bsei_token_contract: Option<String>,
     pub stsei_token_contract: Option<String>,
     pub airdrop_registry_contract: Option<String>,
+    pub insurance_fund_contract: Option<String>,
+    pub rewards_contract: Option<String>,
 
     // #[deprecated]
     pub token_contract: Option<String>,
@@ -320,9 +666,19 @@ pub struct CurrentBatchResponse {
     pub requested_with_fee: Uint128,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NextBatchTimeResponse {
+    pub batch_id: u64,
+    /// The earliest time the batch can be undelegated, in seconds.
+    /// Estimated from the epoch duration with `EpochSource::SeiEpoch`.
+    pub next_batch_time: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct WithdrawableUnbondedResponse {
     pub withdrawable: Uint128,
+    /// Batch id to pass as `start_after` for the next page, if any.
+    pub next_start_after: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -331,6 +687,53 @@ pub struct UnbondRequestsResponse {
     pub requests: UnbondRequest,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchRequest {
+    pub address: String,
+    pub bsei_amount: Uint128,
+    pub stsei_amount: Uint128,
+    pub classes: Option<Vec<ClassUnbondAmount>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchRequestsResponse {
+    pub batch_id: u64,
+    pub requests: Vec<BatchRequest>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnbondRequestStatus {
+    /// The batch waits for the end of the epoch to be undelegated
+    PendingEpoch,
+    /// The batch has been undelegated
+    Unbonding,
+    /// The coins of the batch can be withdrawn
+    Released,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondRequestDetails {
+    pub batch_id: u64,
+    pub status: UnbondRequestStatus,
+    pub bsei_amount: Uint128,
+    pub stsei_amount: Uint128,
+    pub classes: Option<Vec<ClassUnbondAmount>>,
+    /// The undelegation time plus the unbonding period, estimated for a pending batch
+    pub expected_release_time: u64,
+    /// The exchange rates applied to the batch, the current ones for a pending batch
+    pub bsei_exchange_rate: Decimal,
+    pub stsei_exchange_rate: Decimal,
+    /// The expected amount of the underlying coin, the final one once released
+    pub estimated_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondRequestsDetailedResponse {
+    pub address: String,
+    pub requests: Vec<UnbondRequestDetails>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct AllHistoryResponse {
     pub history: Vec<UnbondHistoryResponse>,
@@ -350,18 +753,208 @@ pub enum QueryMsg {
     Config {},
     State {},
     CurrentBatch {},
+    /// When the current batch can be undelegated.
+    NextBatchTime {},
     WithdrawableUnbonded {
         address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     },
     Parameters {},
     UnbondRequests {
         address: String,
     },
+    /// The users that have a request in the batch, ordered by address.
+    BatchRequests {
+        batch_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The unbond requests of `address` along with their status and expected outcome,
+    /// paged in batch id order.
+    UnbondRequestsDetailed {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
     AllHistory {
         start_from: Option<u64>,
         limit: Option<u32>,
     },
     NewOwner {},
+    /// The registered token classes, ordered by name.
+    TokenClasses {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The vote of `voter` on a registered proposal.
+    ProposalVote {
+        proposal_id: u64,
+        voter: String,
+    },
+    /// The aggregated vote of the holders on a registered proposal.
+    ProposalTally {
+        proposal_id: u64,
+    },
+    /// The recorded slashing events, ordered by id.
+    SlashingEvents {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// The slashing of an unbond batch.
+    BatchSlashing {
+        batch_id: u64,
+    },
+    /// The draws from the insurance fund, ordered by id.
+    InsuranceDraws {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Previews a `Bond` or `BondForStSei` of `amount` underlying coins.
+    SimulateBond {
+        bond_type: TokenType,
+        amount: Uint128,
+    },
+    /// Previews an unbond of `amount` tokens.
+    SimulateUnbond {
+        token: TokenType,
+        amount: Uint128,
+    },
+    /// Previews a conversion of `amount` tokens.
+    SimulateConvert {
+        direction: ConvertDirection,
+        amount: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenClassesResponse {
+    pub classes: Vec<TokenClass>,
+}
+
+/// How a slashing was noticed by the hub.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashingSource {
+    /// The delegations of the hub fell below the bonded amount.
+    Delegations,
+    /// The released batches received less than they had unbonded.
+    Unbonding,
+}
+
+/// A slashing recorded by the hub.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SlashingEvent {
+    pub id: u64,
+    pub time: u64,
+    pub source: SlashingSource,
+    /// The bonded amount, or the amount expected from the batches, before the slashing
+    pub amount_before: Uint128,
+    pub amount_after: Uint128,
+    pub bsei_slashed: Uint128,
+    pub stsei_slashed: Uint128,
+    /// The amount slashed from the registered token classes
+    pub classes_slashed: Uint128,
+    /// The range of the batches that were still unbonding, or of the released ones;
+    /// `None` when no batch was affected
+    pub first_batch: Option<u64>,
+    pub last_batch: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SlashingEventsResponse {
+    pub events: Vec<SlashingEvent>,
+}
+
+/// The coins drawn from the insurance fund and rebonded by the hub.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InsuranceDraw {
+    pub id: u64,
+    pub time: u64,
+    pub amount: Uint128,
+    /// The exchange rates once the coins are rebonded
+    pub bsei_exchange_rate: Decimal,
+    pub stsei_exchange_rate: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InsuranceDrawsResponse {
+    /// The part of the last claim that the fund has not covered; it is not drawn again
+    pub uncovered_claim: Uint128,
+    pub draws: Vec<InsuranceDraw>,
+}
+
+/// The slashing of an unbond batch, from its applied and final withdraw rates.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BatchSlashingResponse {
+    pub batch_id: u64,
+    pub released: bool,
+    /// The amounts expected at the exchange rates applied when the batch was undelegated
+    pub bsei_expected_amount: Uint128,
+    pub stsei_expected_amount: Uint128,
+    /// The amounts at the withdraw rates; equal to the expected ones until the release
+    pub bsei_released_amount: Uint128,
+    pub stsei_released_amount: Uint128,
+    /// The total slashed amount of the batch, including the token classes
+    pub slashed_amount: Uint128,
+}
+
+/// The voting power given to an option of a proposal.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OptionPower {
+    pub option: VoteOption,
+    pub power: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalVoteResponse {
+    pub proposal_id: u64,
+    pub voter: String,
+    /// The bonded value the vote was made with, zero if `voter` has not voted
+    pub power: Uint128,
+    pub options: Vec<WeightedVoteOption>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalTallyResponse {
+    pub proposal_id: u64,
+    pub voting_end: u64,
+    /// The block at the start of which the voting power is measured
+    pub snapshot_height: u64,
+    /// Whether the aggregated vote has been cast
+    pub cast: bool,
+    pub total_power: Uint128,
+    pub tally: Vec<OptionPower>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateBondResponse {
+    pub mint_amount: Uint128,
+    /// The peg recovery fee, in minted tokens
+    pub peg_fee: Uint128,
+    pub exchange_rate: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateUnbondResponse {
+    /// The amount of tokens that is unbonded after the peg recovery fee
+    pub unbond_amount: Uint128,
+    /// The peg recovery fee, in burnt tokens
+    pub peg_fee: Uint128,
+    /// The estimated amount of the underlying coin, before any slashing
+    pub underlying_amount: Uint128,
+    pub exchange_rate: Decimal,
+    /// The earliest time the coins can be withdrawn, in seconds
+    pub estimated_release_time: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateConvertResponse {
+    pub mint_amount: Uint128,
+    /// The peg recovery fee, in bSei
+    pub peg_fee: Uint128,
+    /// The amount of the underlying coin moved from one token to the other
+    pub underlying_amount: Uint128,
 }
 
 
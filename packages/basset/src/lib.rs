@@ -6,6 +6,8 @@ pub mod contract_error;
 pub mod hub;
 pub mod reward;
 pub mod swap_ext;
+pub mod token;
+pub mod oracle;
 pub mod oracle_pyth;
 pub mod dispatcher;
 pub mod handle;
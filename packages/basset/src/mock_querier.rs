@@ -125,6 +125,7 @@ impl WasmMockQuerier {
                         rewards_contract: Some(
                             api.addr_canonicalize(&String::from("rewards")).unwrap(),
                         ),
+                        insurance_fund_contract: None,
                     };
                     SystemResult::Ok(ContractResult::from(to_json_binary(&config)))
                 } else {
@@ -0,0 +1,252 @@
+use cosmwasm_std::{
+    to_json_binary, Api, Decimal, QuerierWrapper, QueryRequest, StdError, StdResult, WasmQuery,
+};
+use schemars::JsonSchema;
+use sei_cosmwasm::{SeiQuerier, SeiQueryWrapper};
+use serde::{Deserialize, Serialize};
+
+use crate::oracle_pyth::{PriceFeedQueryMsg, PriceFeedResponse, PythPrice, QueryMsg};
+
+/// A backend an exchange rate can be read from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleSource {
+    /// `QueryExchangeRateByAssetLabel` of a Pyth adapter contract.
+    /// The adapter reports no publish time or confidence interval.
+    PythAdapter { contract: String },
+    /// The price feeds of the Pyth contract, both quoted in the same currency.
+    Pyth {
+        contract: String,
+        base_price_id: String,
+        quote_price_id: String,
+    },
+    /// The exchange rates of the Sei oracle module.
+    SeiNative {},
+    /// A rate set by the admin.
+    Fixed { rate: Decimal },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OracleConfig {
+    pub primary: OracleSource,
+    /// Used when the primary price is unavailable or fails a check
+    pub secondary: Option<OracleSource>,
+    /// The maximum age of a price, in seconds
+    pub max_age: Option<u64>,
+    /// The maximum confidence interval, relative to the price
+    pub max_confidence: Option<Decimal>,
+    /// The maximum difference between the primary and the secondary price,
+    /// relative to the secondary price
+    pub max_deviation: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OraclePrice {
+    pub rate: Decimal,
+    /// The time the price was published, in seconds
+    pub publish_time: Option<u64>,
+    /// The confidence interval, relative to the rate
+    pub confidence: Option<Decimal>,
+}
+
+impl OracleConfig {
+    /// Legacy configuration: a single source without any check.
+    pub fn single(source: OracleSource) -> Self {
+        OracleConfig {
+            primary: source,
+            secondary: None,
+            max_age: None,
+            max_confidence: None,
+            max_deviation: None,
+        }
+    }
+
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        self.primary.validate(api)?;
+        if let Some(secondary) = &self.secondary {
+            secondary.validate(api)?;
+        }
+        Ok(())
+    }
+}
+
+impl OracleSource {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        match self {
+            OracleSource::PythAdapter { contract } | OracleSource::Pyth { contract, .. } => {
+                api.addr_validate(contract)?;
+            }
+            OracleSource::SeiNative {} => {}
+            OracleSource::Fixed { rate } => {
+                if rate.is_zero() {
+                    return Err(StdError::generic_err("fixed oracle rate must not be zero"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the price of `base_denom` in `quote_denom` from the configured sources.
+/// The secondary source is used when the primary one fails, and when both are
+/// available they must not deviate by more than `max_deviation`.
+pub fn query_exchange_rate(
+    querier: &QuerierWrapper,
+    config: &OracleConfig,
+    base_denom: &str,
+    quote_denom: &str,
+    now: u64,
+) -> StdResult<Decimal> {
+    let primary =
+        query_checked_price(querier, config, &config.primary, base_denom, quote_denom, now);
+    let secondary = config
+        .secondary
+        .as_ref()
+        .map(|source| query_checked_price(querier, config, source, base_denom, quote_denom, now));
+
+    match (primary, secondary) {
+        (Ok(primary), Some(Ok(secondary))) => {
+            check_deviation(primary.rate, secondary.rate, config.max_deviation)?;
+            Ok(primary.rate)
+        }
+        (Ok(primary), _) => Ok(primary.rate),
+        (Err(_), Some(Ok(secondary))) => Ok(secondary.rate),
+        (Err(err), _) => Err(err),
+    }
+}
+
+/// Reads the price of one source and checks its age and confidence interval.
+pub fn query_checked_price(
+    querier: &QuerierWrapper,
+    config: &OracleConfig,
+    source: &OracleSource,
+    base_denom: &str,
+    quote_denom: &str,
+    now: u64,
+) -> StdResult<OraclePrice> {
+    let price = query_source_price(querier, source, base_denom, quote_denom)?;
+    if price.rate.is_zero() {
+        return Err(StdError::generic_err("oracle price is zero"));
+    }
+    if let (Some(max_age), Some(publish_time)) = (config.max_age, price.publish_time) {
+        if now.saturating_sub(publish_time) > max_age {
+            return Err(StdError::generic_err(format!(
+                "oracle price is stale: published at {}",
+                publish_time
+            )));
+        }
+    }
+    if let (Some(max_confidence), Some(confidence)) = (config.max_confidence, price.confidence) {
+        if confidence > max_confidence {
+            return Err(StdError::generic_err(format!(
+                "oracle confidence interval {} is too wide",
+                confidence
+            )));
+        }
+    }
+    Ok(price)
+}
+
+pub fn query_source_price(
+    querier: &QuerierWrapper,
+    source: &OracleSource,
+    base_denom: &str,
+    quote_denom: &str,
+) -> StdResult<OraclePrice> {
+    match source {
+        OracleSource::PythAdapter { contract } => {
+            let rate: Decimal = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: contract.to_string(),
+                msg: to_json_binary(&QueryMsg::QueryExchangeRateByAssetLabel {
+                    base_label: base_denom.to_string(),
+                    quote_label: quote_denom.to_string(),
+                })?,
+            }))?;
+            Ok(OraclePrice {
+                rate,
+                publish_time: None,
+                confidence: None,
+            })
+        }
+        OracleSource::Pyth {
+            contract,
+            base_price_id,
+            quote_price_id,
+        } => {
+            let base = query_pyth_price(querier, contract, base_price_id)?;
+            let quote = query_pyth_price(querier, contract, quote_price_id)?;
+            let (base_price, base_conf) = base.to_decimals()?;
+            let (quote_price, quote_conf) = quote.to_decimals()?;
+            Ok(OraclePrice {
+                rate: ratio(base_price, quote_price)?,
+                publish_time: Some(base.publish_time.min(quote.publish_time).max(0) as u64),
+                confidence: Some(ratio(base_conf, base_price)? + ratio(quote_conf, quote_price)?),
+            })
+        }
+        OracleSource::SeiNative {} => {
+            let sei_querier = QuerierWrapper::<SeiQueryWrapper>::new(&**querier);
+            let rates = SeiQuerier::new(&sei_querier).query_exchange_rates()?;
+            // every denom is quoted against the same base
+            let find = |denom: &str| {
+                rates
+                    .denom_oracle_exchange_rate_pairs
+                    .iter()
+                    .find(|pair| pair.denom == denom)
+                    .map(|pair| pair.oracle_exchange_rate.clone())
+                    .ok_or_else(|| {
+                        StdError::generic_err(format!("no oracle exchange rate for {}", denom))
+                    })
+            };
+            let (base, quote) = (find(base_denom)?, find(quote_denom)?);
+            // the oracle module stores the update time in milliseconds
+            let publish_time = base.last_update_timestamp.min(quote.last_update_timestamp) / 1000;
+            Ok(OraclePrice {
+                rate: ratio(base.exchange_rate, quote.exchange_rate)?,
+                publish_time: Some(publish_time),
+                confidence: None,
+            })
+        }
+        OracleSource::Fixed { rate } => Ok(OraclePrice {
+            rate: *rate,
+            publish_time: None,
+            confidence: None,
+        }),
+    }
+}
+
+fn query_pyth_price(querier: &QuerierWrapper, contract: &str, id: &str) -> StdResult<PythPrice> {
+    let res: PriceFeedResponse = querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: contract.to_string(),
+        msg: to_json_binary(&PriceFeedQueryMsg::PriceFeed { id: id.to_string() })?,
+    }))?;
+    Ok(res.price_feed.price)
+}
+
+fn check_deviation(
+    primary: Decimal,
+    secondary: Decimal,
+    max_deviation: Option<Decimal>,
+) -> StdResult<()> {
+    if let Some(max_deviation) = max_deviation {
+        let diff = if primary > secondary {
+            primary - secondary
+        } else {
+            secondary - primary
+        };
+        let deviation = ratio(diff, secondary)?;
+        if deviation > max_deviation {
+            return Err(StdError::generic_err(format!(
+                "oracle prices deviate by {}",
+                deviation
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn ratio(numerator: Decimal, denominator: Decimal) -> StdResult<Decimal> {
+    if denominator.is_zero() {
+        return Err(StdError::generic_err("oracle price is zero"));
+    }
+    Ok(Decimal::from_ratio(numerator.atomics(), denominator.atomics()))
+}
@@ -1,3 +1,4 @@
+use cosmwasm_std::{Decimal, Int64, StdError, StdResult, Uint64};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -6,3 +7,59 @@ use serde::{Deserialize, Serialize};
 pub enum QueryMsg {
     QueryExchangeRateByAssetLabel { base_label: String, quote_label: String },
 }
+
+/// Query of the Pyth price feed contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceFeedQueryMsg {
+    /// `id` is the hex encoded price feed identifier
+    PriceFeed { id: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceFeedResponse {
+    pub price_feed: PriceFeed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceFeed {
+    pub id: String,
+    pub price: PythPrice,
+    pub ema_price: PythPrice,
+}
+
+/// A Pyth price is `price * 10^expo` with a confidence interval of `conf * 10^expo`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PythPrice {
+    pub price: Int64,
+    pub conf: Uint64,
+    pub expo: i32,
+    /// Unix timestamp, in seconds
+    pub publish_time: i64,
+}
+
+impl PythPrice {
+    /// Returns the price and the confidence interval.
+    pub fn to_decimals(&self) -> StdResult<(Decimal, Decimal)> {
+        if self.price.i64() <= 0 {
+            return Err(StdError::generic_err("invalid pyth price"));
+        }
+        Ok((
+            scale(self.price.i64() as u128, self.expo)?,
+            scale(self.conf.u64() as u128, self.expo)?,
+        ))
+    }
+}
+
+fn scale(value: u128, expo: i32) -> StdResult<Decimal> {
+    if expo >= 0 {
+        let factor = 10u128
+            .checked_pow(expo as u32)
+            .and_then(|factor| factor.checked_mul(value))
+            .ok_or_else(|| StdError::generic_err("pyth price overflow"))?;
+        Ok(Decimal::from_ratio(factor, 1u128))
+    } else {
+        Decimal::from_atomics(value, expo.unsigned_abs())
+            .map_err(|err| StdError::generic_err(err.to_string()))
+    }
+}
@@ -60,6 +60,10 @@ pub enum ExecuteMsg {
         swap_denom: String,
         is_add: bool,
     },
+
+    /// Register the bSei token allowed to change the balances.
+    /// Only the hub is allowed to execute
+    UpdateTokenContract { token_contract: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
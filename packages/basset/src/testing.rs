@@ -40,3 +40,28 @@ fn test_deduct_tax() {
         }
     );
 }
+
+#[test]
+fn test_pyth_price_to_decimals() {
+    use crate::oracle_pyth::PythPrice;
+    use cosmwasm_std::{Int64, StdError, Uint64};
+
+    let price = PythPrice {
+        price: Int64::new(3_215_000),
+        conf: Uint64::new(1_500),
+        expo: -5,
+        publish_time: 0,
+    };
+    assert_eq!(
+        price.to_decimals().unwrap(),
+        (Decimal::from_ratio(3215u128, 100u128), Decimal::permille(15))
+    );
+
+    let price = PythPrice {
+        price: Int64::new(-1),
+        conf: Uint64::zero(),
+        expo: 2,
+        publish_time: 0,
+    };
+    assert_eq!(price.to_decimals().unwrap_err(), StdError::generic_err("invalid pyth price"));
+}
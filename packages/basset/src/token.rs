@@ -0,0 +1,207 @@
+use bech32::{ToBase32, Variant};
+use cosmwasm_std::{
+    to_json_binary, to_json_vec, Addr, Api, Binary, CosmosMsg, Deps, Env, QueryRequest, StdError,
+    StdResult, SubMsg, Uint128, WasmMsg, WasmQuery,
+};
+use cw20::Expiration;
+use ripemd::Ripemd160;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::hub::{ConfigResponse, QueryMsg as HubQueryMsg};
+
+/// The most hook contracts a token notifies, to bound the cost of a transfer
+pub const MAX_BALANCE_HOOKS: usize = 10;
+
+/// The gas given to a balance hook other than the reward contract
+pub const BALANCE_HOOK_GAS_LIMIT: u64 = 300_000;
+
+/// The reply id of the balance hooks other than the reward contract, whose failures are ignored
+pub const BALANCE_HOOK_REPLY_ID: u64 = 1;
+
+/// Sent by the bSei and stSei tokens to each of their balance hook contracts.
+/// It matches the balance operations of the bSei reward contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceHookMsg {
+    IncreaseBalance { address: String, amount: Uint128 },
+    DecreaseBalance { address: String, amount: Uint128 },
+}
+
+/// Sent by the hub to the bSei token when the bSei reward contract changes,
+/// so that the token does not have to resolve it on its own.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenConfigMsg {
+    UpdateRewardContract { reward_contract: String },
+}
+
+/// The balance snapshot query of the bSei and stSei tokens, for the contracts reading it.
+/// Return type: cw20::BalanceResponse.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotQueryMsg {
+    BalanceAt { address: String, height: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BalanceHooksResponse {
+    pub hooks: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalSupplyResponse {
+    pub total_supply: Uint128,
+}
+
+/// Notify every hook of a balance moving from `from` to `to`.
+/// A mint has no `from`, and a burn has no `to`.
+/// Only the `required` hook, the reward contract, can fail the balance operation; the
+/// others run with a gas limit and their failures are ignored.
+pub fn balance_hook_messages(
+    hooks: &[Addr],
+    required: Option<&Addr>,
+    from: Option<&str>,
+    to: Option<&str>,
+    amount: Uint128,
+) -> StdResult<Vec<SubMsg>> {
+    let mut messages = vec![];
+    for hook in hooks {
+        let required = Some(hook) == required;
+        if let Some(from) = from {
+            messages.push(hook_message(
+                hook,
+                required,
+                &BalanceHookMsg::DecreaseBalance {
+                    address: from.to_string(),
+                    amount,
+                },
+            )?);
+        }
+        if let Some(to) = to {
+            messages.push(hook_message(
+                hook,
+                required,
+                &BalanceHookMsg::IncreaseBalance {
+                    address: to.to_string(),
+                    amount,
+                },
+            )?);
+        }
+    }
+    Ok(messages)
+}
+
+fn hook_message(hook: &Addr, required: bool, msg: &BalanceHookMsg) -> StdResult<SubMsg> {
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: hook.to_string(),
+        msg: to_json_binary(msg)?,
+        funds: vec![],
+    });
+    Ok(if required {
+        SubMsg::new(msg)
+    } else {
+        SubMsg::reply_on_error(msg, BALANCE_HOOK_REPLY_ID).with_gas_limit(BALANCE_HOOK_GAS_LIMIT)
+    })
+}
+
+/// The hooks are managed by the hub, or by the owner of the hub.
+pub fn is_hook_manager(deps: Deps, hub_contract: &Addr, sender: &Addr) -> StdResult<bool> {
+    if sender == hub_contract {
+        return Ok(true);
+    }
+    let config: ConfigResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: hub_contract.to_string(),
+        msg: to_json_binary(&HubQueryMsg::Config {})?,
+    }))?;
+    Ok(*sender == config.owner)
+}
+
+/// An allowance grant signed off-chain by the owner, which anyone can submit.
+/// The owner signs the sha256 of the JSON of its `PermitPayload`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Permit {
+    pub owner: String,
+    pub spender: String,
+    pub amount: Uint128,
+    /// The expiration of the granted allowance
+    pub expires: Option<Expiration>,
+    /// The next permit nonce of the owner
+    pub nonce: u64,
+    /// The block time, in seconds, after which the permit cannot be submitted
+    pub deadline: u64,
+    /// The compressed secp256k1 public key of the owner
+    pub pubkey: Binary,
+    pub signature: Binary,
+}
+
+/// The payload signed by the owner, which binds a permit to the chain and the token.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PermitPayload {
+    pub chain_id: String,
+    pub contract: String,
+    pub owner: String,
+    pub spender: String,
+    pub amount: Uint128,
+    pub expires: Option<Expiration>,
+    pub nonce: u64,
+    pub deadline: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PermitNonceResponse {
+    pub nonce: u64,
+}
+
+impl Permit {
+    pub fn payload(&self, env: &Env) -> PermitPayload {
+        PermitPayload {
+            chain_id: env.block.chain_id.clone(),
+            contract: env.contract.address.to_string(),
+            owner: self.owner.clone(),
+            spender: self.spender.clone(),
+            amount: self.amount,
+            expires: self.expires,
+            nonce: self.nonce,
+            deadline: self.deadline,
+        }
+    }
+}
+
+/// The hash signed by the owner of a permit.
+pub fn permit_hash(payload: &PermitPayload) -> StdResult<Vec<u8>> {
+    Ok(Sha256::digest(to_json_vec(payload)?).to_vec())
+}
+
+/// The account address of a compressed secp256k1 public key, as on the Cosmos SDK chains.
+pub fn pubkey_address(prefix: &str, pubkey: &[u8]) -> StdResult<String> {
+    let hash = Ripemd160::digest(Sha256::digest(pubkey));
+    bech32::encode(prefix, hash.to_base32(), Variant::Bech32)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+/// Check that the permit is still valid, and signed by the key of its owner.
+/// The nonce is left to the token.
+pub fn verify_permit(api: &dyn Api, env: &Env, permit: &Permit) -> StdResult<()> {
+    if env.block.time.seconds() > permit.deadline {
+        return Err(StdError::generic_err("The permit has expired"));
+    }
+    if permit.pubkey.len() != 33 {
+        return Err(StdError::generic_err(
+            "The permit must carry a compressed secp256k1 public key",
+        ));
+    }
+
+    let (prefix, _, _) =
+        bech32::decode(&permit.owner).map_err(|e| StdError::generic_err(e.to_string()))?;
+    if permit.owner.to_lowercase() != pubkey_address(&prefix, &permit.pubkey)? {
+        return Err(StdError::generic_err("The public key does not match the owner"));
+    }
+
+    let hash = permit_hash(&permit.payload(env))?;
+    if !api.secp256k1_verify(&hash, &permit.signature, &permit.pubkey)? {
+        return Err(StdError::generic_err("Invalid permit signature"));
+    }
+    Ok(())
+}
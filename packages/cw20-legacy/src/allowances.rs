@@ -5,7 +5,7 @@ use cosmwasm_std::{
 use cw20::{AllowanceResponse, Cw20ReceiveMsg, Expiration};
 
 use crate::error::ContractError;
-use crate::state::{ALLOWANCES, BALANCES, TOKEN_INFO};
+use crate::state::{record_total_supply, ALLOWANCES, BALANCES, TOKEN_INFO};
 
 pub fn execute_increase_allowance(
     deps: DepsMut,
@@ -150,6 +150,7 @@ pub fn execute_transfer_from(
         deps.api
             .addr_canonicalize(&owner_addr.to_string())?
             .as_slice(),
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
@@ -159,6 +160,7 @@ pub fn execute_transfer_from(
         deps.api
             .addr_canonicalize(&rcpt_addr.to_string())?
             .as_slice(),
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
 
@@ -197,15 +199,22 @@ pub fn execute_burn_from(
         deps.api
             .addr_canonicalize(&owner_addr.to_string())?
             .as_slice(),
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
     // reduce total_supply
-    TOKEN_INFO.update(deps.storage, |mut meta| -> StdResult<_> {
+    let meta = TOKEN_INFO.update(deps.storage, |mut meta| -> StdResult<_> {
         meta.total_supply = meta.total_supply.checked_sub(amount)?;
         Ok(meta)
     })?;
+    record_total_supply(
+        deps.storage,
+        env.block.height,
+        meta.total_supply + amount,
+        meta.total_supply,
+    )?;
 
     Ok(Response::new().add_attributes(vec![
         attr("action", "burn_from"),
@@ -243,6 +252,7 @@ pub fn execute_send_from(
         deps.api
             .addr_canonicalize(&owner_addr.to_string())?
             .as_slice(),
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
@@ -252,6 +262,7 @@ pub fn execute_send_from(
         deps.api
             .addr_canonicalize(&rcpt_addr.to_string())?
             .as_slice(),
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
 
@@ -321,6 +332,7 @@ mod tests {
                 amount,
             }],
             mint: None,
+            marketing: None,
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -6,7 +6,10 @@ use cosmwasm_std::{
 };
 
 use cw2::set_contract_version;
-use cw20::{BalanceResponse, Cw20Coin, Cw20ReceiveMsg, MinterResponse, TokenInfoResponse};
+use cw20::{
+    BalanceResponse, Cw20Coin, Cw20ReceiveMsg, DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo,
+    MarketingInfoResponse, MinterResponse, TokenInfoResponse,
+};
 
 use crate::allowances::{
     execute_burn_from, execute_decrease_allowance, execute_increase_allowance, execute_send_from,
@@ -14,17 +17,79 @@ use crate::allowances::{
 };
 use crate::enumerable::{query_all_accounts, query_all_allowances};
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{MinterData, TokenInfo, BALANCES, TOKEN_INFO};
+use crate::msg::{
+    ExecuteMsg, InstantiateMarketingInfo, InstantiateMsg, QueryMsg, TotalSupplyResponse,
+};
+use crate::state::{
+    record_total_supply, MinterData, TokenInfo, BALANCES, LOGO, MARKETING_INFO, TOKEN_INFO,
+    TOTAL_SUPPLY_HISTORY,
+};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw20-base";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const LOGO_SIZE_CAP: usize = 5 * 1024;
+
+/// Checks if data starts with XML preamble
+fn verify_xml_preamble(data: &[u8]) -> Result<(), ContractError> {
+    let preamble = data
+        .split_inclusive(|c| *c == b'>')
+        .next()
+        .ok_or(ContractError::InvalidXmlPreamble {})?;
+
+    const PREFIX: &[u8] = b"<?xml ";
+    const POSTFIX: &[u8] = b"?>";
+
+    if !(preamble.starts_with(PREFIX) && preamble.ends_with(POSTFIX)) {
+        Err(ContractError::InvalidXmlPreamble {})
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates XML logo
+fn verify_xml_logo(logo: &[u8]) -> Result<(), ContractError> {
+    verify_xml_preamble(logo)?;
+
+    if logo.len() > LOGO_SIZE_CAP {
+        Err(ContractError::LogoTooBig {})
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates png logo
+fn verify_png_logo(logo: &[u8]) -> Result<(), ContractError> {
+    // PNG header format:
+    // 0x89 - magic byte, out of ASCII table to fail on 7-bit systems
+    // "PNG" ascii representation
+    // [0x0d, 0x0a] - dos style line ending
+    // 0x1a - dos control character, stop displaying rest of the file
+    // 0x0a - unix style line ending
+    const HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    if logo.len() > LOGO_SIZE_CAP {
+        Err(ContractError::LogoTooBig {})
+    } else if !logo.starts_with(&HEADER) {
+        Err(ContractError::InvalidPngHeader {})
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks if passed logo is correct, and if not, returns an error
+fn verify_logo(logo: &Logo) -> Result<(), ContractError> {
+    match logo {
+        Logo::Embedded(EmbeddedLogo::Svg(logo)) => verify_xml_logo(logo),
+        Logo::Embedded(EmbeddedLogo::Png(logo)) => verify_png_logo(logo),
+        Logo::Url(_) => Ok(()),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
@@ -32,7 +97,7 @@ pub fn instantiate(
     // check valid token info
     msg.validate()?;
     // create initial accounts
-    let total_supply = create_accounts(&mut deps, &msg.initial_balances)?;
+    let total_supply = create_accounts(&mut deps, &env, &msg.initial_balances)?;
 
     if let Some(limit) = msg.get_cap() {
         if total_supply > limit {
@@ -57,14 +122,55 @@ pub fn instantiate(
         mint,
     };
     TOKEN_INFO.save(deps.storage, &data)?;
+    record_total_supply(deps.storage, env.block.height, Uint128::zero(), total_supply)?;
+
+    if let Some(marketing) = msg.marketing {
+        instantiate_marketing(deps, marketing)?;
+    }
+
     Ok(Response::default())
 }
 
-pub fn create_accounts(deps: &mut DepsMut, accounts: &[Cw20Coin]) -> StdResult<Uint128> {
+/// Stores the first marketing info and logo of the token.
+/// Used by instantiate and by the migration of tokens instantiated without them.
+pub fn instantiate_marketing(
+    deps: DepsMut,
+    marketing: InstantiateMarketingInfo,
+) -> StdResult<()> {
+    let logo = if let Some(logo) = marketing.logo {
+        // instantiate keeps the StdError signature the legacy tokens rely on
+        verify_logo(&logo).map_err(|e| StdError::generic_err(e.to_string()))?;
+        LOGO.save(deps.storage, &logo)?;
+
+        match logo {
+            Logo::Url(url) => Some(LogoInfo::Url(url)),
+            Logo::Embedded(_) => Some(LogoInfo::Embedded),
+        }
+    } else {
+        None
+    };
+
+    let data = MarketingInfoResponse {
+        project: marketing.project,
+        description: marketing.description,
+        marketing: marketing
+            .marketing
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?,
+        logo,
+    };
+    MARKETING_INFO.save(deps.storage, &data)
+}
+
+pub fn create_accounts(
+    deps: &mut DepsMut,
+    env: &Env,
+    accounts: &[Cw20Coin],
+) -> StdResult<Uint128> {
     let mut total_supply = Uint128::zero();
     for row in accounts {
         let address = deps.api.addr_canonicalize(&row.address)?;
-        BALANCES.save(deps.storage, address.as_slice(), &row.amount)?;
+        BALANCES.save(deps.storage, address.as_slice(), &row.amount, env.block.height)?;
         total_supply += row.amount;
     }
     Ok(total_supply)
@@ -110,12 +216,18 @@ pub fn execute(
             amount,
             msg,
         } => execute_send_from(deps, env, info, owner, contract, amount, msg),
+        ExecuteMsg::UpdateMarketing {
+            project,
+            description,
+            marketing,
+        } => execute_update_marketing(deps, env, info, project, description, marketing),
+        ExecuteMsg::UploadLogo(logo) => execute_upload_logo(deps, env, info, logo),
     }
 }
 
 pub fn execute_transfer(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
@@ -131,6 +243,7 @@ pub fn execute_transfer(
         deps.api
             .addr_canonicalize(&info.sender.to_string())?
             .as_slice(),
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
@@ -140,6 +253,7 @@ pub fn execute_transfer(
         deps.api
             .addr_canonicalize(&rcpt_addr.to_string())?
             .as_slice(),
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
 
@@ -153,7 +267,7 @@ pub fn execute_transfer(
 
 pub fn execute_burn(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
@@ -167,15 +281,22 @@ pub fn execute_burn(
         deps.api
             .addr_canonicalize(&info.sender.to_string())?
             .as_slice(),
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
     // reduce total_supply
-    TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
+    let token_info = TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
         info.total_supply = info.total_supply.checked_sub(amount)?;
         Ok(info)
     })?;
+    record_total_supply(
+        deps.storage,
+        env.block.height,
+        token_info.total_supply + amount,
+        token_info.total_supply,
+    )?;
 
     Ok(Response::new().add_attributes(vec![
         attr("action", "burn"),
@@ -186,7 +307,7 @@ pub fn execute_burn(
 
 pub fn execute_mint(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
@@ -211,6 +332,12 @@ pub fn execute_mint(
         }
     }
     TOKEN_INFO.save(deps.storage, &config)?;
+    record_total_supply(
+        deps.storage,
+        env.block.height,
+        config.total_supply - amount,
+        config.total_supply,
+    )?;
 
     // add amount to recipient balance
     let rcpt_addr = deps.api.addr_validate(&recipient)?;
@@ -219,6 +346,7 @@ pub fn execute_mint(
         deps.api
             .addr_canonicalize(&rcpt_addr.to_string())?
             .as_slice(),
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
 
@@ -231,7 +359,7 @@ pub fn execute_mint(
 
 pub fn execute_send(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     contract: String,
     amount: Uint128,
@@ -249,6 +377,7 @@ pub fn execute_send(
         deps.api
             .addr_canonicalize(&info.sender.to_string())?
             .as_slice(),
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
@@ -258,6 +387,7 @@ pub fn execute_send(
         deps.api
             .addr_canonicalize(&rcpt_addr.to_string())?
             .as_slice(),
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
 
@@ -278,6 +408,82 @@ pub fn execute_send(
     ))
 }
 
+pub fn execute_update_marketing(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    project: Option<String>,
+    description: Option<String>,
+    marketing: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut marketing_info = MARKETING_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    if marketing_info.marketing.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match project {
+        Some(empty) if empty.trim().is_empty() => marketing_info.project = None,
+        Some(project) => marketing_info.project = Some(project),
+        None => (),
+    }
+
+    match description {
+        Some(empty) if empty.trim().is_empty() => marketing_info.description = None,
+        Some(description) => marketing_info.description = Some(description),
+        None => (),
+    }
+
+    match marketing {
+        Some(empty) if empty.trim().is_empty() => marketing_info.marketing = None,
+        Some(marketing) => marketing_info.marketing = Some(deps.api.addr_validate(&marketing)?),
+        None => (),
+    }
+
+    if marketing_info.project.is_none()
+        && marketing_info.description.is_none()
+        && marketing_info.marketing.is_none()
+        && marketing_info.logo.is_none()
+    {
+        MARKETING_INFO.remove(deps.storage);
+    } else {
+        MARKETING_INFO.save(deps.storage, &marketing_info)?;
+    }
+
+    Ok(Response::new().add_attribute("action", "update_marketing"))
+}
+
+pub fn execute_upload_logo(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    logo: Logo,
+) -> Result<Response, ContractError> {
+    let mut marketing_info = MARKETING_INFO
+        .may_load(deps.storage)?
+        .ok_or(ContractError::Unauthorized {})?;
+
+    verify_logo(&logo)?;
+
+    if marketing_info.marketing.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    LOGO.save(deps.storage, &logo)?;
+
+    let logo_info = match logo {
+        Logo::Url(url) => LogoInfo::Url(url),
+        Logo::Embedded(_) => LogoInfo::Embedded,
+    };
+
+    marketing_info.logo = Some(logo_info);
+    MARKETING_INFO.save(deps.storage, &marketing_info)?;
+
+    Ok(Response::new().add_attribute("action", "upload_logo"))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -295,6 +501,14 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::AllAccounts { start_after, limit } => {
             to_json_binary(&query_all_accounts(deps, start_after, limit)?)
         }
+        QueryMsg::MarketingInfo {} => to_json_binary(&query_marketing_info(deps)?),
+        QueryMsg::DownloadLogo {} => to_json_binary(&query_download_logo(deps)?),
+        QueryMsg::BalanceAt { address, height } => {
+            to_json_binary(&query_balance_at(deps, address, height)?)
+        }
+        QueryMsg::TotalSupplyAt { height } => {
+            to_json_binary(&query_total_supply_at(deps, height)?)
+        }
     }
 }
 
@@ -306,6 +520,27 @@ pub fn query_balance(deps: Deps, address: String) -> StdResult<BalanceResponse>
     Ok(BalanceResponse { balance })
 }
 
+/// The balance at the start of the block `height`.
+pub fn query_balance_at(deps: Deps, address: String, height: u64) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_canonicalize(&address)?;
+    let balance = BALANCES
+        .may_load_at_height(deps.storage, address.as_slice(), height)?
+        .unwrap_or_default();
+    Ok(BalanceResponse { balance })
+}
+
+/// The total supply at the start of the block `height`.
+pub fn query_total_supply_at(deps: Deps, height: u64) -> StdResult<TotalSupplyResponse> {
+    // without any record, the supply has not changed since the history is kept
+    let total_supply = match TOTAL_SUPPLY_HISTORY.may_load(deps.storage)? {
+        Some(_) => TOTAL_SUPPLY_HISTORY
+            .may_load_at_height(deps.storage, height)?
+            .unwrap_or_default(),
+        None => TOKEN_INFO.load(deps.storage)?.total_supply,
+    };
+    Ok(TotalSupplyResponse { total_supply })
+}
+
 pub fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
     let info = TOKEN_INFO.load(deps.storage)?;
     let res = TokenInfoResponse {
@@ -329,6 +564,25 @@ pub fn query_minter(deps: Deps) -> StdResult<Option<MinterResponse>> {
     Ok(minter)
 }
 
+pub fn query_marketing_info(deps: Deps) -> StdResult<MarketingInfoResponse> {
+    Ok(MARKETING_INFO.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn query_download_logo(deps: Deps) -> StdResult<DownloadLogoResponse> {
+    let logo = LOGO.load(deps.storage)?;
+    match logo {
+        Logo::Embedded(EmbeddedLogo::Svg(logo)) => Ok(DownloadLogoResponse {
+            mime_type: "image/svg+xml".to_owned(),
+            data: logo,
+        }),
+        Logo::Embedded(EmbeddedLogo::Png(logo)) => Ok(DownloadLogoResponse {
+            mime_type: "image/png".to_owned(),
+            data: logo,
+        }),
+        Logo::Url(_) => Err(StdError::not_found("logo")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
@@ -380,6 +634,7 @@ mod tests {
                 amount,
             }],
             mint: mint.clone(),
+            marketing: None,
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -414,6 +669,7 @@ mod tests {
                 amount,
             }],
             mint: None,
+            marketing: None,
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -453,6 +709,7 @@ mod tests {
                 minter: minter.clone(),
                 cap: Some(limit),
             }),
+            marketing: None,
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -499,6 +756,7 @@ mod tests {
                 minter,
                 cap: Some(limit),
             }),
+            marketing: None,
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -614,6 +872,7 @@ mod tests {
                 },
             ],
             mint: None,
+            marketing: None,
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -1,10 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{CanonicalAddr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{CanonicalAddr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 
-use cw20::AllowanceResponse;
+use cw20::{AllowanceResponse, Logo, MarketingInfoResponse};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
@@ -30,8 +30,38 @@ impl TokenInfo {
 }
 
 pub const TOKEN_INFO: Item<TokenInfo> = Item::new("\u{0}\ntoken_info");
-pub const BALANCES: Map<&[u8], Uint128> = Map::new("balance");
+// the balances keep the legacy namespace, the history is kept aside as one changelog entry
+// per changed account and block, the only write BalanceAt needs
+pub const BALANCES: SnapshotMap<&[u8], Uint128> = SnapshotMap::new(
+    "balance",
+    "balance__checkpoints",
+    "balance__changelog",
+    Strategy::EveryBlock,
+);
+pub const TOTAL_SUPPLY_HISTORY: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_supply",
+    "total_supply__checkpoints",
+    "total_supply__changelog",
+    Strategy::EveryBlock,
+);
 pub const ALLOWANCES: Map<(&[u8], &[u8]), AllowanceResponse> = Map::new("allowance");
+// the marketing extension is stored under the same keys as in cw20-base
+pub const MARKETING_INFO: Item<MarketingInfoResponse> = Item::new("marketing_info");
+pub const LOGO: Item<Logo> = Item::new("logo");
+
+/// Record the total supply as of `height`, after it changed from `previous`.
+/// The supply before the first record is taken as the supply since the genesis.
+pub fn record_total_supply(
+    storage: &mut dyn Storage,
+    height: u64,
+    previous: Uint128,
+    total_supply: Uint128,
+) -> StdResult<()> {
+    if TOTAL_SUPPLY_HISTORY.may_load(storage)?.is_none() {
+        TOTAL_SUPPLY_HISTORY.save(storage, &previous, 0)?;
+    }
+    TOTAL_SUPPLY_HISTORY.save(storage, &total_supply, height)
+}
 
 #[cfg(test)]
 mod test {
@@ -0,0 +1,337 @@
+/// The test suite shared by the token contracts, run through their `instantiate`, `execute`,
+/// `query` and `reply` entry points, so that each thin wrapper is covered by the same cases.
+///
+/// `init_msg` builds the instantiate message from the initial balances, with `hub` as the
+/// minter. `deps` builds the mocked dependencies, which answer the hub config queries of a
+/// reward-bearing token. `reward_contract` is the reward contract registered in the hub, if
+/// the token is reward-bearing.
+/// The paths are resolved from the generated module, so they have to start from `crate`.
+/// The calling crate needs `k256` as a dev-dependency to sign the permits.
+#[macro_export]
+macro_rules! contract_tests {
+    (
+        instantiate: $instantiate:path,
+        execute: $execute:path,
+        query: $query:path,
+        reply: $reply:path,
+        execute_msg: $execute_msg:ty,
+        query_msg: $query_msg:ty,
+        init_msg: $init_msg:expr,
+        deps: $deps:expr,
+        hub: $hub:expr,
+        reward_contract: $reward_contract:expr $(,)?
+    ) => {
+        mod contract_tests {
+            use super::*;
+            use ::basset::hub::ExecuteMsg::CheckSlashing;
+            use ::basset::token::{
+                permit_hash, pubkey_address, BalanceHookMsg, BalanceHooksResponse, Permit,
+                PermitNonceResponse, TotalSupplyResponse, BALANCE_HOOK_GAS_LIMIT,
+                BALANCE_HOOK_REPLY_ID,
+            };
+            use ::cosmwasm_std::testing::{mock_env, mock_info};
+            use ::cosmwasm_std::{
+                from_json, to_json_binary, Binary, CosmosMsg, Deps, DepsMut, Reply, Response,
+                SubMsg, SubMsgResult, Uint128, WasmMsg,
+            };
+            use ::cw20::{
+                AllowanceResponse, BalanceResponse, Cw20Coin, Cw20ReceiveMsg, MinterResponse,
+                TokenInfoResponse,
+            };
+            use ::k256::ecdsa::signature::hazmat::PrehashSigner;
+            use ::k256::ecdsa::{Signature, SigningKey};
+
+            type ExecuteMsg = $execute_msg;
+            type QueryMsg = $query_msg;
+
+            const HOLDER: &str = "addr0000";
+            const GAUGE: &str = "gauge";
+
+            fn init(deps: DepsMut) {
+                let initial_balances = vec![Cw20Coin {
+                    address: HOLDER.to_string(),
+                    amount: Uint128::new(1000),
+                }];
+                let info = mock_info($hub, &[]);
+                $instantiate(deps, mock_env(), info, $init_msg(initial_balances)).unwrap();
+            }
+
+            // the errors of both cw20 implementations are compared by their message
+            fn execute(deps: DepsMut, sender: &str, msg: ExecuteMsg) -> Result<Response, String> {
+                let info = mock_info(sender, &[]);
+                $execute(deps, mock_env(), info, msg).map_err(|e| e.to_string())
+            }
+
+            fn query<T: ::serde::de::DeserializeOwned>(deps: Deps, msg: QueryMsg) -> T {
+                from_json($query(deps, mock_env(), msg).unwrap()).unwrap()
+            }
+
+            fn hook_message(hook: &str, msg: BalanceHookMsg) -> SubMsg {
+                SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: hook.to_string(),
+                    msg: to_json_binary(&msg).unwrap(),
+                    funds: vec![],
+                }))
+            }
+
+            // the notifications of `hook` for a move of `amount` from `from` to `to`
+            fn hook_messages(
+                hook: &str,
+                from: Option<&str>,
+                to: Option<&str>,
+                amount: u128,
+            ) -> Vec<SubMsg> {
+                let decrease = from.map(|address| BalanceHookMsg::DecreaseBalance {
+                    address: address.to_string(),
+                    amount: Uint128::new(amount),
+                });
+                let increase = to.map(|address| BalanceHookMsg::IncreaseBalance {
+                    address: address.to_string(),
+                    amount: Uint128::new(amount),
+                });
+                decrease
+                    .into_iter()
+                    .chain(increase)
+                    .map(|msg| hook_message(hook, msg))
+                    .collect()
+            }
+
+            // the reward contract is notified first, and has to succeed
+            fn reward_messages(
+                from: Option<&str>,
+                to: Option<&str>,
+                amount: u128,
+            ) -> Vec<SubMsg> {
+                let reward_contract: Option<&str> = $reward_contract;
+                reward_contract
+                    .map(|reward| hook_messages(reward, from, to, amount))
+                    .unwrap_or_default()
+            }
+
+            fn sign_permit(key: &SigningKey, permit: &mut Permit) {
+                let hash = permit_hash(&permit.payload(&mock_env())).unwrap();
+                let signature: Signature = key.sign_prehash(&hash).unwrap();
+                permit.signature = Binary::from(&signature.to_bytes()[..]);
+            }
+
+            #[test]
+            fn queries() {
+                let mut deps = $deps;
+                init(deps.as_mut());
+                // the block after the instantiation
+                let height = mock_env().block.height + 1;
+
+                // the cw20 queries
+                let balance: BalanceResponse = query(
+                    deps.as_ref(),
+                    QueryMsg::Balance {
+                        address: HOLDER.to_string(),
+                    },
+                );
+                assert_eq!(balance.balance, Uint128::new(1000));
+                let token_info: TokenInfoResponse = query(deps.as_ref(), QueryMsg::TokenInfo {});
+                assert_eq!(token_info.total_supply, Uint128::new(1000));
+                let minter: Option<MinterResponse> = query(deps.as_ref(), QueryMsg::Minter {});
+                assert_eq!(minter.unwrap().minter, $hub.to_string());
+
+                // the extensions
+                let balance: BalanceResponse = query(
+                    deps.as_ref(),
+                    QueryMsg::BalanceAt {
+                        address: HOLDER.to_string(),
+                        height,
+                    },
+                );
+                assert_eq!(balance.balance, Uint128::new(1000));
+                let total_supply: TotalSupplyResponse =
+                    query(deps.as_ref(), QueryMsg::TotalSupplyAt { height });
+                assert_eq!(total_supply.total_supply, Uint128::new(1000));
+                let hooks: BalanceHooksResponse = query(deps.as_ref(), QueryMsg::BalanceHooks {});
+                assert!(hooks.hooks.is_empty());
+                let nonce: PermitNonceResponse = query(
+                    deps.as_ref(),
+                    QueryMsg::PermitNonce {
+                        owner: HOLDER.to_string(),
+                    },
+                );
+                assert_eq!(nonce.nonce, 0);
+            }
+
+            #[test]
+            fn mint_and_burn_by_hub() {
+                let mut deps = $deps;
+                init(deps.as_mut());
+
+                let mint = ExecuteMsg::Mint {
+                    recipient: HOLDER.to_string(),
+                    amount: Uint128::new(10),
+                };
+                let err = execute(deps.as_mut(), HOLDER, mint.clone()).unwrap_err();
+                assert_eq!(err, "Unauthorized");
+                let res = execute(deps.as_mut(), $hub, mint).unwrap();
+                assert_eq!(res.messages, reward_messages(None, Some(HOLDER), 10));
+
+                // the hub burns the tokens it is sent
+                let transfer = ExecuteMsg::Transfer {
+                    recipient: $hub.to_string(),
+                    amount: Uint128::new(10),
+                };
+                let res = execute(deps.as_mut(), HOLDER, transfer).unwrap();
+                assert_eq!(res.messages, reward_messages(Some(HOLDER), Some($hub), 10));
+
+                let burn = ExecuteMsg::Burn {
+                    amount: Uint128::new(10),
+                };
+                let err = execute(deps.as_mut(), HOLDER, burn.clone()).unwrap_err();
+                assert_eq!(err, "Unauthorized");
+                let res = execute(deps.as_mut(), $hub, burn).unwrap();
+                assert_eq!(res.messages, reward_messages(Some($hub), None, 10));
+
+                let token_info: TokenInfoResponse = query(deps.as_ref(), QueryMsg::TokenInfo {});
+                assert_eq!(token_info.total_supply, Uint128::new(1000));
+            }
+
+            #[test]
+            fn balance_hooks_before_receive() {
+                let mut deps = $deps;
+                init(deps.as_mut());
+
+                let add_hook = ExecuteMsg::AddBalanceHook {
+                    address: GAUGE.to_string(),
+                };
+                let err = execute(deps.as_mut(), HOLDER, add_hook.clone()).unwrap_err();
+                assert_eq!(err, "Unauthorized");
+                execute(deps.as_mut(), $hub, add_hook).unwrap();
+                let reward_contract: Option<&str> = $reward_contract;
+                let mut registered: Vec<String> =
+                    reward_contract.into_iter().map(String::from).collect();
+                registered.push(GAUGE.to_string());
+                let hooks: BalanceHooksResponse = query(deps.as_ref(), QueryMsg::BalanceHooks {});
+                assert_eq!(hooks.hooks, registered);
+
+                let contract = "contract0000";
+                let msg = Binary::from(br#"{"unbond":{}}"#.as_slice());
+                let send = ExecuteMsg::Send {
+                    contract: contract.to_string(),
+                    amount: Uint128::new(10),
+                    msg: msg.clone(),
+                };
+                let res = execute(deps.as_mut(), HOLDER, send).unwrap();
+
+                // the optional hooks run with a gas limit, the receiver is called last
+                let optional = hook_messages(GAUGE, Some(HOLDER), Some(contract), 10)
+                    .into_iter()
+                    .map(|msg| {
+                        SubMsg::reply_on_error(msg.msg, BALANCE_HOOK_REPLY_ID)
+                            .with_gas_limit(BALANCE_HOOK_GAS_LIMIT)
+                    });
+                let receive = Cw20ReceiveMsg {
+                    sender: HOLDER.to_string(),
+                    amount: Uint128::new(10),
+                    msg,
+                };
+                let mut expected = reward_messages(Some(HOLDER), Some(contract), 10);
+                expected.extend(optional);
+                expected.push(SubMsg::new(receive.into_cosmos_msg(contract).unwrap()));
+                assert_eq!(res.messages, expected);
+
+                let remove_hook = ExecuteMsg::RemoveBalanceHook {
+                    address: GAUGE.to_string(),
+                };
+                execute(deps.as_mut(), $hub, remove_hook).unwrap();
+                registered.pop();
+                let hooks: BalanceHooksResponse = query(deps.as_ref(), QueryMsg::BalanceHooks {});
+                assert_eq!(hooks.hooks, registered);
+            }
+
+            #[test]
+            fn reply_balance_hook() {
+                let mut deps = $deps;
+                init(deps.as_mut());
+
+                // a failing optional hook does not revert the balance change
+                let reply = Reply {
+                    id: BALANCE_HOOK_REPLY_ID,
+                    result: SubMsgResult::Err("out of gas".to_string()),
+                };
+                let res = $reply(deps.as_mut(), mock_env(), reply).unwrap();
+                assert!(res.messages.is_empty());
+                assert_eq!(res.attributes[0].value, "balance_hook_failed");
+                assert_eq!(res.attributes[1].value, "out of gas");
+
+                let reply = Reply {
+                    id: BALANCE_HOOK_REPLY_ID + 1,
+                    result: SubMsgResult::Err("out of gas".to_string()),
+                };
+                assert!($reply(deps.as_mut(), mock_env(), reply).is_err());
+            }
+
+            #[test]
+            fn burn_from_checks_slashing() {
+                let mut deps = $deps;
+                init(deps.as_mut());
+
+                let spender = "addr0001";
+                let allowance = ExecuteMsg::IncreaseAllowance {
+                    spender: spender.to_string(),
+                    amount: Uint128::new(10),
+                    expires: None,
+                };
+                execute(deps.as_mut(), HOLDER, allowance).unwrap();
+                let burn_from = ExecuteMsg::BurnFrom {
+                    owner: HOLDER.to_string(),
+                    amount: Uint128::new(10),
+                };
+                let res = execute(deps.as_mut(), spender, burn_from).unwrap();
+                let mut expected = reward_messages(Some(HOLDER), None, 10);
+                expected.push(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: $hub.to_string(),
+                    msg: to_json_binary(&CheckSlashing {}).unwrap(),
+                    funds: vec![],
+                })));
+                assert_eq!(res.messages, expected);
+            }
+
+            #[test]
+            fn permit() {
+                let mut deps = $deps;
+                init(deps.as_mut());
+
+                // the owner is the account of its key
+                let key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+                let pubkey = Binary::from(key.verifying_key().to_encoded_point(true).as_bytes());
+                let owner = pubkey_address("sei", &pubkey).unwrap();
+                let spender = "aggregator".to_string();
+                let mut permit = Permit {
+                    owner: owner.clone(),
+                    spender: spender.clone(),
+                    amount: Uint128::new(100),
+                    expires: None,
+                    nonce: 0,
+                    deadline: mock_env().block.time.seconds() + 60,
+                    pubkey,
+                    signature: Binary::default(),
+                };
+                sign_permit(&key, &mut permit);
+
+                // anyone submits the permit of the owner, once
+                let msg = ExecuteMsg::Permit(permit);
+                execute(deps.as_mut(), "relayer", msg.clone()).unwrap();
+                let err = execute(deps.as_mut(), "relayer", msg).unwrap_err();
+                assert_eq!(err, "Generic error: Invalid permit nonce, expected 1");
+
+                let allowance: AllowanceResponse = query(
+                    deps.as_ref(),
+                    QueryMsg::Allowance {
+                        owner: owner.clone(),
+                        spender,
+                    },
+                );
+                assert_eq!(allowance.allowance, Uint128::new(100));
+                let nonce: PermitNonceResponse =
+                    query(deps.as_ref(), QueryMsg::PermitNonce { owner });
+                assert_eq!(nonce.nonce, 1);
+            }
+        }
+    };
+}
@@ -0,0 +1,311 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+use cw20::Expiration;
+
+use crate::querier::query_reward_contract;
+use crate::state::{BALANCE_HOOKS, PERMIT_NONCES, REWARD_CONTRACT};
+use crate::token::{LstToken, TokenError, TokenKind};
+use basset::hub::ExecuteMsg::CheckSlashing;
+use basset::token::{
+    balance_hook_messages, is_hook_manager, verify_permit, BalanceHooksResponse, Permit,
+    PermitNonceResponse, BALANCE_HOOK_REPLY_ID, MAX_BALANCE_HOOKS,
+};
+
+/// Move `amount` from `owner` to `recipient` with a transfer or a send of the cw20
+/// implementation, either by the owner or from an allowance.
+pub fn execute_transfer<E, F>(
+    deps: DepsMut,
+    token: &LstToken,
+    owner: &Addr,
+    recipient: &str,
+    amount: Uint128,
+    transfer: F,
+) -> Result<Response, E>
+where
+    E: TokenError,
+    F: FnOnce(DepsMut) -> Result<Response, E>,
+{
+    with_balance_hooks(deps, token, Some(owner.as_str()), Some(recipient), amount, transfer)
+}
+
+/// Only the hub mints, for the bonded funds.
+pub fn execute_mint<E, F>(
+    deps: DepsMut,
+    token: &LstToken,
+    sender: &Addr,
+    recipient: &str,
+    amount: Uint128,
+    mint: F,
+) -> Result<Response, E>
+where
+    E: TokenError,
+    F: FnOnce(DepsMut) -> Result<Response, E>,
+{
+    if *sender != token.hub_contract {
+        return Err(E::unauthorized());
+    }
+    with_balance_hooks(deps, token, None, Some(recipient), amount, mint)
+}
+
+/// Only the hub burns, the tokens it has been sent to unbond. It has just checked slashing to
+/// compute the unbonded amount, so it is not asked to again.
+pub fn execute_burn<E, F>(
+    deps: DepsMut,
+    token: &LstToken,
+    sender: &Addr,
+    amount: Uint128,
+    burn: F,
+) -> Result<Response, E>
+where
+    E: TokenError,
+    F: FnOnce(DepsMut) -> Result<Response, E>,
+{
+    if *sender != token.hub_contract {
+        return Err(E::unauthorized());
+    }
+    with_balance_hooks(deps, token, Some(sender.as_str()), None, amount, burn)
+}
+
+/// The tokens burned from an allowance leave the total supply without going through the hub,
+/// which is asked to check slashing to update its exchange rates.
+pub fn execute_burn_from<E, F>(
+    deps: DepsMut,
+    token: &LstToken,
+    owner: &Addr,
+    amount: Uint128,
+    burn_from: F,
+) -> Result<Response, E>
+where
+    E: TokenError,
+    F: FnOnce(DepsMut) -> Result<Response, E>,
+{
+    let res = with_balance_hooks(deps, token, Some(owner.as_str()), None, amount, burn_from)?;
+    Ok(res.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.hub_contract.to_string(),
+        msg: to_json_binary(&CheckSlashing {})?,
+        funds: vec![],
+    })))
+}
+
+pub fn execute_add_balance_hook<E: TokenError>(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    token: &LstToken,
+    address: String,
+) -> Result<Response, E> {
+    check_hook_manager::<E>(deps.as_ref(), &info, token)?;
+
+    let hook = deps.api.addr_canonicalize(deps.api.addr_validate(&address)?.as_str())?;
+    balance_hooks(&mut deps, token)?;
+    let mut hooks = BALANCE_HOOKS.may_load(deps.storage)?.unwrap_or_default();
+    if hooks.contains(&hook) {
+        return Err(StdError::generic_err(format!(
+            "Balance hook {} is already registered",
+            address
+        ))
+        .into());
+    }
+    if hooks.len() >= MAX_BALANCE_HOOKS {
+        return Err(StdError::generic_err("Too many balance hooks").into());
+    }
+    hooks.push(hook);
+    BALANCE_HOOKS.save(deps.storage, &hooks)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_balance_hook")
+        .add_attribute("hook", address))
+}
+
+pub fn execute_remove_balance_hook<E: TokenError>(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    token: &LstToken,
+    address: String,
+) -> Result<Response, E> {
+    check_hook_manager::<E>(deps.as_ref(), &info, token)?;
+
+    let hook = deps.api.addr_canonicalize(deps.api.addr_validate(&address)?.as_str())?;
+    balance_hooks(&mut deps, token)?;
+    let mut hooks = BALANCE_HOOKS.may_load(deps.storage)?.unwrap_or_default();
+    if !hooks.contains(&hook) {
+        return Err(StdError::generic_err(format!(
+            "Balance hook {} is not registered",
+            address
+        ))
+        .into());
+    }
+    // the reward contract is replaced through the hub, never removed
+    if REWARD_CONTRACT.may_load(deps.storage)? == Some(hook.clone()) {
+        return Err(StdError::generic_err("The reward contract cannot be removed").into());
+    }
+    hooks.retain(|h| *h != hook);
+    BALANCE_HOOKS.save(deps.storage, &hooks)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_balance_hook")
+        .add_attribute("hook", address))
+}
+
+pub fn execute_update_reward_contract<E: TokenError>(
+    deps: DepsMut,
+    info: MessageInfo,
+    token: &LstToken,
+    reward_contract: String,
+) -> Result<Response, E> {
+    if info.sender != token.hub_contract {
+        return Err(E::unauthorized());
+    }
+    if token.kind != TokenKind::RewardBearing {
+        return Err(StdError::generic_err("The token does not have a reward contract").into());
+    }
+
+    let reward = deps.api.addr_canonicalize(deps.api.addr_validate(&reward_contract)?.as_str())?;
+    let mut hooks = BALANCE_HOOKS.may_load(deps.storage)?.unwrap_or_default();
+    // the new reward contract may have been registered as another hook already
+    hooks.retain(|h| *h != reward);
+    let previous = REWARD_CONTRACT.may_load(deps.storage)?;
+    match hooks.iter().position(|h| Some(h) == previous.as_ref()) {
+        Some(i) => hooks[i] = reward.clone(),
+        None => hooks.insert(0, reward.clone()),
+    }
+    BALANCE_HOOKS.save(deps.storage, &hooks)?;
+    REWARD_CONTRACT.save(deps.storage, &reward)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_reward_contract")
+        .add_attribute("reward_contract", reward_contract))
+}
+
+/// Increase the allowance of the spender on behalf of the owner, who signed the permit.
+pub fn execute_permit<E, F>(
+    deps: DepsMut,
+    env: Env,
+    permit: Permit,
+    increase_allowance: F,
+) -> Result<Response, E>
+where
+    E: TokenError,
+    F: FnOnce(
+        DepsMut,
+        Env,
+        MessageInfo,
+        String,
+        Uint128,
+        Option<Expiration>,
+    ) -> Result<Response, E>,
+{
+    let owner = deps.api.addr_validate(&permit.owner)?;
+    let nonce = PERMIT_NONCES.may_load(deps.storage, &owner)?.unwrap_or_default();
+    if permit.nonce != nonce {
+        return Err(StdError::generic_err(format!(
+            "Invalid permit nonce, expected {}",
+            nonce
+        ))
+        .into());
+    }
+    verify_permit(deps.api, &env, &permit)?;
+    PERMIT_NONCES.save(deps.storage, &owner, &(nonce + 1))?;
+
+    let info = MessageInfo {
+        sender: owner,
+        funds: vec![],
+    };
+    let res = increase_allowance(
+        deps,
+        env,
+        info,
+        permit.spender,
+        permit.amount,
+        permit.expires,
+    )?;
+    Ok(res.add_attribute("permit_nonce", nonce.to_string()))
+}
+
+/// A balance hook other than the reward contract failed. Its changes are reverted, and the
+/// balance operation goes on without it.
+pub fn reply_balance_hook(msg: Reply) -> StdResult<Response> {
+    if msg.id != BALANCE_HOOK_REPLY_ID {
+        return Err(StdError::generic_err(format!(
+            "Unknown reply id {}",
+            msg.id
+        )));
+    }
+    let error = msg.result.into_result().err().unwrap_or_default();
+    Ok(Response::new()
+        .add_attribute("action", "balance_hook_failed")
+        .add_attribute("error", error))
+}
+
+pub fn query_permit_nonce(deps: Deps, owner: String) -> StdResult<PermitNonceResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    Ok(PermitNonceResponse {
+        nonce: PERMIT_NONCES.may_load(deps.storage, &owner)?.unwrap_or_default(),
+    })
+}
+
+pub fn query_balance_hooks(deps: Deps) -> StdResult<BalanceHooksResponse> {
+    let hooks = BALANCE_HOOKS.may_load(deps.storage)?.unwrap_or_default();
+    Ok(BalanceHooksResponse {
+        hooks: hooks
+            .iter()
+            .map(|hook| Ok(deps.api.addr_humanize(hook)?.to_string()))
+            .collect::<StdResult<Vec<String>>>()?,
+    })
+}
+
+/// Run a balance operation of the cw20 implementation, and notify the balance hooks
+/// before the messages of the operation, so that they are up to date for a receiver contract.
+fn with_balance_hooks<E, F>(
+    mut deps: DepsMut,
+    token: &LstToken,
+    from: Option<&str>,
+    to: Option<&str>,
+    amount: Uint128,
+    operation: F,
+) -> Result<Response, E>
+where
+    E: TokenError,
+    F: FnOnce(DepsMut) -> Result<Response, E>,
+{
+    let hooks = balance_hooks(&mut deps, token)?;
+    let reward_contract = REWARD_CONTRACT
+        .may_load(deps.storage)?
+        .map(|reward| deps.api.addr_humanize(&reward))
+        .transpose()?;
+    let res = operation(deps)?;
+    let hook_msgs = balance_hook_messages(&hooks, reward_contract.as_ref(), from, to, amount)?;
+    Ok(Response::new()
+        .add_submessages(hook_msgs)
+        .add_submessages(res.messages)
+        .add_attributes(res.attributes))
+}
+
+/// The balance hooks, resolving the reward contract of a reward-bearing token as the first one.
+fn balance_hooks(deps: &mut DepsMut, token: &LstToken) -> StdResult<Vec<Addr>> {
+    let hooks = match BALANCE_HOOKS.may_load(deps.storage)? {
+        Some(hooks) => hooks,
+        None if token.kind == TokenKind::RewardBearing => {
+            let reward_contract = query_reward_contract(deps, &token.hub_contract)?;
+            let reward_contract = deps.api.addr_canonicalize(reward_contract.as_str())?;
+            let hooks = vec![reward_contract.clone()];
+            BALANCE_HOOKS.save(deps.storage, &hooks)?;
+            REWARD_CONTRACT.save(deps.storage, &reward_contract)?;
+            hooks
+        }
+        None => vec![],
+    };
+    hooks.iter().map(|hook| deps.api.addr_humanize(hook)).collect()
+}
+
+fn check_hook_manager<E: TokenError>(
+    deps: Deps,
+    info: &MessageInfo,
+    token: &LstToken,
+) -> Result<(), E> {
+    if !is_hook_manager(deps, &token.hub_contract, &info.sender)? {
+        return Err(E::unauthorized());
+    }
+    Ok(())
+}
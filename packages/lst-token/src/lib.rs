@@ -0,0 +1,8 @@
+mod contract_tests;
+pub mod handler;
+pub mod querier;
+pub mod state;
+pub mod token;
+
+#[cfg(test)]
+mod testing;
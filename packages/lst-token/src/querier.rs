@@ -0,0 +1,31 @@
+// Copyright 2021 Anchor Protocol. Modified by Lido
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use cosmwasm_std::{to_json_binary, Addr, DepsMut, QueryRequest, StdError, StdResult, WasmQuery};
+
+use basset::hub::{ConfigResponse, QueryMsg as HubQueryMsg};
+
+/// The reward contract registered in the hub, which pushes every later change of it.
+pub fn query_reward_contract(deps: &DepsMut, hub_contract: &Addr) -> StdResult<Addr> {
+    let config: ConfigResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: hub_contract.to_string(),
+        msg: to_json_binary(&HubQueryMsg::Config {})?,
+    }))?;
+
+    let rewards_contract = config
+        .rewards_contract
+        .ok_or_else(|| StdError::generic_err("the reward contract must have been registered"))?;
+
+    deps.api.addr_validate(&rewards_contract)
+}
@@ -0,0 +1,13 @@
+use cosmwasm_std::{Addr, CanonicalAddr};
+use cw_storage_plus::{Item, Map};
+
+/// The contracts notified of the balance changes.
+/// For a reward-bearing token, unset until the first balance change, which registers the reward
+/// contract
+pub const BALANCE_HOOKS: Item<Vec<CanonicalAddr>> = Item::new("balance_hooks");
+
+/// The nonce of the next permit of each owner
+pub const PERMIT_NONCES: Map<&Addr, u64> = Map::new("permit_nonces");
+
+/// The reward contract among the balance hooks, kept to replace it when the hub pushes a new one
+pub const REWARD_CONTRACT: Item<CanonicalAddr> = Item::new("reward_contract");
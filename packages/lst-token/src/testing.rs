@@ -0,0 +1,335 @@
+use crate::handler::{
+    execute_add_balance_hook, execute_burn, execute_burn_from, execute_mint,
+    execute_remove_balance_hook, execute_transfer, execute_update_reward_contract,
+    reply_balance_hook,
+};
+use crate::state::{BALANCE_HOOKS, REWARD_CONTRACT};
+use crate::token::{LstToken, TokenKind};
+use basset::hub::ExecuteMsg::CheckSlashing;
+use basset::token::{BalanceHookMsg, BALANCE_HOOK_GAS_LIMIT, BALANCE_HOOK_REPLY_ID};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, DepsMut, Reply, StdError, SubMsg, SubMsgResult,
+    Uint128, WasmMsg,
+};
+use cw20::{Cw20Coin, Cw20ReceiveMsg, MinterResponse};
+
+const HUB: &str = "hub";
+const REWARD: &str = "reward";
+const GAUGE: &str = "gauge";
+const HOLDER: &str = "addr0000";
+
+fn token(kind: TokenKind) -> LstToken {
+    LstToken {
+        kind,
+        hub_contract: Addr::unchecked(HUB),
+    }
+}
+
+// the reward contract is registered as if it had been resolved through the hub
+fn register_reward_contract(deps: DepsMut) {
+    let reward = deps.api.addr_canonicalize(REWARD).unwrap();
+    BALANCE_HOOKS.save(deps.storage, &vec![reward.clone()]).unwrap();
+    REWARD_CONTRACT.save(deps.storage, &reward).unwrap();
+}
+
+fn hook_message(hook: &str, msg: BalanceHookMsg) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: hook.to_string(),
+        msg: to_json_binary(&msg).unwrap(),
+        funds: vec![],
+    }))
+}
+
+fn increase_balance(address: &str, amount: u128) -> SubMsg {
+    hook_message(
+        REWARD,
+        BalanceHookMsg::IncreaseBalance {
+            address: address.to_string(),
+            amount: Uint128::new(amount),
+        },
+    )
+}
+
+fn decrease_balance(address: &str, amount: u128) -> SubMsg {
+    hook_message(
+        REWARD,
+        BalanceHookMsg::DecreaseBalance {
+            address: address.to_string(),
+            amount: Uint128::new(amount),
+        },
+    )
+}
+
+fn check_slashing() -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: HUB.to_string(),
+        msg: to_json_binary(&CheckSlashing {}).unwrap(),
+        funds: vec![],
+    }))
+}
+
+/// The same suite runs against each cw20 implementation the tokens are built on.
+macro_rules! token_tests {
+    ($name:ident, $cw20:ident) => {
+        mod $name {
+            use super::*;
+            use $cw20::allowances::{
+                execute_burn_from as cw20_burn_from,
+                execute_increase_allowance as cw20_increase_allowance,
+            };
+            use $cw20::contract::{
+                execute_burn as cw20_burn, execute_mint as cw20_mint, execute_send as cw20_send,
+                execute_transfer as cw20_transfer, instantiate as cw20_init,
+            };
+            use $cw20::msg::InstantiateMsg;
+            use $cw20::ContractError;
+
+            fn init(deps: DepsMut) {
+                let msg = InstantiateMsg {
+                    name: "liquid staked sei".to_string(),
+                    symbol: "LST".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![
+                        Cw20Coin {
+                            address: HOLDER.to_string(),
+                            amount: Uint128::new(1000),
+                        },
+                        Cw20Coin {
+                            address: HUB.to_string(),
+                            amount: Uint128::new(1000),
+                        },
+                    ],
+                    mint: Some(MinterResponse {
+                        minter: HUB.to_string(),
+                        cap: None,
+                    }),
+                    marketing: None,
+                };
+                cw20_init(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+            }
+
+            #[test]
+            fn hub_gated_mint_and_burn() {
+                let mut deps = mock_dependencies();
+                init(deps.as_mut());
+                register_reward_contract(deps.as_mut());
+                let token = token(TokenKind::RewardBearing);
+
+                let holder = Addr::unchecked(HOLDER);
+                let res = execute_mint(
+                    deps.as_mut(),
+                    &token,
+                    &holder,
+                    HOLDER,
+                    Uint128::new(10),
+                    |deps| {
+                        let info = mock_info(HOLDER, &[]);
+                        cw20_mint(deps, mock_env(), info, HOLDER.to_string(), Uint128::new(10))
+                    },
+                );
+                assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+                let hub = Addr::unchecked(HUB);
+                let res = execute_mint(
+                    deps.as_mut(),
+                    &token,
+                    &hub,
+                    HOLDER,
+                    Uint128::new(10),
+                    |deps| {
+                        let info = mock_info(HUB, &[]);
+                        cw20_mint(deps, mock_env(), info, HOLDER.to_string(), Uint128::new(10))
+                    },
+                )
+                .unwrap();
+                assert_eq!(res.messages, vec![increase_balance(HOLDER, 10)]);
+
+                let res = execute_burn(deps.as_mut(), &token, &holder, Uint128::new(10), |deps| {
+                    cw20_burn(deps, mock_env(), mock_info(HOLDER, &[]), Uint128::new(10))
+                });
+                assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+
+                // the hub does not ask itself to check slashing
+                let res = execute_burn(deps.as_mut(), &token, &hub, Uint128::new(10), |deps| {
+                    cw20_burn(deps, mock_env(), mock_info(HUB, &[]), Uint128::new(10))
+                })
+                .unwrap();
+                assert_eq!(res.messages, vec![decrease_balance(HUB, 10)]);
+            }
+
+            #[test]
+            fn burn_from_checks_slashing() {
+                let mut deps = mock_dependencies();
+                init(deps.as_mut());
+                register_reward_contract(deps.as_mut());
+                let token = token(TokenKind::RewardBearing);
+
+                let spender = "addr0001".to_string();
+                let info = mock_info(HOLDER, &[]);
+                let amount = Uint128::new(10);
+                let env = mock_env();
+                cw20_increase_allowance(deps.as_mut(), env, info, spender.clone(), amount, None)
+                    .unwrap();
+
+                let holder = Addr::unchecked(HOLDER);
+                let res = execute_burn_from(deps.as_mut(), &token, &holder, amount, |deps| {
+                    let info = mock_info(&spender, &[]);
+                    cw20_burn_from(deps, mock_env(), info, HOLDER.to_string(), amount)
+                })
+                .unwrap();
+                assert_eq!(res.messages, vec![decrease_balance(HOLDER, 10), check_slashing()]);
+            }
+
+            #[test]
+            fn balance_hooks_before_receive() {
+                let mut deps = mock_dependencies();
+                init(deps.as_mut());
+                register_reward_contract(deps.as_mut());
+                let token = token(TokenKind::RewardBearing);
+
+                let contract = "contract0000".to_string();
+                let msg = Binary::from(br#"{"unbond":{}}"#.as_slice());
+                let holder = Addr::unchecked(HOLDER);
+                let res = execute_transfer(
+                    deps.as_mut(),
+                    &token,
+                    &holder,
+                    &contract,
+                    Uint128::new(10),
+                    |deps| {
+                        let info = mock_info(HOLDER, &[]);
+                        let amount = Uint128::new(10);
+                        cw20_send(deps, mock_env(), info, contract.clone(), amount, msg.clone())
+                    },
+                )
+                .unwrap();
+                let receive = Cw20ReceiveMsg {
+                    sender: HOLDER.to_string(),
+                    amount: Uint128::new(10),
+                    msg,
+                };
+                assert_eq!(
+                    res.messages,
+                    vec![
+                        decrease_balance(HOLDER, 10),
+                        increase_balance(&contract, 10),
+                        SubMsg::new(receive.into_cosmos_msg(contract.clone()).unwrap()),
+                    ]
+                );
+            }
+
+            #[test]
+            fn optional_hooks_do_not_block_transfers() {
+                let mut deps = mock_dependencies();
+                init(deps.as_mut());
+                register_reward_contract(deps.as_mut());
+                let token = token(TokenKind::RewardBearing);
+
+                let info = mock_info(HUB, &[]);
+                execute_add_balance_hook::<ContractError>(
+                    deps.as_mut(),
+                    info.clone(),
+                    &token,
+                    GAUGE.to_string(),
+                )
+                .unwrap();
+
+                // the reward contract stays required, the gauge runs with a gas limit
+                let holder = Addr::unchecked(HOLDER);
+                let res = execute_transfer(
+                    deps.as_mut(),
+                    &token,
+                    &holder,
+                    HUB,
+                    Uint128::new(10),
+                    |deps| {
+                        let info = mock_info(HOLDER, &[]);
+                        cw20_transfer(deps, mock_env(), info, HUB.to_string(), Uint128::new(10))
+                    },
+                )
+                .unwrap();
+                let optional = |msg: SubMsg| {
+                    SubMsg::reply_on_error(msg.msg, BALANCE_HOOK_REPLY_ID)
+                        .with_gas_limit(BALANCE_HOOK_GAS_LIMIT)
+                };
+                assert_eq!(
+                    res.messages,
+                    vec![
+                        decrease_balance(HOLDER, 10),
+                        increase_balance(HUB, 10),
+                        optional(hook_message(
+                            GAUGE,
+                            BalanceHookMsg::DecreaseBalance {
+                                address: HOLDER.to_string(),
+                                amount: Uint128::new(10),
+                            },
+                        )),
+                        optional(hook_message(
+                            GAUGE,
+                            BalanceHookMsg::IncreaseBalance {
+                                address: HUB.to_string(),
+                                amount: Uint128::new(10),
+                            },
+                        )),
+                    ]
+                );
+
+                // a failing gauge does not revert the transfer
+                let reply = Reply {
+                    id: BALANCE_HOOK_REPLY_ID,
+                    result: SubMsgResult::Err("out of gas".to_string()),
+                };
+                let res = reply_balance_hook(reply).unwrap();
+                assert!(res.messages.is_empty());
+
+                // the reward contract can only be replaced through the hub
+                let err = execute_remove_balance_hook::<ContractError>(
+                    deps.as_mut(),
+                    info.clone(),
+                    &token,
+                    REWARD.to_string(),
+                )
+                .unwrap_err();
+                assert_eq!(
+                    err,
+                    StdError::generic_err("The reward contract cannot be removed").into()
+                );
+                execute_remove_balance_hook::<ContractError>(
+                    deps.as_mut(),
+                    info,
+                    &token,
+                    GAUGE.to_string(),
+                )
+                .unwrap();
+            }
+
+            #[test]
+            fn value_accruing_without_reward_contract() {
+                let mut deps = mock_dependencies();
+                init(deps.as_mut());
+                let token = token(TokenKind::ValueAccruing);
+
+                // nothing is resolved through the hub, there is no hook to notify
+                let hub = Addr::unchecked(HUB);
+                let res = execute_burn(deps.as_mut(), &token, &hub, Uint128::new(10), |deps| {
+                    cw20_burn(deps, mock_env(), mock_info(HUB, &[]), Uint128::new(10))
+                })
+                .unwrap();
+                assert!(res.messages.is_empty());
+
+                let info = mock_info(HUB, &[]);
+                let res = execute_update_reward_contract::<ContractError>(
+                    deps.as_mut(),
+                    info,
+                    &token,
+                    REWARD.to_string(),
+                );
+                assert!(res.is_err());
+            }
+        }
+    };
+}
+
+token_tests!(cw20_base_token, cw20_base);
+token_tests!(cw20_legacy_token, cw20_legacy);
@@ -0,0 +1,35 @@
+use cosmwasm_std::{Addr, StdError};
+
+/// How the holders of a liquid staking token are rewarded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    /// The holders are paid by the reward contract, which is notified of the balance changes
+    /// before the other balance hooks (bSei).
+    RewardBearing,
+    /// The rewards accrue to the exchange rate of the token (stSei).
+    ValueAccruing,
+}
+
+/// A liquid staking token, only minted and burned by its hub.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LstToken {
+    pub kind: TokenKind,
+    pub hub_contract: Addr,
+}
+
+/// The error of the cw20 implementation a token is built on.
+pub trait TokenError: From<StdError> {
+    fn unauthorized() -> Self;
+}
+
+impl TokenError for cw20_base::ContractError {
+    fn unauthorized() -> Self {
+        cw20_base::ContractError::Unauthorized {}
+    }
+}
+
+impl TokenError for cw20_legacy::ContractError {
+    fn unauthorized() -> Self {
+        cw20_legacy::ContractError::Unauthorized {}
+    }
+}